@@ -0,0 +1,349 @@
+//! 24位 BMP 位图读写
+//!
+//! 与 `Capture` 保存的文件格式一致: 24位色, 无压缩, 行数据从下往上存放, 每行补齐到4字节.
+//! 大漠找图时, 如果图片四个角的颜色相同, 那么这个颜色会被当作透明色, 见 [`Bmp::transparent_color`].
+
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::Path,
+};
+
+use crate::color::Rgb;
+
+/// 文件头(14字节) + BITMAPINFOHEADER(40字节)
+const HEADER_SIZE: usize = 54;
+const INFO_HEADER_SIZE: u32 = 40;
+
+/// 24位位图, 像素按从上到下, 从左到右的顺序存放
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bmp {
+    width: u32,
+    height: u32,
+    pixels: Vec<Rgb>,
+}
+
+impl Bmp {
+    /// 新建一个黑色的位图
+    /// # Panics
+    /// 位图尺寸超出 `usize` 的范围
+    pub fn new(width: u32, height: u32) -> Self {
+        let len = pixel_count(width, height).expect("位图尺寸超出范围");
+        Self {
+            width,
+            height,
+            pixels: vec![Rgb::BLACK; len],
+        }
+    }
+
+    /// 从像素数据构建位图, 像素按从上到下, 从左到右的顺序排列
+    ///
+    /// `pixels` 的长度不等于 `width * height` 时返回 `None`
+    pub fn from_pixels(width: u32, height: u32, pixels: Vec<Rgb>) -> Option<Self> {
+        (pixel_count(width, height) == Some(pixels.len())).then_some(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// 位图宽度
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// 位图高度
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// 所有像素, 按从上到下, 从左到右的顺序排列
+    pub fn pixels(&self) -> &[Rgb] {
+        &self.pixels
+    }
+
+    /// 获取(x,y)的颜色, 越界返回 `None`
+    pub fn get(&self, x: u32, y: u32) -> Option<Rgb> {
+        (x < self.width && y < self.height).then(|| self.pixels[self.index(x, y)])
+    }
+
+    /// 获取(x,y)的颜色
+    /// # Panics
+    /// 坐标越界
+    pub fn pixel(&self, x: u32, y: u32) -> Rgb {
+        self.get(x, y).expect("坐标超出位图范围")
+    }
+
+    /// 设置(x,y)的颜色
+    /// # Panics
+    /// 坐标越界
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: Rgb) {
+        assert!(x < self.width && y < self.height, "坐标超出位图范围");
+        let i = self.index(x, y);
+        self.pixels[i] = color;
+    }
+
+    /// 截取以(x,y)为左上角, 宽`width`高`height`的区域, 超出范围返回 `None`
+    pub fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Option<Bmp> {
+        if !fits(x, width, self.width) || !fits(y, height, self.height) {
+            return None;
+        }
+        let pixels = (y..y + height)
+            .flat_map(|row| {
+                let start = self.index(x, row);
+                self.pixels[start..start + width as usize].iter().copied()
+            })
+            .collect();
+        Some(Bmp {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// 大漠的透明色约定: 四个角的颜色相同时, 这个颜色就是透明色, 找图时会忽略此颜色的像素
+    ///
+    /// 四个角颜色不同(或者位图为空)时返回 `None`
+    pub fn transparent_color(&self) -> Option<Rgb> {
+        let (w, h) = (self.width, self.height);
+        let first = self.get(0, 0)?;
+        [(w - 1, 0), (0, h - 1), (w - 1, h - 1)]
+            .into_iter()
+            .all(|(x, y)| self.pixel(x, y) == first)
+            .then_some(first)
+    }
+
+    /// 从内存中的 BMP 文件数据解析位图
+    ///
+    /// 支持24位无压缩位图, 行顺序可以是从下往上(`Capture` 的格式)或者从上往下(高度为负数)
+    pub fn from_bytes(data: &[u8]) -> io::Result<Self> {
+        let u16_at = |i: usize| u16::from_le_bytes([data[i], data[i + 1]]);
+        let u32_at =
+            |i: usize| u32::from_le_bytes([data[i], data[i + 1], data[i + 2], data[i + 3]]);
+
+        if data.len() < HEADER_SIZE || &data[..2] != b"BM" {
+            return Err(invalid_data("不是 BMP 文件"));
+        }
+        let offset = u32_at(10) as usize;
+        if u32_at(14) < INFO_HEADER_SIZE {
+            return Err(invalid_data("不支持的 BMP 信息头"));
+        }
+        let width = u32_at(18) as i32;
+        let height = u32_at(22) as i32;
+        if u16_at(28) != 24 || u32_at(30) != 0 {
+            return Err(invalid_data("只支持24位无压缩的 BMP 文件"));
+        }
+        if width < 0 {
+            return Err(invalid_data("BMP 宽度为负数"));
+        }
+        if width == 0 || height == 0 {
+            return Err(invalid_data("BMP 宽度或者高度为0"));
+        }
+
+        let (width, bottom_up) = (width as u32, height > 0);
+        let height = height.unsigned_abs();
+        let stride = row_stride(width).ok_or_else(|| invalid_data("BMP 宽度超出范围"))?;
+        let end = stride
+            .checked_mul(height as usize)
+            .and_then(|size| size.checked_add(offset));
+        if end.is_none_or(|end| end > data.len()) {
+            return Err(invalid_data("BMP 像素数据不完整"));
+        }
+
+        let mut pixels = Vec::with_capacity(width as usize * height as usize);
+        for y in 0..height {
+            let row = if bottom_up { height - 1 - y } else { y };
+            let start = offset + row as usize * stride;
+            pixels.extend(
+                data[start..start + width as usize * 3]
+                    .chunks_exact(3)
+                    .map(|bgr| Rgb::new(bgr[2], bgr[1], bgr[0])),
+            );
+        }
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// 从 `reader` 读取 BMP 文件
+    pub fn read(mut reader: impl Read) -> io::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        Self::from_bytes(&data)
+    }
+
+    /// 打开 BMP 文件
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    /// 编码为和 `Capture` 一致的24位 BMP 文件数据
+    ///
+    /// 文件大小超出4GB(BMP 文件头能表示的范围)时返回 [`io::ErrorKind::InvalidInput`]
+    pub fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        let stride = row_stride(self.width).expect("构建位图时已经检查过宽度");
+        let image_size = stride
+            .checked_mul(self.height as usize)
+            .and_then(|size| u32::try_from(size).ok())
+            .filter(|size| size.checked_add(HEADER_SIZE as u32).is_some())
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "位图超出 BMP 文件的大小范围")
+            })?;
+        let mut data = Vec::with_capacity(HEADER_SIZE + image_size as usize);
+
+        // BITMAPFILEHEADER
+        data.extend_from_slice(b"BM");
+        data.extend_from_slice(&(HEADER_SIZE as u32 + image_size).to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&(HEADER_SIZE as u32).to_le_bytes());
+        // BITMAPINFOHEADER
+        data.extend_from_slice(&INFO_HEADER_SIZE.to_le_bytes());
+        data.extend_from_slice(&(self.width as i32).to_le_bytes());
+        data.extend_from_slice(&(self.height as i32).to_le_bytes());
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.extend_from_slice(&24u16.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&image_size.to_le_bytes());
+        data.extend_from_slice(&[0; 16]);
+
+        let padding = stride - self.width as usize * 3;
+        for row in self.pixels.chunks_exact(self.width.max(1) as usize).rev() {
+            for p in row {
+                data.extend_from_slice(&[p.b, p.g, p.r]);
+            }
+            data.extend(std::iter::repeat_n(0, padding));
+        }
+        Ok(data)
+    }
+
+    /// 把位图写入 `writer`
+    pub fn write(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(&self.to_bytes()?)
+    }
+
+    /// 保存为 BMP 文件
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_bytes()?)
+    }
+
+    /// (x,y)在像素数据中的下标
+    fn index(&self, x: u32, y: u32) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+}
+
+/// 像素数量, 像素数量或者每行的字节数超出 `usize` 的范围时返回 `None`
+fn pixel_count(width: u32, height: u32) -> Option<usize> {
+    row_stride(width)?;
+    (width as usize).checked_mul(height as usize)
+}
+
+/// 每行像素数据的字节数, 补齐到4字节, 超出 `usize` 的范围时返回 `None`
+fn row_stride(width: u32) -> Option<usize> {
+    Some((width as usize).checked_mul(3)?.checked_add(3)? & !3)
+}
+
+/// [start, start + len) 是否在 [0, max) 范围内, 不会溢出
+pub(crate) fn fits(start: u32, len: u32, max: u32) -> bool {
+    start.checked_add(len).is_some_and(|end| end <= max)
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 每个像素的颜色都不同
+    fn gradient(width: u32, height: u32) -> Bmp {
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Rgb::new(x as u8 * 10, y as u8 * 20, 0x80)))
+            .collect();
+        Bmp::from_pixels(width, height, pixels).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        for (width, height) in [(1, 1), (2, 3), (3, 2), (4, 4), (5, 1), (7, 5)] {
+            let bmp = gradient(width, height);
+            let data = bmp.to_bytes().unwrap();
+            assert_eq!(Bmp::from_bytes(&data).unwrap(), bmp, "{width}x{height}");
+            let mut written = Vec::new();
+            bmp.write(&mut written).unwrap();
+            assert_eq!(written, data);
+            assert_eq!(Bmp::read(&written[..]).unwrap(), bmp);
+        }
+    }
+
+    #[test]
+    fn padding_and_header() {
+        for (width, stride) in [(1, 4), (2, 8), (3, 12), (4, 12), (5, 16)] {
+            let bmp = gradient(width, 2);
+            let data = bmp.to_bytes().unwrap();
+            assert_eq!(data.len(), HEADER_SIZE + stride * 2, "{width}");
+            let u32_at = |i: usize| u32::from_le_bytes(data[i..i + 4].try_into().unwrap());
+            assert_eq!(u32_at(2) as usize, data.len());
+            assert_eq!(u32_at(10) as usize, HEADER_SIZE);
+            assert_eq!(u32_at(34) as usize, stride * 2);
+            // 第一行是最下面一行, 行尾补0
+            let row = &data[HEADER_SIZE..HEADER_SIZE + stride];
+            let first = bmp.pixel(0, 1);
+            assert_eq!(row[..3], [first.b, first.g, first.r]);
+            assert!(row[width as usize * 3..].iter().all(|&b| b == 0));
+        }
+    }
+
+    #[test]
+    fn top_down() {
+        let bmp = gradient(3, 2);
+        let mut data = bmp.to_bytes().unwrap();
+        // 高度改为负数, 并且交换两行
+        data[22..26].copy_from_slice(&(-2i32).to_le_bytes());
+        let (first, second) = data[HEADER_SIZE..].split_at_mut(12);
+        first.swap_with_slice(second);
+        assert_eq!(Bmp::from_bytes(&data).unwrap(), bmp);
+    }
+
+    #[test]
+    fn invalid_headers() {
+        let data = gradient(3, 2).to_bytes().unwrap();
+        let with = |offset: usize, value: &[u8]| {
+            let mut data = data.clone();
+            data[offset..offset + value.len()].copy_from_slice(value);
+            Bmp::from_bytes(&data).unwrap_err().kind()
+        };
+        assert_eq!(with(0, b"XX"), io::ErrorKind::InvalidData);
+        // 32位
+        assert_eq!(with(28, &32u16.to_le_bytes()), io::ErrorKind::InvalidData);
+        // 宽度为0, 高度很大
+        let mut zero = data.clone();
+        zero[18..22].copy_from_slice(&0u32.to_le_bytes());
+        zero[22..26].copy_from_slice(&i32::MAX.to_le_bytes());
+        assert_eq!(
+            Bmp::from_bytes(&zero).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert_eq!(with(22, &0u32.to_le_bytes()), io::ErrorKind::InvalidData);
+        assert_eq!(with(18, &(-3i32).to_le_bytes()), io::ErrorKind::InvalidData);
+        // 像素数据不完整
+        assert!(Bmp::from_bytes(&data[..data.len() - 1]).is_err());
+        assert!(Bmp::from_bytes(&data[..HEADER_SIZE - 1]).is_err());
+    }
+
+    #[test]
+    fn crop_and_transparent_color() {
+        let mut bmp = Bmp::new(4, 3);
+        assert_eq!(bmp.transparent_color(), Some(Rgb::BLACK));
+        bmp.set_pixel(1, 1, Rgb::new(1, 2, 3));
+        let cropped = bmp.crop(1, 1, 2, 2).unwrap();
+        assert_eq!(cropped.pixel(0, 0), Rgb::new(1, 2, 3));
+        assert_eq!(cropped.transparent_color(), None);
+        assert!(bmp.crop(3, 0, 2, 1).is_none());
+        assert!(bmp.crop(0, 0, u32::MAX, 1).is_none());
+        assert_eq!(Bmp::new(0, 0).transparent_color(), None);
+    }
+}
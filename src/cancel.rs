@@ -0,0 +1,283 @@
+//! 取消和暂停: 多个线程共享的 [`CancelToken`], 用于随时停止或者暂停正在执行的脚本
+//!
+//! 通过 [`Dmsoft::set_cancel_token`] 设置后, 辅助函数(组合键, 输入文本, 点击, 拖放, 轨迹移动, 宏回放和录制)
+//! 中的等待都会检查取消令牌: 暂停时等待不计时, 取消时先调用 [`Dmsoft::release_all`] 弹起所有按住的键,
+//! 再返回 [`Error::Cancelled`].
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::{Dmsoft, Error, KeyMap, Result};
+
+/// 检查取消和暂停的间隔
+const SLICE: Duration = Duration::from_millis(10);
+
+#[derive(Debug, Default)]
+struct State {
+    cancelled: AtomicBool,
+    paused: AtomicBool,
+}
+
+/// 取消令牌, 克隆后共享同一个状态
+/// # Examples
+/// ```
+/// let token = CancelToken::new();
+/// let remote = token.clone();
+/// std::thread::spawn(move || {
+///     std::thread::sleep(Duration::from_secs(5));
+///     remote.cancel();
+/// });
+/// while token.check().is_ok() {
+///     // 执行脚本
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<State>);
+
+impl CancelToken {
+    /// 新建一个没有取消的令牌
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 取消, 之后所有检查都返回 [`Error::Cancelled`]
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// 是否已经取消
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// 暂停, 检查和等待会阻塞直到恢复或者取消
+    pub fn pause(&self) {
+        self.0.paused.store(true, Ordering::SeqCst);
+    }
+
+    /// 恢复
+    pub fn resume(&self) {
+        self.0.paused.store(false, Ordering::SeqCst);
+    }
+
+    /// 切换暂停状态, 返回切换后是否暂停
+    pub fn toggle_pause(&self) -> bool {
+        !self.0.paused.fetch_xor(true, Ordering::SeqCst)
+    }
+
+    /// 是否暂停
+    pub fn is_paused(&self) -> bool {
+        self.0.paused.load(Ordering::SeqCst)
+    }
+
+    /// 暂停时阻塞直到恢复, 已经取消时返回 [`Error::Cancelled`]
+    pub fn check(&self) -> Result<()> {
+        loop {
+            if self.is_cancelled() {
+                return Err(Error::Cancelled);
+            }
+            if !self.is_paused() {
+                return Ok(());
+            }
+            thread::sleep(SLICE);
+        }
+    }
+
+    /// 等待`duration`, 暂停的时间不计入, 取消时立即返回 [`Error::Cancelled`]
+    pub fn sleep(&self, duration: Duration) -> Result<()> {
+        let mut remaining = duration;
+        loop {
+            self.check()?;
+            if remaining.is_zero() {
+                return Ok(());
+            }
+            let slice = remaining.min(SLICE);
+            thread::sleep(slice);
+            remaining -= slice;
+        }
+    }
+}
+
+/// 取消令牌辅助函数
+impl Dmsoft {
+    /// 设置辅助函数中的等待检查的取消令牌, `None` 表示不检查
+    pub fn set_cancel_token(&self, token: Option<CancelToken>) {
+        *self.cancel.write().unwrap() = token;
+    }
+
+    /// 当前设置的取消令牌
+    pub fn cancel_token(&self) -> Option<CancelToken> {
+        self.cancel.read().unwrap().clone()
+    }
+
+    /// 检查取消令牌, 暂停时阻塞直到恢复, 已经取消时弹起所有按住的键并返回 [`Error::Cancelled`]. 没有设置令牌时直接返回
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// dm.set_cancel_token(Some(token.clone()));
+    /// loop {
+    ///     dm.check_cancel()?;
+    ///     // 执行一轮脚本
+    /// }
+    /// ```
+    pub unsafe fn check_cancel(&self) -> Result<()> {
+        match self.cancel_token() {
+            Some(token) => self.release_on_cancel(token.check()),
+            None => Ok(()),
+        }
+    }
+
+    /// 等待`duration`, 设置了取消令牌时同 [`CancelToken::sleep`], 取消时弹起所有按住的键
+    pub unsafe fn wait(&self, duration: Duration) -> Result<()> {
+        match self.cancel_token() {
+            Some(token) => self.release_on_cancel(token.sleep(duration)),
+            None => {
+                thread::sleep(duration);
+                Ok(())
+            }
+        }
+    }
+
+    /// 取消时调用 [`Dmsoft::release_all`], 弹起失败不影响返回的 [`Error::Cancelled`]
+    unsafe fn release_on_cancel(&self, result: Result<()>) -> Result<()> {
+        if let Err(Error::Cancelled) = result {
+            let _ = self.release_all();
+        }
+        result
+    }
+
+    /// 分段调用 `WaitKey` 等待按键按下, 每段之间检查取消令牌
+    ///
+    /// `time_out` 为 `None` 时一直等待. 返回值同 `WaitKey`, 超时返回0
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// println!("按 F1 开始");
+    /// dm.wait_key(KeyMap::KEY_F1, None).unwrap();
+    /// ```
+    pub unsafe fn wait_key(&self, vk: KeyMap, time_out: Option<Duration>) -> Result<i32> {
+        /// 每次调用 `WaitKey` 的最长时间
+        const STEP: Duration = Duration::from_millis(100);
+        let mut remaining = time_out;
+        loop {
+            self.check_cancel()?;
+            let step = remaining.map_or(STEP, |r| r.min(STEP));
+            if step.is_zero() {
+                return Ok(0);
+            }
+            // WaitKey 的0表示一直等待, 至少等待1毫秒
+            let ret = self.WaitKey(vk, step.as_millis().max(1) as i32)?;
+            if ret != 0 {
+                return Ok(ret);
+            }
+            remaining = remaining.map(|r| r - step);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn check_and_toggle() {
+        let token = CancelToken::new();
+        assert!(token.check().is_ok());
+        assert!(token.toggle_pause());
+        assert!(token.is_paused());
+        assert!(!token.toggle_pause());
+        assert!(!token.is_paused());
+        let remote = token.clone();
+        remote.cancel();
+        assert!(token.is_cancelled());
+        assert!(matches!(token.check(), Err(Error::Cancelled)));
+        assert!(matches!(token.sleep(Duration::ZERO), Err(Error::Cancelled)));
+    }
+
+    #[test]
+    fn sleep_full_duration() {
+        let token = CancelToken::new();
+        let start = Instant::now();
+        token.sleep(Duration::from_millis(50)).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn cancel_interrupts_sleep() {
+        let token = CancelToken::new();
+        let remote = token.clone();
+        let canceller = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            remote.cancel();
+        });
+        let start = Instant::now();
+        assert!(matches!(
+            token.sleep(Duration::from_secs(10)),
+            Err(Error::Cancelled)
+        ));
+        assert!(start.elapsed() < Duration::from_secs(2));
+        canceller.join().unwrap();
+    }
+
+    #[test]
+    fn cancel_interrupts_pause() {
+        let token = CancelToken::new();
+        token.pause();
+        let remote = token.clone();
+        let canceller = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            remote.cancel();
+        });
+        let start = Instant::now();
+        assert!(matches!(token.check(), Err(Error::Cancelled)));
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert!(start.elapsed() < Duration::from_secs(2));
+        canceller.join().unwrap();
+    }
+
+    #[test]
+    fn pause_not_counted() {
+        let token = CancelToken::new();
+        let remote = token.clone();
+        let pauser = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            remote.pause();
+            thread::sleep(Duration::from_millis(300));
+            remote.resume();
+        });
+        let start = Instant::now();
+        token.sleep(Duration::from_millis(100)).unwrap();
+        // 100毫秒的等待中插入了300毫秒的暂停
+        assert!(start.elapsed() >= Duration::from_millis(320));
+        pauser.join().unwrap();
+    }
+
+    #[cfg(all(windows, feature = "keymap"))]
+    #[test]
+    #[ignore = "需要注册大漠插件"]
+    fn cancel_releases_held_keys() {
+        use crate::held::Held;
+
+        unsafe {
+            let dm = Dmsoft::new().unwrap();
+            let token = CancelToken::new();
+            dm.set_cancel_token(Some(token.clone()));
+            assert_eq!(dm.KeyDown(KeyMap::KEY_SHIFT).unwrap(), 1);
+            assert_eq!(dm.held_keys(), [Held::Key(16)]);
+            token.cancel();
+            assert!(matches!(
+                dm.wait(Duration::from_secs(10)),
+                Err(Error::Cancelled)
+            ));
+            assert!(dm.held_keys().is_empty());
+        }
+    }
+}
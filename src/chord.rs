@@ -0,0 +1,237 @@
+//! 组合键和按键序列, 比如 "ctrl+shift+a", "alt+f4, enter"
+//!
+//! * 组合键用 '+' 连接, 最后一个为按下的键, 前面的为修饰键
+//! * 多个组合键用 ',' 分隔, 依次执行
+//! * 按键名同 [`KeyMap`] 的 `FromStr`, 不区分大小写. '+' 和 ',' 本身可以写作 "num+" 和 "comma"
+
+use std::{fmt, str::FromStr, time::Duration};
+
+use crate::{check, Dmsoft, KeyMap, Result};
+
+/// 组合键解析失败
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseChordError(String);
+
+impl fmt::Display for ParseChordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "非法的组合键: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseChordError {}
+
+/// 组合键: 依次按住修饰键, 按一下主键, 再倒序弹起修饰键
+/// # Examples
+/// ```
+/// let chord: Chord = "ctrl+shift+a".parse().unwrap();
+/// assert_eq!(chord.modifiers(), &[KeyMap::KEY_CTRL, KeyMap::KEY_SHIFT]);
+/// assert_eq!(chord.key(), KeyMap::KEY_A);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Chord {
+    modifiers: Vec<KeyMap<'static>>,
+    key: KeyMap<'static>,
+}
+
+impl Chord {
+    /// 新建一个组合键
+    pub fn new(modifiers: impl IntoIterator<Item = KeyMap<'static>>, key: KeyMap<'static>) -> Self {
+        Self {
+            modifiers: modifiers.into_iter().collect(),
+            key,
+        }
+    }
+
+    /// 修饰键, 按顺序按住
+    pub fn modifiers(&self) -> &[KeyMap<'static>] {
+        &self.modifiers
+    }
+
+    /// 主键
+    pub fn key(&self) -> KeyMap<'static> {
+        self.key
+    }
+}
+
+impl From<KeyMap<'static>> for Chord {
+    fn from(key: KeyMap<'static>) -> Self {
+        Self::new([], key)
+    }
+}
+
+impl FromStr for Chord {
+    type Err = ParseChordError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let err = || ParseChordError(s.to_owned());
+        let mut keys = split_keep(s, '+')
+            .iter()
+            .map(|name| name.parse::<KeyMap>().map_err(|_| err()))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let key = keys.pop().ok_or_else(err)?;
+        Ok(Self {
+            modifiers: keys,
+            key,
+        })
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for modifier in &self.modifiers {
+            write!(f, "{}+", modifier)?;
+        }
+        self.key.fmt(f)
+    }
+}
+
+/// 按键序列, 依次执行的多个组合键
+/// # Examples
+/// ```
+/// let seq: KeySequence = "alt+f4, enter".parse().unwrap();
+/// assert_eq!(seq.chords().len(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct KeySequence {
+    chords: Vec<Chord>,
+}
+
+impl KeySequence {
+    /// 新建一个空的按键序列
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加一个组合键, 返回自身
+    pub fn with(mut self, chord: impl Into<Chord>) -> Self {
+        self.chords.push(chord.into());
+        self
+    }
+
+    /// 所有组合键
+    pub fn chords(&self) -> &[Chord] {
+        &self.chords
+    }
+}
+
+impl FromIterator<Chord> for KeySequence {
+    fn from_iter<T: IntoIterator<Item = Chord>>(iter: T) -> Self {
+        Self {
+            chords: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl FromStr for KeySequence {
+    type Err = ParseChordError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Err(ParseChordError(s.to_owned()));
+        }
+        split_keep(s, ',')
+            .iter()
+            .map(|chord| chord.parse())
+            .collect()
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, chord) in self.chords.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            chord.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// 按`sep`分割并去除空白, 分割后为空的部分表示分隔符本身, 合并到前一部分(比如 "num+")
+fn split_keep(s: &str, sep: char) -> Vec<String> {
+    let mut parts: Vec<String> = Vec::new();
+    for part in s.split(sep).map(str::trim) {
+        match parts.last_mut() {
+            Some(last) if part.is_empty() => last.push(sep),
+            _ => parts.push(part.to_owned()),
+        }
+    }
+    parts
+}
+
+/// 组合键的执行间隔
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChordTiming {
+    /// 同一个组合键中每次按下和弹起之间的间隔, 默认为30毫秒
+    pub key_delay: Duration,
+    /// 按键序列中两个组合键之间的间隔, 默认为100毫秒
+    pub chord_delay: Duration,
+}
+
+impl Default for ChordTiming {
+    fn default() -> Self {
+        Self {
+            key_delay: Duration::from_millis(30),
+            chord_delay: Duration::from_millis(100),
+        }
+    }
+}
+
+/// 组合键辅助函数
+impl Dmsoft {
+    /// 执行组合键: `KeyDown` 依次按住修饰键, `KeyPress` 按下主键, `KeyUp` 倒序弹起修饰键
+    ///
+    /// 任意一步失败或者被取消时弹起已经按住的修饰键, 并返回错误
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// dm.press_chord(&"ctrl+shift+a".parse().unwrap(), &ChordTiming::default()).unwrap();
+    /// ```
+    pub unsafe fn press_chord(&self, chord: &Chord, timing: &ChordTiming) -> Result<()> {
+        let mut pressed = 0;
+        let mut result = Ok(());
+        for modifier in &chord.modifiers {
+            if let Err(e) = check(self.KeyDown(*modifier), "KeyDown") {
+                result = Err(e);
+                break;
+            }
+            pressed += 1;
+            if let Err(e) = self.wait(timing.key_delay) {
+                result = Err(e);
+                break;
+            }
+        }
+        if result.is_ok() {
+            result = check(self.KeyPress(&chord.key), "KeyPress")
+                .and_then(|_| self.wait(timing.key_delay));
+        }
+        for modifier in chord.modifiers[..pressed].iter().rev() {
+            let up = check(self.KeyUp(*modifier), "KeyUp");
+            if result.is_ok() {
+                result = up.and_then(|_| self.wait(timing.key_delay));
+            }
+        }
+        result
+    }
+
+    /// 依次执行按键序列中的组合键, 遇到失败时停止
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// dm.press_sequence(&"alt+f4, enter".parse().unwrap(), &ChordTiming::default()).unwrap();
+    /// ```
+    pub unsafe fn press_sequence(
+        &self,
+        sequence: &KeySequence,
+        timing: &ChordTiming,
+    ) -> Result<()> {
+        for (i, chord) in sequence.chords.iter().enumerate() {
+            if i > 0 {
+                self.wait(timing.chord_delay)?;
+            }
+            self.press_chord(chord, timing)?;
+        }
+        Ok(())
+    }
+}
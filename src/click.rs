@@ -0,0 +1,220 @@
+//! 组合鼠标操作: 移动到目标再点击, 拖放
+//!
+//! 按下的键通过 [`HeldGuard`](crate::held::HeldGuard) 管理, 中途失败或者 panic 时也会弹起.
+
+use std::time::Duration;
+
+use crate::{
+    check,
+    held::HeldGuard,
+    trajectory::{MoveMethod, Trajectory},
+    Dmsoft, Point, Result,
+};
+
+/// 鼠标按键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum MouseButton {
+    /// 左键
+    #[default]
+    Left,
+    /// 右键
+    Right,
+    /// 中键
+    Middle,
+}
+
+/// 点击和拖放的间隔
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClickTiming {
+    /// 移动到目标后, 按下之前的间隔, 默认为50毫秒
+    pub pre_delay: Duration,
+    /// 按下和弹起之间的间隔, 默认为30毫秒
+    pub hold: Duration,
+    /// 连续点击时两次点击之间的间隔, 默认为80毫秒
+    pub interval: Duration,
+    /// 弹起之后的间隔, 默认为50毫秒
+    pub post_delay: Duration,
+}
+
+impl Default for ClickTiming {
+    fn default() -> Self {
+        Self {
+            pre_delay: Duration::from_millis(50),
+            hold: Duration::from_millis(30),
+            interval: Duration::from_millis(80),
+            post_delay: Duration::from_millis(50),
+        }
+    }
+}
+
+/// 拖放时从起点移动到终点的方式
+#[derive(Debug)]
+pub enum DragPath<'a> {
+    /// `MoveTo` 直接移动到终点
+    Direct,
+    /// 沿直线分`steps`步移动, 每步间隔`interval`
+    Linear {
+        /// 步数
+        steps: u32,
+        /// 每步的间隔
+        interval: Duration,
+    },
+    /// 按轨迹生成器生成的轨迹移动
+    Trajectory(&'a mut Trajectory),
+}
+
+/// 组合鼠标操作辅助函数
+impl Dmsoft {
+    /// 按下鼠标键, 返回的 [`HeldGuard`] 离开作用域时弹起
+    pub unsafe fn button_down_guard(&self, button: MouseButton) -> Result<HeldGuard<'_>> {
+        match button {
+            MouseButton::Left => self.left_down_guard(),
+            MouseButton::Right => self.right_down_guard(),
+            MouseButton::Middle => self.middle_down_guard(),
+        }
+    }
+
+    /// `MoveTo` 移动到`point`, 用`button`点击`count`次
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// if let Some((_, pos)) = dm.FindPic(0,0,2000,2000,&pics,DeltaColor::splat(0x20),0.9,Dir::LeftTop).unwrap() {
+    ///     dm.click_at(pos, MouseButton::Left, 1, &ClickTiming::default()).unwrap();
+    /// }
+    /// ```
+    pub unsafe fn click_at(
+        &self,
+        point: Point,
+        button: MouseButton,
+        count: u32,
+        timing: &ClickTiming,
+    ) -> Result<()> {
+        check(self.MoveTo(point.x, point.y), "MoveTo")?;
+        self.wait(timing.pre_delay)?;
+        for i in 0..count {
+            if i > 0 {
+                self.wait(timing.interval)?;
+            }
+            let guard = self.button_down_guard(button)?;
+            self.wait(timing.hold)?;
+            guard.release()?;
+        }
+        self.wait(timing.post_delay)?;
+        Ok(())
+    }
+
+    /// 移动到`point`, 右键点击一次
+    pub unsafe fn right_click_at(&self, point: Point, timing: &ClickTiming) -> Result<()> {
+        self.click_at(point, MouseButton::Right, 1, timing)
+    }
+
+    /// 移动到`point`, 左键双击. 双击的间隔为 [`ClickTiming::interval`], 需要小于系统的双击间隔
+    pub unsafe fn double_click_at(&self, point: Point, timing: &ClickTiming) -> Result<()> {
+        self.click_at(point, MouseButton::Left, 2, timing)
+    }
+
+    /// 用左键把`from`拖放到`to`
+    ///
+    /// 移动到`from`, 按下左键, 等待`hold_time`后按`path`移动到`to`, 再等待`hold_time`后弹起
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let mut traj = Trajectory::new(1);
+    /// dm.drag(
+    ///     Point::new(100, 100),
+    ///     Point::new(300, 120),
+    ///     DragPath::Trajectory(&mut traj),
+    ///     Duration::from_millis(100),
+    ///     &ClickTiming::default(),
+    /// )
+    /// .unwrap();
+    /// ```
+    pub unsafe fn drag(
+        &self,
+        from: Point,
+        to: Point,
+        path: DragPath,
+        hold_time: Duration,
+        timing: &ClickTiming,
+    ) -> Result<()> {
+        check(self.MoveTo(from.x, from.y), "MoveTo")?;
+        self.wait(timing.pre_delay)?;
+        let guard = self.left_down_guard()?;
+        self.wait(hold_time)?;
+        match path {
+            DragPath::Direct => check(self.MoveTo(to.x, to.y), "MoveTo")?,
+            DragPath::Linear { steps, interval } => {
+                for (i, point) in linear_points(from, to, steps).enumerate() {
+                    if i > 0 {
+                        self.wait(interval)?;
+                    }
+                    check(self.MoveTo(point.x, point.y), "MoveTo")?;
+                }
+            }
+            DragPath::Trajectory(trajectory) => {
+                self.move_along(&trajectory.generate(from, to), MoveMethod::MoveTo)?
+            }
+        }
+        self.wait(hold_time)?;
+        guard.release()?;
+        self.wait(timing.post_delay)?;
+        Ok(())
+    }
+}
+
+/// [`DragPath::Linear`] 经过的点: 从`from`沿直线分`steps`(至少为1)步到`to`, 不包括起点, 最后一个点为`to`
+fn linear_points(from: Point, to: Point, steps: u32) -> impl Iterator<Item = Point> {
+    let steps = steps.max(1);
+    (1..=steps).map(move |i| {
+        let t = i as f64 / steps as f64;
+        let x = from.x + ((to.x - from.x) as f64 * t).round() as i32;
+        let y = from.y + ((to.y - from.y) as f64 * t).round() as i32;
+        Point::new(x, y)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(from: (i32, i32), to: (i32, i32), steps: u32) -> Vec<(i32, i32)> {
+        linear_points(Point::new(from.0, from.1), Point::new(to.0, to.1), steps)
+            .map(|p| (p.x, p.y))
+            .collect()
+    }
+
+    #[test]
+    fn linear_steps() {
+        assert_eq!(
+            points((0, 0), (100, 40), 4),
+            [(25, 10), (50, 20), (75, 30), (100, 40)]
+        );
+        // 反方向, 按四舍五入取整
+        assert_eq!(points((10, 10), (0, 5), 3), [(7, 8), (3, 7), (0, 5)]);
+    }
+
+    #[test]
+    fn linear_ends_at_target() {
+        // 0步按1步处理, 直接移动到终点
+        assert_eq!(points((5, 5), (9, -3), 0), [(9, -3)]);
+        assert_eq!(points((5, 5), (9, -3), 1), [(9, -3)]);
+        for steps in 1..20 {
+            let found = points((-7, 3), (311, -250), steps);
+            assert_eq!(found.len(), steps as usize);
+            assert_eq!(found.last(), Some(&(311, -250)));
+        }
+        // 起点和终点相同
+        assert_eq!(points((1, 2), (1, 2), 3), [(1, 2); 3]);
+    }
+
+    #[test]
+    fn default_timing() {
+        let timing = ClickTiming::default();
+        assert_eq!(timing.pre_delay, Duration::from_millis(50));
+        assert_eq!(timing.hold, Duration::from_millis(30));
+        assert_eq!(timing.interval, Duration::from_millis(80));
+        assert_eq!(timing.post_delay, Duration::from_millis(50));
+    }
+}
@@ -0,0 +1,236 @@
+//! 大漠颜色格式
+
+use std::{fmt, str::FromStr};
+
+/// RGB 颜色, 字符串格式为大漠使用的 "RRGGBB"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgb {
+    /// 红
+    pub r: u8,
+    /// 绿
+    pub g: u8,
+    /// 蓝
+    pub b: u8,
+}
+
+impl Rgb {
+    /// 黑色 "000000"
+    pub const BLACK: Rgb = Rgb::new(0, 0, 0);
+    /// 白色 "ffffff"
+    pub const WHITE: Rgb = Rgb::new(255, 255, 255);
+
+    /// 新建一个 RGB 颜色
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+}
+
+/// 颜色字符串解析失败
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseColorError(String);
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "非法的颜色字符串: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+/// 解析 "RRGGBB" 格式的16进制颜色, 不区分大小写
+impl FromStr for Rgb {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseColorError(s.to_owned());
+        if s.len() != 6 || !s.is_ascii() {
+            return Err(err());
+        }
+        let channel = |i: usize| u8::from_str_radix(&s[i..i + 2], 16).map_err(|_| err());
+        Ok(Rgb::new(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+/// 输出 "rrggbb" 格式(小写, 和 `GetColor` 的返回一致)
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// 偏色, 每个通道允许的最大误差, 字符串格式为 "DRDGDB", 比如 "101010"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DeltaColor(pub Rgb);
+
+impl DeltaColor {
+    /// 不允许偏色 "000000"
+    pub const ZERO: DeltaColor = DeltaColor(Rgb::BLACK);
+
+    /// 新建一个偏色
+    pub const fn new(dr: u8, dg: u8, db: u8) -> Self {
+        Self(Rgb::new(dr, dg, db))
+    }
+
+    /// 三个通道使用相同的偏色
+    pub const fn splat(delta: u8) -> Self {
+        Self::new(delta, delta, delta)
+    }
+
+    /// `a` 和 `b` 每个通道的差值都不超过偏色时返回 `true`
+    pub fn matches(&self, a: Rgb, b: Rgb) -> bool {
+        a.r.abs_diff(b.r) <= self.0.r
+            && a.g.abs_diff(b.g) <= self.0.g
+            && a.b.abs_diff(b.b) <= self.0.b
+    }
+}
+
+impl FromStr for DeltaColor {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(DeltaColor)
+    }
+}
+
+impl fmt::Display for DeltaColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// 颜色格式中的一项
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorItem {
+    /// "RRGGBB-DRDGDB": 颜色和偏色, 偏色可以省略
+    Rgb(Rgb, DeltaColor),
+    /// "#GG-DG": 灰度和灰度偏差(都是16进制), 灰度为 (R*299 + G*587 + B*114) / 1000
+    Gray(u8, u8),
+}
+
+impl ColorItem {
+    /// `color` 符合这一项时返回 `true`
+    pub fn matches(&self, color: Rgb) -> bool {
+        match *self {
+            ColorItem::Rgb(rgb, delta) => delta.matches(color, rgb),
+            ColorItem::Gray(gray, delta) => gray_of(color).abs_diff(gray) <= delta,
+        }
+    }
+}
+
+fn gray_of(color: Rgb) -> u8 {
+    ((color.r as u32 * 299 + color.g as u32 * 587 + color.b as u32 * 114) / 1000) as u8
+}
+
+impl FromStr for ColorItem {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseColorError(s.to_owned());
+        let (color, delta) = s.split_once('-').unwrap_or((s, ""));
+        match color.strip_prefix('#') {
+            Some(gray) => {
+                let channel = |v: &str| u8::from_str_radix(v, 16).map_err(|_| err());
+                let delta = if delta.is_empty() { 0 } else { channel(delta)? };
+                Ok(ColorItem::Gray(channel(gray)?, delta))
+            }
+            None => {
+                let delta = if delta.is_empty() {
+                    DeltaColor::ZERO
+                } else {
+                    delta.parse().map_err(|_| err())?
+                };
+                Ok(ColorItem::Rgb(color.parse().map_err(|_| err())?, delta))
+            }
+        }
+    }
+}
+
+impl fmt::Display for ColorItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorItem::Rgb(rgb, delta) => write!(f, "{}-{}", rgb, delta),
+            ColorItem::Gray(gray, delta) => write!(f, "#{:02x}-{:02x}", gray, delta),
+        }
+    }
+}
+
+/// 识字使用的颜色格式, 比如 "ffffff-101010|ff0000-000000", "#c0-20", "b@000000-000000"
+///
+/// * 多个颜色用 '|' 分隔, 符合任意一个颜色的像素为字的点
+/// * "b@" 开头表示这些颜色是背景色, 不符合任何一个颜色的像素为字的点
+/// # Examples
+/// ```
+/// let spec: ColorSpec = "ffffff-101010|#c0-20".parse().unwrap();
+/// assert!(spec.matches(Rgb::new(0xf8, 0xf8, 0xf8)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ColorSpec {
+    items: Vec<ColorItem>,
+    background: bool,
+}
+
+impl ColorSpec {
+    /// 新建一个空的颜色格式
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加一个颜色, 返回自身
+    pub fn with(mut self, item: ColorItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// 设置是否为背景色模式("b@"), 返回自身
+    pub fn background(mut self, background: bool) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// 所有颜色
+    pub fn items(&self) -> &[ColorItem] {
+        &self.items
+    }
+
+    /// 是否为背景色模式
+    pub fn is_background(&self) -> bool {
+        self.background
+    }
+
+    /// `color` 是字的点时返回 `true`
+    pub fn matches(&self, color: Rgb) -> bool {
+        self.items.iter().any(|item| item.matches(color)) != self.background
+    }
+}
+
+impl FromStr for ColorSpec {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (body, background) = match s.strip_prefix("b@") {
+            Some(body) => (body, true),
+            None => (s, false),
+        };
+        let items = body
+            .split('|')
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map_err(|_| ParseColorError(s.to_owned()))?;
+        Ok(Self { items, background })
+    }
+}
+
+impl fmt::Display for ColorSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.background {
+            f.write_str("b@")?;
+        }
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                f.write_str("|")?;
+            }
+            item.fmt(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,290 @@
+//! 字库管理: 字库序号, 字体类别, 字库加载和切换
+//!
+//! [`DictManager`] 只记录通过它加载的字库, 不记录当前使用的字库序号:
+//! [`DictManager::active`] 和 [`DictGuard`] 每次都通过 `GetNowDict` 向插件查询.
+
+use std::{collections::BTreeMap, fmt, ops::BitOr};
+
+use crate::{check, Dmsoft, Result};
+
+/// 字库序号, 取值范围0-99
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct DictSlot(u8);
+
+impl DictSlot {
+    /// 最大的字库序号 99
+    pub const MAX: DictSlot = DictSlot(99);
+
+    /// 新建一个字库序号, 超出0-99时返回 `None`
+    pub const fn new(index: i32) -> Option<Self> {
+        if index >= 0 && index <= Self::MAX.0 as i32 {
+            Some(Self(index as u8))
+        } else {
+            None
+        }
+    }
+
+    /// 字库序号的数值
+    pub const fn index(self) -> i32 {
+        self.0 as i32
+    }
+
+    /// 所有字库序号 0-99
+    pub fn all() -> impl Iterator<Item = DictSlot> {
+        (0..=Self::MAX.0).map(DictSlot)
+    }
+}
+
+impl fmt::Display for DictSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// 系统字体类别, 可以用 `|` 组合
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FontFlags(i32);
+
+impl FontFlags {
+    /// 0: 正常字体
+    pub const NORMAL: FontFlags = FontFlags(0);
+    /// 1: 粗体
+    pub const BOLD: FontFlags = FontFlags(1);
+    /// 2: 斜体
+    pub const ITALIC: FontFlags = FontFlags(2);
+    /// 4: 下划线
+    pub const UNDERLINE: FontFlags = FontFlags(4);
+    /// 8: 删除线
+    pub const STRIKEOUT: FontFlags = FontFlags(8);
+
+    /// 传给插件的数值
+    pub const fn bits(self) -> i32 {
+        self.0
+    }
+
+    /// 包含`other`中的所有类别时返回 `true`
+    pub const fn contains(self, other: FontFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for FontFlags {
+    type Output = FontFlags;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        FontFlags(self.0 | rhs.0)
+    }
+}
+
+/// 字库的来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DictSource<'a> {
+    /// `SetDict` 加载的字库文件
+    File(String),
+    /// `SetDictMem` 加载的内存字库
+    Mem(&'a [u8]),
+}
+
+/// 字库管理器, 记录每个字库序号加载的字库, 离开作用域时调用 `ClearDict` 清空所有加载过的字库
+///
+/// 内存字库的数据借用到管理器销毁为止, 保证插件使用期间数据有效.
+/// # Examples
+/// ```
+/// static DICT: &[u8] = include_bytes!("num.txt");
+/// let dm = Dmsoft::new();
+/// let mut dicts = dm.dict_manager();
+/// let main = DictSlot::new(0).unwrap();
+/// let num = DictSlot::new(1).unwrap();
+/// dicts.load_file(main, "main.txt").unwrap();
+/// dicts.load_mem(num, DICT).unwrap();
+/// dicts.use_dict(main).unwrap();
+/// {
+///     let _guard = dicts.scoped(num).unwrap();
+///     let s = dm.Ocr(0,0,2000,2000,"ffffff-000000",1.0).unwrap();
+///     // _guard 离开作用域时切换回 main
+/// }
+/// ```
+#[derive(Debug)]
+pub struct DictManager<'a> {
+    dm: &'a Dmsoft,
+    slots: BTreeMap<DictSlot, DictSource<'a>>,
+}
+
+impl<'a> DictManager<'a> {
+    /// 调用 `SetDict` 把字库文件加载到`slot`
+    pub unsafe fn load_file(&mut self, slot: DictSlot, file: &str) -> Result<()> {
+        check(self.dm.SetDict(slot, file), "SetDict")?;
+        self.slots.insert(slot, DictSource::File(file.to_owned()));
+        Ok(())
+    }
+
+    /// 调用 `SetDictMem` 把内存中的字库加载到`slot`, `dict` 需要在管理器销毁之前一直有效
+    pub unsafe fn load_mem(&mut self, slot: DictSlot, dict: &'a [u8]) -> Result<()> {
+        check(self.dm.SetDictMem(slot, dict), "SetDictMem")?;
+        self.slots.insert(slot, DictSource::Mem(dict));
+        Ok(())
+    }
+
+    /// 调用 `ClearDict` 清空`slot`
+    pub unsafe fn unload(&mut self, slot: DictSlot) -> Result<()> {
+        check(self.dm.ClearDict(slot), "ClearDict")?;
+        self.slots.remove(&slot);
+        Ok(())
+    }
+
+    /// `slot` 加载的字库, 没有通过管理器加载时返回 `None`
+    pub fn source(&self, slot: DictSlot) -> Option<&DictSource<'a>> {
+        self.slots.get(&slot)
+    }
+
+    /// 所有通过管理器加载的字库
+    pub fn slots(&self) -> impl Iterator<Item = (DictSlot, &DictSource<'a>)> {
+        self.slots.iter().map(|(slot, source)| (*slot, source))
+    }
+
+    /// 调用 `AddDict` 给`slot`添加一条字库信息
+    pub unsafe fn add(&self, slot: DictSlot, dict_info: &str) -> Result<()> {
+        check(self.dm.AddDict(slot, dict_info), "AddDict")
+    }
+
+    /// 调用 `SaveDict` 把`slot`保存到文件
+    pub unsafe fn save(&self, slot: DictSlot, file: &str) -> Result<()> {
+        check(self.dm.SaveDict(slot, file), "SaveDict")
+    }
+
+    /// 当前使用的字库, 每次调用 `GetNowDict` 查询
+    pub unsafe fn active(&self) -> Result<DictSlot> {
+        self.dm.GetNowDict()
+    }
+
+    /// 调用 `UseDict` 切换当前使用的字库
+    pub unsafe fn use_dict(&self, slot: DictSlot) -> Result<()> {
+        use_dict(self.dm, slot)
+    }
+
+    /// 临时切换到`slot`, 返回的 [`DictGuard`] 离开作用域时切换回之前使用的字库
+    pub unsafe fn scoped(&self, slot: DictSlot) -> Result<DictGuard<'a>> {
+        self.dm.use_dict_scoped(slot)
+    }
+
+    /// `slot` 中所有的字库条目(`GetDictCount` + `GetDict`)
+    pub unsafe fn entries(&self, slot: DictSlot) -> Result<Vec<String>> {
+        (0..self.dm.GetDictCount(slot)?)
+            .map(|i| self.dm.GetDict(slot, i))
+            .collect()
+    }
+}
+
+impl Drop for DictManager<'_> {
+    fn drop(&mut self) {
+        for slot in self.slots.keys() {
+            unsafe {
+                let _ = self.dm.ClearDict(*slot);
+            }
+        }
+    }
+}
+
+/// 临时切换的字库, 离开作用域时调用 `UseDict` 切换回之前使用的字库
+#[derive(Debug)]
+pub struct DictGuard<'a> {
+    dm: &'a Dmsoft,
+    previous: DictSlot,
+}
+
+impl DictGuard<'_> {
+    /// 切换之前使用的字库
+    pub fn previous(&self) -> DictSlot {
+        self.previous
+    }
+}
+
+impl Drop for DictGuard<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self.dm.UseDict(self.previous);
+        }
+    }
+}
+
+unsafe fn use_dict(dm: &Dmsoft, slot: DictSlot) -> Result<()> {
+    check(dm.UseDict(slot), "UseDict")
+}
+
+/// 字库辅助函数
+impl Dmsoft {
+    /// 新建一个字库管理器
+    pub fn dict_manager(&self) -> DictManager<'_> {
+        DictManager {
+            dm: self,
+            slots: BTreeMap::new(),
+        }
+    }
+
+    /// 临时切换到`slot`, 返回的 [`DictGuard`] 离开作用域时切换回之前使用的字库
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let guard = dm.use_dict_scoped(DictSlot::new(1).unwrap()).unwrap();
+    /// let s = dm.Ocr(0,0,2000,2000,"ffffff-000000",1.0).unwrap();
+    /// drop(guard);
+    /// ```
+    pub unsafe fn use_dict_scoped(&self, slot: DictSlot) -> Result<DictGuard<'_>> {
+        let previous = self.GetNowDict()?;
+        use_dict(self, slot)?;
+        Ok(DictGuard { dm: self, previous })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slot_range() {
+        assert_eq!(DictSlot::new(0), Some(DictSlot::default()));
+        assert_eq!(DictSlot::new(99), Some(DictSlot::MAX));
+        assert_eq!(DictSlot::new(100), None);
+        assert_eq!(DictSlot::new(-1), None);
+        assert_eq!(DictSlot::new(42).unwrap().index(), 42);
+        assert_eq!(DictSlot::new(7).unwrap().to_string(), "7");
+        let all: Vec<i32> = DictSlot::all().map(DictSlot::index).collect();
+        assert_eq!(all, (0..=99).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn font_flags() {
+        let flags = FontFlags::BOLD | FontFlags::UNDERLINE;
+        assert_eq!(flags.bits(), 5);
+        assert!(flags.contains(FontFlags::BOLD));
+        assert!(flags.contains(FontFlags::NORMAL));
+        assert!(!flags.contains(FontFlags::ITALIC));
+        assert_eq!(FontFlags::default(), FontFlags::NORMAL);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    #[ignore = "需要注册大漠插件"]
+    fn plugin_manager() {
+        static DICT: &[u8] = include_bytes!("../tests/fixtures/ocr/dict.txt");
+        let main = DictSlot::new(0).unwrap();
+        let num = DictSlot::new(1).unwrap();
+
+        unsafe {
+            let dm = Dmsoft::new().unwrap();
+            let mut dicts = dm.dict_manager();
+            dicts.load_mem(num, DICT).unwrap();
+            assert_eq!(dicts.source(num), Some(&DictSource::Mem(DICT)));
+            assert_eq!(dicts.entries(num).unwrap().len(), 11);
+            dicts.use_dict(main).unwrap();
+            {
+                let guard = dicts.scoped(num).unwrap();
+                assert_eq!(guard.previous(), main);
+                assert_eq!(dicts.active().unwrap(), num);
+            }
+            assert_eq!(dicts.active().unwrap(), main);
+            dicts.unload(num).unwrap();
+            assert_eq!(dicts.slots().count(), 0);
+        }
+    }
+}
@@ -0,0 +1,756 @@
+//! 大漠字库文件(.txt)的解析和生成
+//!
+//! 字库文件每行是一个字符条目, 格式为 `点阵$字符$字符信息$高度`, 比如 `F999F$口$0.0.14$4`
+//! * 点阵: 16进制字符串, 按列从左到右, 每列从上到下, 每个点占1位, 末尾补0到4位对齐
+//! * 字符: 识别出的字符串, 可以是多个字符
+//! * 字符信息: 一般为 "0.0.点数", 也可以只有 "0.0"
+//! * 高度: 点阵的高度, 点阵的宽度由点阵位数除以高度得到
+//!
+//! 大漠综合工具保存的字库文件一般为 GBK 编码, 读取时会自动识别 UTF-8 和 GBK, 保存时使用读取时的编码.
+//!
+//! 比如大漠文档中的 `FFF00A7D49292524A7D402805FFC$回$0.0.54$11` 是宽10高11, 共54个点的"回"字,
+//! `081101BF8020089FD10A21443F85038$记$0.0$11` 宽11高11, 末尾补了3个0位.
+
+use std::{collections::HashSet, fmt, fs, io, path::Path, str::FromStr};
+
+/// 字符点阵, 按从上到下, 从左到右的顺序存放
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Glyph {
+    width: u32,
+    height: u32,
+    bits: Vec<bool>,
+}
+
+impl Glyph {
+    /// 新建一个空白点阵
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            bits: vec![false; width as usize * height as usize],
+        }
+    }
+
+    /// 从字符串构建点阵, 每个字符串为一行, `'#'` 和 `'1'` 表示有点, 其他字符表示无点
+    ///
+    /// 宽度为最长一行的字符数
+    pub fn from_rows<S: AsRef<str>>(rows: &[S]) -> Self {
+        let width = rows
+            .iter()
+            .map(|row| row.as_ref().chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut glyph = Glyph::new(width as u32, rows.len() as u32);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.as_ref().chars().enumerate() {
+                glyph.set(x as u32, y as u32, matches!(c, '#' | '1'));
+            }
+        }
+        glyph
+    }
+
+    /// 点阵宽度
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// 点阵高度
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// (x,y)是否有点, 越界返回 `false`
+    pub fn get(&self, x: u32, y: u32) -> bool {
+        x < self.width && y < self.height && self.bits[self.index(x, y)]
+    }
+
+    /// 设置(x,y)是否有点
+    /// # Panics
+    /// 坐标越界
+    pub fn set(&mut self, x: u32, y: u32, value: bool) {
+        assert!(x < self.width && y < self.height, "坐标超出点阵范围");
+        let i = self.index(x, y);
+        self.bits[i] = value;
+    }
+
+    /// (x,y)在点阵数据中的下标
+    fn index(&self, x: u32, y: u32) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+
+    /// 点的数量
+    pub fn count_ones(&self) -> usize {
+        self.bits.iter().filter(|&&bit| bit).count()
+    }
+
+    /// 字库文件中的16进制点阵字符串
+    pub fn to_hex(&self) -> String {
+        let bits: Vec<bool> = (0..self.width)
+            .flat_map(|x| (0..self.height).map(move |y| (x, y)))
+            .map(|(x, y)| self.get(x, y))
+            .collect();
+        bits.chunks(4)
+            .map(|nibble| {
+                let v = nibble
+                    .iter()
+                    .chain(std::iter::repeat(&false))
+                    .take(4)
+                    .fold(0, |v, &bit| v << 1 | bit as u32);
+                char::from_digit(v, 16).unwrap().to_ascii_uppercase()
+            })
+            .collect()
+    }
+
+    /// 从16进制点阵字符串和高度解析点阵, 字符串不是合法的16进制或者高度为0时返回 `None`
+    pub fn from_hex(hex: &str, height: u32) -> Option<Self> {
+        if height == 0 {
+            return None;
+        }
+        let bits = hex
+            .chars()
+            .map(|c| c.to_digit(16))
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .flat_map(|v| (0..4).rev().map(move |i| v >> i & 1 == 1))
+            .collect::<Vec<_>>();
+        let width = bits.len() as u32 / height;
+        let mut glyph = Glyph::new(width, height);
+        for x in 0..width {
+            for y in 0..height {
+                glyph.set(x, y, bits[(x * height + y) as usize]);
+            }
+        }
+        Some(glyph)
+    }
+}
+
+/// 每行一个字符串, `'#'` 表示有点, `'.'` 表示无点
+impl fmt::Display for Glyph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                f.write_str(if self.get(x, y) { "#" } else { "." })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// 字库中的一个字符条目
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DictEntry {
+    /// 16进制点阵字符串
+    pub bits: String,
+    /// 字符
+    pub text: String,
+    /// 字符信息, 一般为 "0.0.点数"
+    pub info: String,
+    /// 点阵高度
+    pub height: u32,
+}
+
+impl DictEntry {
+    /// 从点阵构建字符条目
+    pub fn new(text: impl Into<String>, glyph: &Glyph) -> Self {
+        Self {
+            bits: glyph.to_hex(),
+            text: text.into(),
+            info: format!("0.0.{}", glyph.count_ones()),
+            height: glyph.height(),
+        }
+    }
+
+    /// 解码点阵, 点阵字符串不合法时返回 `None`
+    pub fn glyph(&self) -> Option<Glyph> {
+        Glyph::from_hex(&self.bits, self.height)
+    }
+
+    /// 点阵宽度
+    pub fn width(&self) -> u32 {
+        (self.bits.len() as u32 * 4)
+            .checked_div(self.height)
+            .unwrap_or(0)
+    }
+
+    /// 点阵相同(点阵字符串和高度都相同)时返回 `true`
+    pub fn same_glyph(&self, other: &DictEntry) -> bool {
+        self.height == other.height && self.bits.eq_ignore_ascii_case(&other.bits)
+    }
+}
+
+/// 字库解析失败的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseDictErrorKind {
+    /// 不是 `点阵$字符$字符信息$高度` 格式
+    Entry,
+    /// 既不是合法的 UTF-8 也不是合法的 GBK 编码
+    Encoding,
+}
+
+/// 字库解析失败
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDictError {
+    /// 出错的行号(从1开始)
+    pub line: usize,
+    /// 出错的行, 编码错误时无法解码的字节被替换为 '\u{FFFD}'
+    pub content: String,
+    /// 失败的原因
+    pub kind: ParseDictErrorKind,
+}
+
+impl fmt::Display for ParseDictError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseDictErrorKind::Entry => {
+                write!(f, "第{}行不是合法的字库条目: {:?}", self.line, self.content)
+            }
+            ParseDictErrorKind::Encoding => {
+                write!(
+                    f,
+                    "第{}行不是合法的 UTF-8 或者 GBK 编码: {:?}",
+                    self.line, self.content
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseDictError {}
+
+/// 解析 `点阵$字符$字符信息$高度`, 字符本身可以是 '$'
+///
+/// 点阵为空, 不是16进制或者高度为0时返回错误
+impl FromStr for DictEntry {
+    type Err = ParseDictError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseDictError {
+            line: 1,
+            content: s.to_owned(),
+            kind: ParseDictErrorKind::Entry,
+        };
+        let (bits, rest) = s.split_once('$').ok_or_else(err)?;
+        let mut it = rest.rsplitn(3, '$');
+        let height = it.next().ok_or_else(err)?;
+        let info = it.next().ok_or_else(err)?;
+        let text = it.next().ok_or_else(err)?;
+        let height = height.trim().parse().map_err(|_| err())?;
+        if height == 0 || bits.is_empty() || !bits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(err());
+        }
+        Ok(Self {
+            bits: bits.to_owned(),
+            text: text.to_owned(),
+            info: info.to_owned(),
+            height,
+        })
+    }
+}
+
+impl fmt::Display for DictEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}${}${}${}",
+            self.bits, self.text, self.info, self.height
+        )
+    }
+}
+
+/// 字库文件的编码
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DictEncoding {
+    /// GBK 编码, 大漠综合工具保存的格式
+    #[default]
+    Gbk,
+    /// UTF-8 编码
+    Utf8,
+}
+
+/// 条目所在的行在文件中的布局, 保存时原样写回
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Line {
+    /// 条目之前的空行, 包括换行符
+    blank_before: String,
+    /// 读取时这一行的原始内容, 新添加的条目为 `None`
+    raw: Option<String>,
+    /// 行尾的换行符, 只有文件的最后一行可以为空
+    newline: &'static str,
+}
+
+/// 字库, 条目按文件中的顺序排列
+///
+/// 读取的文件保存时是无损的: UTF-8 的 BOM, 空行, 每一行的换行符和文件末尾是否有换行符都会保留.
+/// 新添加的条目使用文件中第一个换行符, 新建的字库使用 "\r\n"
+/// # Examples
+/// ```
+/// let mut dict = Dict::open("main.txt").unwrap();
+/// let other = Dict::open("other.txt").unwrap();
+/// dict.merge(other);
+/// dict.dedupe();
+/// dict.save("main.txt").unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dict {
+    entries: Vec<DictEntry>,
+    /// 和 `entries` 一一对应
+    lines: Vec<Line>,
+    /// 最后一个条目之后的空行
+    trailer: String,
+    encoding: DictEncoding,
+    bom: bool,
+    newline: &'static str,
+}
+
+impl Dict {
+    /// 新建一个空字库, 保存时使用 GBK 编码和 "\r\n" 换行
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            lines: Vec::new(),
+            trailer: String::new(),
+            encoding: DictEncoding::Gbk,
+            bom: false,
+            newline: "\r\n",
+        }
+    }
+
+    /// 所有条目
+    pub fn entries(&self) -> &[DictEntry] {
+        &self.entries
+    }
+
+    /// 条目数量
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 字库为空时返回 `true`
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 遍历所有条目
+    pub fn iter(&self) -> std::slice::Iter<'_, DictEntry> {
+        self.entries.iter()
+    }
+
+    /// 保存时使用的编码
+    pub fn encoding(&self) -> DictEncoding {
+        self.encoding
+    }
+
+    /// 设置保存时使用的编码
+    pub fn set_encoding(&mut self, encoding: DictEncoding) {
+        self.encoding = encoding;
+    }
+
+    /// 读取的文件是否有 UTF-8 的 BOM, 使用 UTF-8 编码保存时会写回
+    pub fn has_bom(&self) -> bool {
+        self.bom
+    }
+
+    /// 设置使用 UTF-8 编码保存时是否写入 BOM
+    pub fn set_bom(&mut self, bom: bool) {
+        self.bom = bom;
+    }
+
+    /// 第一个字符为`text`的条目
+    pub fn find(&self, text: &str) -> Option<&DictEntry> {
+        self.entries.iter().find(|entry| entry.text == text)
+    }
+
+    /// 在末尾添加一个条目
+    pub fn add(&mut self, entry: DictEntry) {
+        let newline = match self.lines.last_mut() {
+            // 原来的最后一行没有换行符时, 新的最后一行同样没有换行符
+            Some(last) if last.newline.is_empty() => {
+                last.newline = self.newline;
+                ""
+            }
+            _ => self.newline,
+        };
+        self.entries.push(entry);
+        self.lines.push(Line {
+            blank_before: String::new(),
+            raw: None,
+            newline,
+        });
+    }
+
+    /// 删除所有字符为`text`的条目, 返回删除的数量
+    pub fn remove(&mut self, text: &str) -> usize {
+        self.retain(|entry| entry.text != text)
+    }
+
+    /// 把`other`中的条目添加到末尾, 已经存在的条目(点阵, 字符和高度都相同)会被跳过, 返回添加的数量
+    pub fn merge(&mut self, other: Dict) -> usize {
+        let len = self.entries.len();
+        for entry in other.entries {
+            if !self
+                .entries
+                .iter()
+                .any(|e| e.same_glyph(&entry) && e.text == entry.text)
+            {
+                self.add(entry);
+            }
+        }
+        self.entries.len() - len
+    }
+
+    /// 删除点阵重复的条目(同一个点阵只能识别为一个字符), 保留第一个, 返回删除的数量
+    pub fn dedupe(&mut self) -> usize {
+        let mut seen = HashSet::new();
+        self.retain(|entry| seen.insert((entry.height, entry.bits.to_ascii_uppercase())))
+    }
+
+    /// 只保留`keep`返回 `true` 的条目, 被删除条目之前的空行一起删除, 返回删除的数量
+    fn retain(&mut self, mut keep: impl FnMut(&DictEntry) -> bool) -> usize {
+        let len = self.entries.len();
+        let no_final_newline = self
+            .lines
+            .last()
+            .is_some_and(|line| line.newline.is_empty());
+        let kept: Vec<bool> = self.entries.iter().map(&mut keep).collect();
+        let mut it = kept.iter();
+        self.entries.retain(|_| *it.next().unwrap());
+        let mut it = kept.iter();
+        self.lines.retain(|_| *it.next().unwrap());
+        if let (Some(last), true) = (self.lines.last_mut(), no_final_newline) {
+            last.newline = "";
+        }
+        len - self.entries.len()
+    }
+
+    /// 从文件数据解析字库, 自动识别 UTF-8(可以有 BOM) 和 GBK 编码, 没有 BOM 的纯 ASCII 文件当作 GBK
+    ///
+    /// 既不是合法的 UTF-8 也不是合法的 GBK 时返回错误, 而不是替换为 '\u{FFFD}'
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ParseDictError> {
+        let (data, bom) = match data.strip_prefix(b"\xEF\xBB\xBF") {
+            Some(data) => (data, true),
+            None => (data, false),
+        };
+        let (text, encoding) = match std::str::from_utf8(data) {
+            // 纯 ASCII 的文件两种编码都一样, 按大漠默认的 GBK 处理, 之后添加的汉字也会按 GBK 保存
+            Ok(text) if !bom && text.is_ascii() => (text.into(), DictEncoding::Gbk),
+            Ok(text) => (text.into(), DictEncoding::Utf8),
+            Err(_) if !bom => (decode_gbk(data)?, DictEncoding::Gbk),
+            Err(e) => return Err(encoding_error(data, e.valid_up_to())),
+        };
+        let mut dict: Dict = text.parse()?;
+        dict.encoding = encoding;
+        dict.bom = bom;
+        Ok(dict)
+    }
+
+    /// 编码为文件数据
+    ///
+    /// 使用 GBK 编码时, 条目中有 GBK 无法表示的字符会返回 [`io::ErrorKind::InvalidData`] 错误
+    pub fn to_bytes(&self) -> io::Result<Vec<u8>> {
+        let text = self.to_string();
+        match self.encoding {
+            DictEncoding::Utf8 if self.bom => Ok([b"\xEF\xBB\xBF", text.as_bytes()].concat()),
+            DictEncoding::Utf8 => Ok(text.into_bytes()),
+            DictEncoding::Gbk => match encoding_rs::GBK.encode(&text) {
+                (_, _, true) => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "字库中有 GBK 无法表示的字符",
+                )),
+                (data, _, false) => Ok(data.into_owned()),
+            },
+        }
+    }
+
+    /// 读取字库文件
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_bytes(&fs::read(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// 保存字库文件
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_bytes()?)
+    }
+}
+
+impl Default for Dict {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 严格按 GBK 解码, 有无法解码的字节时返回所在的行
+fn decode_gbk(data: &[u8]) -> Result<String, ParseDictError> {
+    match encoding_rs::GBK.decode_without_bom_handling_and_without_replacement(data) {
+        Some(text) => Ok(text.into_owned()),
+        None => {
+            let mut offset = 0;
+            for line in data.split_inclusive(|&b| b == b'\n') {
+                if encoding_rs::GBK
+                    .decode_without_bom_handling_and_without_replacement(line)
+                    .is_none()
+                {
+                    break;
+                }
+                offset += line.len();
+            }
+            Err(encoding_error(data, offset))
+        }
+    }
+}
+
+/// `offset` 所在的行不是合法的编码
+fn encoding_error(data: &[u8], offset: usize) -> ParseDictError {
+    let start = data[..offset]
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    let end = data[offset..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(data.len(), |i| offset + i);
+    ParseDictError {
+        line: data[..start].iter().filter(|&&b| b == b'\n').count() + 1,
+        content: String::from_utf8_lossy(&data[start..end]).into_owned(),
+        kind: ParseDictErrorKind::Encoding,
+    }
+}
+
+/// 把一行分为内容和换行符
+fn split_newline(line: &str) -> (&str, &'static str) {
+    if let Some(content) = line.strip_suffix("\r\n") {
+        (content, "\r\n")
+    } else if let Some(content) = line.strip_suffix('\n') {
+        (content, "\n")
+    } else {
+        (line, "")
+    }
+}
+
+/// 解析字库文本, 空行会被保留, 但不算作条目
+///
+/// 有非 ASCII 字符时保存为 UTF-8, 否则和 [`Dict::new`] 一样保存为 GBK
+impl FromStr for Dict {
+    type Err = ParseDictError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut dict = Dict::new();
+        if !s.is_ascii() {
+            dict.encoding = DictEncoding::Utf8;
+        }
+        if let Some((first, _)) = s.split_once('\n') {
+            dict.newline = if first.ends_with('\r') { "\r\n" } else { "\n" };
+        }
+        let mut blank = String::new();
+        for (i, line) in s.split_inclusive('\n').enumerate() {
+            let (content, newline) = split_newline(line);
+            if content.trim().is_empty() {
+                blank.push_str(line);
+                continue;
+            }
+            let entry = content
+                .parse()
+                .map_err(|e: ParseDictError| ParseDictError { line: i + 1, ..e })?;
+            dict.entries.push(entry);
+            dict.lines.push(Line {
+                blank_before: std::mem::take(&mut blank),
+                raw: Some(content.to_owned()),
+                newline,
+            });
+        }
+        dict.trailer = blank;
+        Ok(dict)
+    }
+}
+
+/// 输出文件内容(不包括 BOM), 读取的条目原样输出, 新添加的条目每行一个
+impl fmt::Display for Dict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (entry, line) in self.entries.iter().zip(&self.lines) {
+            f.write_str(&line.blank_before)?;
+            match &line.raw {
+                Some(raw) => f.write_str(raw)?,
+                None => write!(f, "{}", entry)?,
+            }
+            f.write_str(line.newline)?;
+        }
+        f.write_str(&self.trailer)
+    }
+}
+
+impl<'a> IntoIterator for &'a Dict {
+    type Item = &'a DictEntry;
+    type IntoIter = std::slice::Iter<'a, DictEntry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+impl FromIterator<DictEntry> for Dict {
+    fn from_iter<T: IntoIterator<Item = DictEntry>>(iter: T) -> Self {
+        let mut dict = Dict::new();
+        for entry in iter {
+            dict.add(entry);
+        }
+        dict
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 大漠文档(SaveDict, AddDict)中的字库条目和对应的宽度
+    const SAMPLES: &[(&str, u32)] = &[
+        ("FFF00A7D49292524A7D402805FFC$回$0.0.54$11", 10),
+        ("3F0020087FF08270B9A108268708808$收$0.0.43$11", 11),
+        ("2055C98617420807C097F222447C800$站$0.0.44$11", 11),
+        ("081101BF8020089FD10A21443F85038$记$0.0$11", 11),
+    ];
+
+    #[test]
+    fn plugin_samples() {
+        for &(line, width) in SAMPLES {
+            let entry: DictEntry = line.parse().unwrap();
+            let glyph = entry.glyph().unwrap();
+            assert_eq!(entry.width(), width, "{line}");
+            assert_eq!((glyph.width(), glyph.height()), (width, 11), "{line}");
+            assert_eq!(glyph.to_hex(), entry.bits, "{line}");
+            if let Some(points) = entry.info.strip_prefix("0.0.") {
+                assert_eq!(glyph.count_ones().to_string(), points, "{line}");
+            }
+            assert_eq!(entry.to_string(), line);
+        }
+        let hui = SAMPLES[0].0.parse::<DictEntry>().unwrap().glyph().unwrap();
+        assert_eq!(hui.to_string().lines().next(), Some("##########"));
+    }
+
+    #[test]
+    fn new_entry_matches_plugin() {
+        let ji: DictEntry = SAMPLES[3].0.parse().unwrap();
+        let entry = DictEntry::new("记", &ji.glyph().unwrap());
+        assert_eq!(entry.bits, ji.bits);
+        assert_eq!(entry.info, "0.0.42");
+        assert_eq!(entry.height, 11);
+    }
+
+    #[test]
+    fn lossless_utf8() {
+        let text = "\n\nF999F$口$0.0.14$4\r\n\r\n  \n081101BF8020089FD10A21443F85038$记$0.0$11\nf999f$口$0.0.14$4";
+        let data = [b"\xEF\xBB\xBF".as_slice(), text.as_bytes()].concat();
+        let dict = Dict::from_bytes(&data).unwrap();
+        assert_eq!(dict.len(), 3);
+        assert_eq!(dict.encoding(), DictEncoding::Utf8);
+        assert!(dict.has_bom());
+        assert_eq!(dict.to_string(), text);
+        assert_eq!(dict.to_bytes().unwrap(), data);
+    }
+
+    #[test]
+    fn lossless_gbk() {
+        let text = "F999F$口$0.0.14$4\r\nFFF00A7D49292524A7D402805FFC$回$0.0.54$11\r\n\r\n";
+        let (data, _, _) = encoding_rs::GBK.encode(text);
+        let dict = Dict::from_bytes(&data).unwrap();
+        assert_eq!(dict.encoding(), DictEncoding::Gbk);
+        assert!(!dict.has_bom());
+        assert_eq!(
+            dict.find("回").unwrap().bits,
+            "FFF00A7D49292524A7D402805FFC"
+        );
+        assert_eq!(dict.to_bytes().unwrap(), &*data);
+    }
+
+    #[test]
+    fn invalid_encoding() {
+        let data = b"F999F$\xBF\xDA$0.0.14$4\r\nF999F$\xFF\xFF$0.0.14$4\r\n";
+        let err = Dict::from_bytes(data).unwrap_err();
+        assert_eq!((err.line, err.kind), (2, ParseDictErrorKind::Encoding));
+
+        let data = b"\xEF\xBB\xBFF999F$\xBF\xDA$0.0.14$4";
+        let err = Dict::from_bytes(data).unwrap_err();
+        assert_eq!((err.line, err.kind), (1, ParseDictErrorKind::Encoding));
+
+        let err = "F999F$口$0.0.14$4\n\nbad".parse::<Dict>().unwrap_err();
+        assert_eq!((err.line, err.kind), (3, ParseDictErrorKind::Entry));
+
+        let mut dict = Dict::new();
+        dict.add(DictEntry::new("😀", &Glyph::new(1, 1)));
+        assert!(dict.to_bytes().is_err());
+        dict.set_encoding(DictEncoding::Utf8);
+        assert!(dict.to_bytes().is_ok());
+    }
+
+    #[test]
+    fn ascii_is_gbk() {
+        for text in ["", "\r\n", "8$a$0.0.1$1\r\n"] {
+            let dict = Dict::from_bytes(text.as_bytes()).unwrap();
+            assert_eq!(dict.encoding(), DictEncoding::Gbk, "{text:?}");
+            assert_eq!(text.parse::<Dict>().unwrap().encoding(), DictEncoding::Gbk);
+        }
+        assert_eq!("".parse::<Dict>().unwrap(), Dict::new());
+
+        // 之后添加的汉字按 GBK 保存
+        let mut dict = Dict::from_bytes(b"8$a$0.0.1$1\r\n").unwrap();
+        dict.add(DictEntry::new("口", &Glyph::from_rows(&["#"])));
+        let (expected, _, _) = encoding_rs::GBK.encode("8$a$0.0.1$1\r\n8$口$0.0.1$1\r\n");
+        assert_eq!(dict.to_bytes().unwrap(), &*expected);
+
+        // 有 BOM 时仍然是 UTF-8
+        let dict = Dict::from_bytes(b"\xEF\xBB\xBF8$a$0.0.1$1").unwrap();
+        assert_eq!(dict.encoding(), DictEncoding::Utf8);
+    }
+
+    #[test]
+    fn invalid_entries() {
+        for line in [
+            "$口$0.0.14$4",
+            "F999F$口$0.0.14$0",
+            "F99G$口$0.0.14$4",
+            "F999F$口$4",
+            "F999F",
+        ] {
+            let err = line.parse::<DictEntry>().unwrap_err();
+            assert_eq!(err.kind, ParseDictErrorKind::Entry, "{line}");
+        }
+        // 字符本身是 '$'
+        let entry: DictEntry = "F999F$$$0.0.14$4".parse().unwrap();
+        assert_eq!(entry.text, "$");
+    }
+
+    #[test]
+    fn final_newline() {
+        let entry = |text: &str| DictEntry::new(text, &Glyph::from_rows(&["#"]));
+
+        let mut dict: Dict = "F999F$口$0.0.14$4".parse().unwrap();
+        dict.add(entry("a"));
+        assert_eq!(dict.to_string(), "F999F$口$0.0.14$4\r\n8$a$0.0.1$1");
+        assert_eq!(dict.remove("a"), 1);
+        assert_eq!(dict.to_string(), "F999F$口$0.0.14$4");
+
+        let mut dict: Dict = "F999F$口$0.0.14$4\r\n".parse().unwrap();
+        dict.add(entry("a"));
+        assert_eq!(dict.to_string(), "F999F$口$0.0.14$4\r\n8$a$0.0.1$1\r\n");
+    }
+
+    #[test]
+    fn default_is_new() {
+        assert_eq!(Dict::default(), Dict::new());
+        let mut dict = Dict::default();
+        dict.add(DictEntry::new("口", &Glyph::from_rows(&["#"])));
+        assert_eq!(dict.encoding(), DictEncoding::Gbk);
+        assert_eq!(dict.to_string(), "8$口$0.0.1$1\r\n");
+    }
+
+    #[test]
+    fn merge_and_dedupe() {
+        let mut dict: Dict = "F999F$口$0.0.14$4\nF999F$回$0.0.14$4\n".parse().unwrap();
+        let other: Dict = "f999f$口$0.0.14$4\n8$a$0.0.1$1\n".parse().unwrap();
+        assert_eq!(dict.merge(other), 1);
+        assert_eq!(dict.dedupe(), 1);
+        let texts: Vec<_> = dict.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, ["口", "a"]);
+        assert_eq!(dict.to_string(), "F999F$口$0.0.14$4\n8$a$0.0.1$1\n");
+    }
+}
@@ -0,0 +1,136 @@
+//! 找字相关的类型: 字符串列表, 返回值解析
+
+use std::fmt;
+
+use crate::{pic, Dmsoft, Point, Result};
+
+/// 找字使用的字符串列表, 转换为字符串后为 "长安|洛阳" 格式
+/// # Examples
+/// ```
+/// let strs = StrSet::new().with("长安").with("洛阳");
+/// assert_eq!(strs.to_string(), "长安|洛阳");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StrSet {
+    strs: Vec<String>,
+}
+
+impl StrSet {
+    /// 新建一个空的字符串列表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加一个字符串, 返回自身
+    pub fn with(mut self, s: impl Into<String>) -> Self {
+        self.push(s);
+        self
+    }
+
+    /// 添加一个字符串
+    pub fn push(&mut self, s: impl Into<String>) {
+        self.strs.push(s.into());
+    }
+
+    /// 所有字符串
+    pub fn strs(&self) -> &[String] {
+        &self.strs
+    }
+
+    /// 第`index`个字符串
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.strs.get(index).map(String::as_str)
+    }
+
+    /// 字符串数量
+    pub fn len(&self) -> usize {
+        self.strs.len()
+    }
+
+    /// 列表为空时返回 `true`
+    pub fn is_empty(&self) -> bool {
+        self.strs.is_empty()
+    }
+
+    /// 把插件返回的字符串序号转换为 (字符串, 坐标), 序号为`-1`或者越界时返回 `None`
+    pub(crate) fn matched(&self, index: i32, pos: Point) -> Option<(String, Point)> {
+        let s = self.get(usize::try_from(index).ok()?)?;
+        Some((s.to_owned(), pos))
+    }
+
+    /// 解析 "id|x|y" 格式的返回串
+    pub(crate) fn matched_one(&self, ret: &str) -> Option<(String, Point)> {
+        pic::parse_index_pos(ret).and_then(|(index, pos)| self.matched(index, pos))
+    }
+
+    /// 解析 "id,x,y|id,x,y" 格式的返回串
+    pub(crate) fn matched_list(&self, ret: &str) -> Vec<(String, Point)> {
+        pic::parse_list(ret)
+            .filter_map(|(index, pos)| self.matched(index.parse().ok()?, pos))
+            .collect()
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for StrSet {
+    fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+        Self {
+            strs: iter.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl fmt::Display for StrSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.strs.join("|"))
+    }
+}
+
+/// 找字辅助函数
+impl Dmsoft {
+    /// 同 `FindStr`, 用 [`StrSet`] 传入字符串, 返回找到的字符串和坐标, 没找到返回 `None`
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    ///
+    /// let strs = StrSet::new().with("长安");
+    /// if let Some((_, pos)) = dm.find_str(0,0,2000,2000,&strs,"9f2e3f-000000",1.0).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    ///
+    /// let strs = StrSet::new().with("长安").with("洛阳");
+    /// if let Some((s, pos)) = dm.find_str(0,0,2000,2000,&strs,"9f2e3f-000000",1.0).unwrap() {
+    ///     println!("找到{}", s);
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    ///
+    /// // 查找时,对多行文本进行换行,换行分隔符是"|". 语法是在","后增加换行字符串.任意字符串都可以.
+    /// if let Some((s, pos)) = dm.find_str(0,0,2000,2000,&strs,"9f2e3f-000000,|",1.0).unwrap() {
+    ///     println!("找到{}", s);
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    /// ```
+    pub unsafe fn find_str(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        strs: &StrSet,
+        color: &str,
+        sim: f64,
+    ) -> Result<Option<(String, Point)>> {
+        let (mut x, mut y) = (-1, -1);
+        let index = self.FindStr(
+            x1,
+            y1,
+            x2,
+            y2,
+            &strs.to_string(),
+            color,
+            sim,
+            &mut x,
+            &mut y,
+        )?;
+        Ok(strs.matched(index, Point::new(x, y)))
+    }
+}
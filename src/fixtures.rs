@@ -0,0 +1,27 @@
+//! 测试用的 `tests/fixtures` 下的图片和字库
+
+use std::path::PathBuf;
+
+use crate::bmp::Bmp;
+
+/// `tests/fixtures/<dir>/<name>` 的路径, `name` 为空时为目录本身
+pub(crate) fn path(dir: &str, name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(dir)
+        .join(name)
+}
+
+/// 打开 `tests/fixtures/<dir>/<name>` 位图
+pub(crate) fn bmp(dir: &str, name: &str) -> Bmp {
+    Bmp::open(path(dir, name)).unwrap()
+}
+
+/// 新建插件对象, 全局路径为 `tests/fixtures/<dir>`, 截图改为读取其中的`image`
+#[cfg(windows)]
+pub(crate) unsafe fn plugin(dir: &str, image: &str) -> crate::Dmsoft {
+    let dm = crate::Dmsoft::new().unwrap();
+    assert_eq!(dm.SetPath(path(dir, "").to_str().unwrap()).unwrap(), 1);
+    assert_eq!(dm.SetDisplayInput(&format!("pic:{image}")).unwrap(), 1);
+    dm
+}
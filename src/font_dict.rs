@@ -0,0 +1,193 @@
+//! 从 TTF/OTF 字体生成大漠字库, 需要开启 `font` feature
+//!
+//! 每个字符按指定的像素大小光栅化, 覆盖率不小于阈值的像素算作有点, 然后裁剪到有点的最小范围,
+//! 生成的 [`Dict`] 可以直接保存为字库文件给 `SetDict` 使用.
+
+use std::{fs, io, path::Path};
+
+use ab_glyph::{point, Font, FontVec, PxScale};
+
+use crate::dict_file::{Dict, DictEntry, Glyph};
+
+/// 字体光栅化工具
+/// # Examples
+/// ```
+/// let font = FontRasterizer::open("simsun.ttf").unwrap().with_size(12.0);
+/// let dict = font.build_dict("0123456789".chars());
+/// dict.save("num.txt").unwrap();
+/// let dm = Dmsoft::new();
+/// dm.SetDict(DictSlot::new(0).unwrap(), "num.txt").unwrap();
+/// ```
+pub struct FontRasterizer {
+    font: FontVec,
+    size: f32,
+    threshold: f32,
+}
+
+impl FontRasterizer {
+    /// 从字体文件数据创建, 默认大小为12像素, 阈值为0.5
+    pub fn from_bytes(data: Vec<u8>) -> io::Result<Self> {
+        let font = FontVec::try_from_vec(data)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(Self {
+            font,
+            size: 12.0,
+            threshold: 0.5,
+        })
+    }
+
+    /// 打开字体文件
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_bytes(fs::read(path)?)
+    }
+
+    /// 设置字体大小(像素), 返回自身
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// 设置覆盖率阈值(0.0-1.0), 覆盖率不小于阈值的像素算作有点, 返回自身
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// 字体大小(像素)
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+
+    /// 覆盖率阈值
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    /// 光栅化一个字符, 裁剪到有点的最小范围
+    ///
+    /// 字体中没有这个字符, 或者字符没有任何点(比如空格)时返回 `None`
+    pub fn rasterize(&self, ch: char) -> Option<Glyph> {
+        let id = self.font.glyph_id(ch);
+        if id.0 == 0 {
+            return None;
+        }
+        let glyph = id.with_scale_and_position(PxScale::from(self.size), point(0.0, 0.0));
+        let outlined = self.font.outline_glyph(glyph)?;
+        let bounds = outlined.px_bounds();
+        let (width, height) = (bounds.width() as u32, bounds.height() as u32);
+        let mut full = Glyph::new(width, height);
+        outlined.draw(|x, y, coverage| {
+            if x < width && y < height && coverage >= self.threshold {
+                full.set(x, y, true);
+            }
+        });
+        trim(&full)
+    }
+
+    /// 光栅化所有字符生成字库, 跳过无法光栅化的字符
+    pub fn build_dict(&self, chars: impl IntoIterator<Item = char>) -> Dict {
+        chars
+            .into_iter()
+            .filter_map(|ch| Some(DictEntry::new(ch, &self.rasterize(ch)?)))
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for FontRasterizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FontRasterizer")
+            .field("size", &self.size)
+            .field("threshold", &self.threshold)
+            .finish_non_exhaustive()
+    }
+}
+
+/// 裁剪到有点的最小范围, 没有点时返回 `None`
+fn trim(glyph: &Glyph) -> Option<Glyph> {
+    let points: Vec<(u32, u32)> = (0..glyph.height())
+        .flat_map(|y| (0..glyph.width()).map(move |x| (x, y)))
+        .filter(|&(x, y)| glyph.get(x, y))
+        .collect();
+    let x1 = points.iter().map(|p| p.0).min()?;
+    let x2 = points.iter().map(|p| p.0).max()?;
+    let y1 = points.iter().map(|p| p.1).min()?;
+    let y2 = points.iter().map(|p| p.1).max()?;
+    let mut trimmed = Glyph::new(x2 - x1 + 1, y2 - y1 + 1);
+    for (x, y) in points {
+        trimmed.set(x - x1, y - y1, true);
+    }
+    Some(trimmed)
+}
+
+/// `tests/fixtures/font/mono.ttf` 是 DejaVu Sans Mono 中 "0123456789AHx" 的子集
+#[cfg(all(test, feature = "font"))]
+mod tests {
+    use super::*;
+
+    const CHARS: &str = "0123456789AHx";
+
+    fn font() -> FontRasterizer {
+        FontRasterizer::from_bytes(include_bytes!("../tests/fixtures/font/mono.ttf").to_vec())
+            .unwrap()
+    }
+
+    #[test]
+    fn dict_round_trip() {
+        for size in [9.0, 12.0, 16.0] {
+            let font = font().with_size(size);
+            let glyphs: Vec<Glyph> = CHARS
+                .chars()
+                .map(|ch| font.rasterize(ch).unwrap())
+                .collect();
+            let dict = font.build_dict(CHARS.chars());
+            assert_eq!(dict.len(), CHARS.chars().count());
+
+            let dict = Dict::from_bytes(&dict.to_bytes().unwrap()).unwrap();
+            for ((entry, glyph), ch) in dict.iter().zip(&glyphs).zip(CHARS.chars()) {
+                assert_eq!(entry.text, ch.to_string());
+                assert_eq!(
+                    entry.glyph().as_ref(),
+                    Some(glyph),
+                    "{ch} {size}px\n{glyph}"
+                );
+                assert_eq!(entry.info, format!("0.0.{}", glyph.count_ones()));
+            }
+        }
+    }
+
+    #[test]
+    fn glyph_shape() {
+        let font = font();
+        assert_eq!(font.rasterize(' '), None);
+        assert_eq!(font.rasterize('中'), None);
+        let h = font.with_size(16.0).rasterize('H').unwrap();
+        let rows: Vec<String> = h.to_string().lines().map(str::to_owned).collect();
+        // 两条竖线和中间的横线
+        assert!(rows
+            .iter()
+            .all(|row| row.starts_with('#') && row.ends_with('#')));
+        assert!(rows.iter().any(|row| !row.contains('.')));
+        assert!(rows[0].contains('.'));
+    }
+
+    #[test]
+    fn hex_padding() {
+        // 大漠文档 AddDict 中的条目, 11x11 共121位, 末尾补3个0位
+        let sample = "081101BF8020089FD10A21443F85038$记$0.0$11";
+        let entry: DictEntry = sample.parse().unwrap();
+        let glyph = entry.glyph().unwrap();
+        assert_eq!((glyph.width(), glyph.height()), (11, 11));
+        assert_eq!(DictEntry::new("记", &glyph).bits, entry.bits);
+
+        let font = font();
+        for ch in CHARS.chars() {
+            let glyph = font.rasterize(ch).unwrap();
+            let bits = (glyph.width() * glyph.height()) as usize;
+            let hex = glyph.to_hex();
+            assert_eq!(hex.len(), bits.div_ceil(4), "{ch}");
+            let last = hex.chars().last().unwrap().to_digit(16).unwrap();
+            let pad = hex.len() * 4 - bits;
+            assert_eq!(last & ((1 << pad) - 1), 0, "{ch} {hex}");
+        }
+    }
+}
@@ -0,0 +1,320 @@
+//! 识字结果的后处理: 按词表模糊纠正识别结果, 以及从识别结果中提取数值字段
+//!
+//! 纠正使用带权重的编辑距离: 插入和删除一个字符的代价为1, 替换的代价默认为1,
+//! 可以为形状相近的字符(比如 "日" 和 "目", "己" 和 "已")设置更低的代价, 也可以从字库的点阵相似度自动计算.
+//!
+//! 开启 `regex` feature 后可以用正则表达式的命名分组提取字段.
+
+use std::collections::HashMap;
+
+use crate::{dict_file::Dict, ocr::Word};
+
+/// 纠正后的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapped {
+    /// 词表中最接近的词
+    pub word: String,
+    /// 带权重的编辑距离
+    pub distance: f64,
+    /// 置信度, 取值范围0.0-1.0, 1.0表示完全一致
+    pub confidence: f64,
+}
+
+/// 词表, 识别结果只能是其中的一个词
+/// # Examples
+/// ```
+/// let vocab = Vocabulary::new().with("长安").with("洛阳").with_confusion('日', '阳', 0.3);
+/// let snapped = vocab.snap("洛日").unwrap();
+/// assert_eq!(snapped.word, "洛阳");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Vocabulary {
+    words: Vec<String>,
+    confusions: HashMap<(char, char), f64>,
+}
+
+impl Vocabulary {
+    /// 新建一个空词表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加一个词, 返回自身
+    pub fn with(mut self, word: impl Into<String>) -> Self {
+        self.push(word);
+        self
+    }
+
+    /// 添加一个词
+    pub fn push(&mut self, word: impl Into<String>) {
+        self.words.push(word.into());
+    }
+
+    /// 所有词
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// 设置`a`和`b`互相替换的代价(0.0-1.0), 返回自身
+    pub fn with_confusion(mut self, a: char, b: char, cost: f64) -> Self {
+        self.set_confusion(a, b, cost);
+        self
+    }
+
+    /// 设置`a`和`b`互相替换的代价(0.0-1.0)
+    pub fn set_confusion(&mut self, a: char, b: char, cost: f64) {
+        let cost = cost.clamp(0.0, 1.0);
+        self.confusions.insert((a, b), cost);
+        self.confusions.insert((b, a), cost);
+    }
+
+    /// 根据字库中单个字符的点阵相似度设置替换代价, 返回自身
+    ///
+    /// 只比较宽高相同的点阵, 相似度为相同的点数占两个点阵点数并集的比例, 代价为 1 - 相似度.
+    /// 相似度低于`min_similarity`的字符对会被忽略
+    pub fn with_dict_confusions(mut self, dict: &Dict, min_similarity: f64) -> Self {
+        let glyphs: Vec<(char, _)> = dict
+            .iter()
+            .filter_map(|entry| {
+                let mut chars = entry.text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Some((c, entry.glyph()?)),
+                    _ => None,
+                }
+            })
+            .collect();
+        for (i, (a, ga)) in glyphs.iter().enumerate() {
+            for (b, gb) in &glyphs[i + 1..] {
+                if a == b || ga.width() != gb.width() || ga.height() != gb.height() {
+                    continue;
+                }
+                let (mut same, mut union) = (0, 0);
+                for y in 0..ga.height() {
+                    for x in 0..ga.width() {
+                        let (pa, pb) = (ga.get(x, y), gb.get(x, y));
+                        union += (pa || pb) as u32;
+                        same += (pa && pb) as u32;
+                    }
+                }
+                let similarity = if union == 0 {
+                    1.0
+                } else {
+                    same as f64 / union as f64
+                };
+                if similarity >= min_similarity {
+                    self.set_confusion(*a, *b, 1.0 - similarity);
+                }
+            }
+        }
+        self
+    }
+
+    /// 替换代价
+    fn substitution(&self, a: char, b: char) -> f64 {
+        if a == b {
+            0.0
+        } else {
+            self.confusions.get(&(a, b)).copied().unwrap_or(1.0)
+        }
+    }
+
+    /// `a` 和 `b` 之间带权重的编辑距离
+    pub fn distance(&self, a: &str, b: &str) -> f64 {
+        let b: Vec<char> = b.chars().collect();
+        let mut prev: Vec<f64> = (0..=b.len()).map(|j| j as f64).collect();
+        for (i, ca) in a.chars().enumerate() {
+            let mut cur = vec![(i + 1) as f64; b.len() + 1];
+            for (j, &cb) in b.iter().enumerate() {
+                cur[j + 1] = (prev[j] + self.substitution(ca, cb))
+                    .min(prev[j + 1] + 1.0)
+                    .min(cur[j] + 1.0);
+            }
+            prev = cur;
+        }
+        prev[b.len()]
+    }
+
+    /// 把`text`纠正为词表中最接近的词, 词表为空时返回 `None`
+    ///
+    /// 置信度为 1 - 距离 / 较长的字符数
+    pub fn snap(&self, text: &str) -> Option<Snapped> {
+        self.words
+            .iter()
+            .map(|word| {
+                let distance = self.distance(text, word);
+                let len = text.chars().count().max(word.chars().count()).max(1);
+                Snapped {
+                    word: word.clone(),
+                    distance,
+                    confidence: (1.0 - distance / len as f64).max(0.0),
+                }
+            })
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+
+    /// 同 [`Vocabulary::snap`], 置信度低于`min_confidence`时返回 `None`
+    pub fn snap_min(&self, text: &str, min_confidence: f64) -> Option<Snapped> {
+        self.snap(text)
+            .filter(|snapped| snapped.confidence >= min_confidence)
+    }
+
+    /// 纠正识别出的单词, 坐标不变, 置信度低于`min_confidence`的单词会被丢弃
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let vocab = Vocabulary::new().with("确定").with("取消");
+    /// let chars = dm.OcrEx(0,0,2000,2000,"ffffff-000000",0.9).unwrap();
+    /// for line in ocr::group_lines(&chars, 3) {
+    ///     for word in vocab.snap_words(&ocr::group_words(&line, 14), 0.5) {
+    ///         println!("{}: {},{}", word.text, word.pos.x, word.pos.y);
+    ///     }
+    /// }
+    /// ```
+    pub fn snap_words(&self, words: &[Word], min_confidence: f64) -> Vec<Word> {
+        words
+            .iter()
+            .filter_map(|word| {
+                Some(Word {
+                    text: self.snap_min(&word.text, min_confidence)?.word,
+                    pos: word.pos,
+                })
+            })
+            .collect()
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for Vocabulary {
+    fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+        Self {
+            words: iter.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+}
+
+/// 提取文本中所有的整数, 比如 "HP 123/456" 返回 `[123, 456]`
+///
+/// '-' 只有在文本开头或者跟在非字母数字的字符后面时才是负号, 所以 "-5" 和 "x: -5" 返回 `[-5]`,
+/// 而 "10-20" 返回 `[10, 20]`
+pub fn numbers(text: &str) -> Vec<i64> {
+    let mut result = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let negative = c == '-'
+            && !prev.is_some_and(char::is_alphanumeric)
+            && chars.peek().is_some_and(|(_, c)| c.is_ascii_digit());
+        prev = Some(c);
+        if !negative && !c.is_ascii_digit() {
+            continue;
+        }
+        let mut end = start + c.len_utf8();
+        while let Some(&(i, c)) = chars.peek() {
+            if !c.is_ascii_digit() {
+                break;
+            }
+            end = i + c.len_utf8();
+            prev = Some(c);
+            chars.next();
+        }
+        if let Ok(n) = text[start..end].parse() {
+            result.push(n);
+        }
+    }
+    result
+}
+
+/// 提取 "123/456" 格式的比值, 比如 "HP 123/456" 返回 `Some((123, 456))`
+pub fn ratio(text: &str) -> Option<(i64, i64)> {
+    let (left, right) = text.split_once('/')?;
+    let current = *numbers(left).last()?;
+    let max = *numbers(right).first()?;
+    Some((current, max))
+}
+
+/// 用正则表达式的命名分组提取字段, 不匹配时返回 `None`
+/// # Examples
+/// ```
+/// let re = Regex::new(r"HP\s*(?P<hp>\d+)/(?P<max>\d+)").unwrap();
+/// let fields = fuzzy::fields(&re, "HP 123/456").unwrap();
+/// assert_eq!(fields["hp"], "123");
+/// ```
+#[cfg(feature = "regex")]
+pub fn fields(re: &regex::Regex, text: &str) -> Option<HashMap<String, String>> {
+    let captures = re.captures(text)?;
+    Some(
+        re.capture_names()
+            .flatten()
+            .filter_map(|name| Some((name.to_owned(), captures.name(name)?.as_str().to_owned())))
+            .collect(),
+    )
+}
+
+/// 用正则表达式提取名为`name`的分组, 并解析为`T`, 不匹配或者解析失败时返回 `None`
+/// # Examples
+/// ```
+/// let re = Regex::new(r"金币[:：]\s*(?P<gold>\d+)").unwrap();
+/// let gold: Option<u32> = fuzzy::field(&re, "金币: 1024", "gold");
+/// ```
+#[cfg(feature = "regex")]
+pub fn field<T: std::str::FromStr>(re: &regex::Regex, text: &str, name: &str) -> Option<T> {
+    re.captures(text)?.name(name)?.as_str().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_and_signs() {
+        assert_eq!(numbers("HP 123/456"), [123, 456]);
+        assert_eq!(numbers("-5"), [-5]);
+        assert_eq!(numbers("x: -5, y:-12"), [-5, -12]);
+        assert_eq!(numbers("10-20"), [10, 20]);
+        assert_eq!(numbers("第3-4关"), [3, 4]);
+        assert_eq!(numbers("HP-5 a-1 -"), [5, 1]);
+        assert_eq!(numbers("(-7)"), [-7]);
+        assert_eq!(numbers("--3"), [-3]);
+        assert_eq!(numbers("金币:１２ 99999999999999999999 8"), [8]);
+        assert!(numbers("无").is_empty());
+    }
+
+    #[test]
+    fn ratios() {
+        assert_eq!(ratio("HP 123/456"), Some((123, 456)));
+        assert_eq!(ratio("MP 1-2/30"), Some((2, 30)));
+        assert_eq!(ratio("温度 -5/10"), Some((-5, 10)));
+        assert_eq!(ratio("123"), None);
+        assert_eq!(ratio("/456"), None);
+    }
+
+    #[test]
+    fn distance_with_confusions() {
+        let vocab = Vocabulary::new().with_confusion('日', '目', 0.2);
+        assert_eq!(vocab.distance("日", "目"), 0.2);
+        assert_eq!(vocab.distance("目", "日"), 0.2);
+        assert_eq!(vocab.distance("日", "白"), 1.0);
+        assert_eq!(vocab.distance("", "abc"), 3.0);
+        assert_eq!(vocab.distance("abc", "abc"), 0.0);
+        assert_eq!(vocab.distance("明日", "明目x"), 1.2);
+        // 替换代价会被限制在 0.0-1.0
+        let vocab = Vocabulary::new().with_confusion('a', 'b', 5.0);
+        assert_eq!(vocab.distance("a", "b"), 1.0);
+    }
+
+    #[test]
+    fn snapping() {
+        let vocab = Vocabulary::new()
+            .with("确定")
+            .with("取消")
+            .with_confusion('己', '已', 0.1)
+            .with("已完成");
+        let snapped = vocab.snap("己完成").unwrap();
+        assert_eq!(snapped.word, "已完成");
+        assert!((snapped.confidence - (1.0 - 0.1 / 3.0)).abs() < 1e-9);
+        assert_eq!(vocab.snap_min("确走", 0.5).unwrap().word, "确定");
+        assert_eq!(vocab.snap_min("确走", 0.6), None);
+        assert_eq!(vocab.snap_min("xyz", 0.0).unwrap().confidence, 0.0);
+        assert_eq!(Vocabulary::new().snap("确定"), None);
+    }
+}
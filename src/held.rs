@@ -0,0 +1,215 @@
+//! 防止按键卡住: 记录通过绑定按住但还没有弹起的键, 提供离开作用域时自动弹起的守卫
+//!
+//! `KeyDown` / `LeftDown` / `RightDown` / `MiddleDown` 成功后会记录按住的键, 对应的弹起函数成功后移除记录.
+//! [`Dmsoft`] 销毁时(包括 panic 展开时)会调用 [`Dmsoft::release_all`] 弹起所有记录的键.
+
+use std::collections::HashSet;
+
+use crate::{check, Dmsoft, KeyMap, Result};
+
+/// 按住的键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Held {
+    /// 键盘按键, 参数为虚拟键码
+    Key(i32),
+    /// 鼠标左键
+    LeftButton,
+    /// 鼠标右键
+    RightButton,
+    /// 鼠标中键
+    MiddleButton,
+}
+
+impl Held {
+    /// 调用对应的弹起函数
+    unsafe fn release(self, dm: &Dmsoft) -> Result<()> {
+        match self {
+            Held::Key(id) => check(dm.KeyUp(KeyMap::new("", id)), "KeyUp"),
+            Held::LeftButton => check(dm.LeftUp(), "LeftUp"),
+            Held::RightButton => check(dm.RightUp(), "RightUp"),
+            Held::MiddleButton => check(dm.MiddleUp(), "MiddleUp"),
+        }
+    }
+}
+
+/// 按住的键, 离开作用域时弹起
+/// # Examples
+/// ```
+/// let dm = Dmsoft::new();
+/// {
+///     let _shift = dm.key_down_guard(KeyMap::KEY_SHIFT).unwrap();
+///     dm.LeftClick().unwrap();
+///     // 即使这里提前返回或者 panic, shift 也会被弹起
+/// }
+/// ```
+#[derive(Debug)]
+#[must_use = "守卫销毁时会立即弹起按键"]
+pub struct HeldGuard<'a> {
+    dm: &'a Dmsoft,
+    held: Held,
+}
+
+impl HeldGuard<'_> {
+    /// 按住的键
+    pub fn held(&self) -> Held {
+        self.held
+    }
+
+    /// 立即弹起, 返回弹起的结果
+    pub unsafe fn release(self) -> Result<()> {
+        let held = self.held;
+        let dm = self.dm;
+        std::mem::forget(self);
+        held.release(dm)
+    }
+}
+
+impl Drop for HeldGuard<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self.held.release(self.dm);
+        }
+    }
+}
+
+/// 防止按键卡住辅助函数
+impl Dmsoft {
+    /// `KeyDown` 按住`vk`, 返回的 [`HeldGuard`] 离开作用域时 `KeyUp` 弹起
+    pub unsafe fn key_down_guard(&self, vk: KeyMap) -> Result<HeldGuard<'_>> {
+        check(self.KeyDown(vk), "KeyDown")?;
+        Ok(HeldGuard {
+            dm: self,
+            held: Held::Key(vk.get_id()),
+        })
+    }
+
+    /// `LeftDown` 按住鼠标左键, 返回的 [`HeldGuard`] 离开作用域时 `LeftUp` 弹起
+    pub unsafe fn left_down_guard(&self) -> Result<HeldGuard<'_>> {
+        check(self.LeftDown(), "LeftDown")?;
+        Ok(HeldGuard {
+            dm: self,
+            held: Held::LeftButton,
+        })
+    }
+
+    /// `RightDown` 按住鼠标右键, 返回的 [`HeldGuard`] 离开作用域时 `RightUp` 弹起
+    pub unsafe fn right_down_guard(&self) -> Result<HeldGuard<'_>> {
+        check(self.RightDown(), "RightDown")?;
+        Ok(HeldGuard {
+            dm: self,
+            held: Held::RightButton,
+        })
+    }
+
+    /// `MiddleDown` 按住鼠标中键, 返回的 [`HeldGuard`] 离开作用域时 `MiddleUp` 弹起
+    pub unsafe fn middle_down_guard(&self) -> Result<HeldGuard<'_>> {
+        check(self.MiddleDown(), "MiddleDown")?;
+        Ok(HeldGuard {
+            dm: self,
+            held: Held::MiddleButton,
+        })
+    }
+
+    /// 所有通过绑定按住但还没有弹起的键
+    pub fn held_keys(&self) -> Vec<Held> {
+        let mut held: Vec<Held> = self.held.read().unwrap().iter().copied().collect();
+        held.sort();
+        held
+    }
+
+    /// 弹起所有记录的按键, 全部尝试后返回第一个错误
+    pub unsafe fn release_all(&self) -> Result<()> {
+        let mut result = Ok(());
+        for held in self.held_keys() {
+            if let Err(e) = held.release(self) {
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+        result
+    }
+
+    /// 按下或者弹起成功后更新记录
+    pub(crate) fn track_held(&self, ret: i32, held: Held, down: bool) {
+        track(&mut self.held.write().unwrap(), ret, held, down);
+    }
+}
+
+/// 调用成功(`ret` 不为0)时, 按下记录`held`, 弹起移除`held`
+fn track(set: &mut HashSet<Held>, ret: i32, held: Held, down: bool) {
+    if ret != 0 {
+        if down {
+            set.insert(held);
+        } else {
+            set.remove(&held);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn down_and_up() {
+        let mut set = HashSet::new();
+        track(&mut set, 1, Held::Key(16), true);
+        track(&mut set, 1, Held::LeftButton, true);
+        // 重复按下只记录一次
+        track(&mut set, 1, Held::Key(16), true);
+        assert_eq!(set, HashSet::from([Held::Key(16), Held::LeftButton]));
+        track(&mut set, 1, Held::Key(16), false);
+        assert_eq!(set, HashSet::from([Held::LeftButton]));
+        // 没有按住的键弹起不影响记录
+        track(&mut set, 1, Held::RightButton, false);
+        assert_eq!(set, HashSet::from([Held::LeftButton]));
+    }
+
+    #[test]
+    fn failed_calls_ignored() {
+        let mut set = HashSet::new();
+        track(&mut set, 0, Held::Key(17), true);
+        assert!(set.is_empty());
+        track(&mut set, 1, Held::MiddleButton, true);
+        track(&mut set, 0, Held::MiddleButton, false);
+        assert_eq!(set, HashSet::from([Held::MiddleButton]));
+    }
+
+    #[test]
+    fn sorted_order() {
+        let mut held = [
+            Held::MiddleButton,
+            Held::Key(65),
+            Held::LeftButton,
+            Held::Key(16),
+        ];
+        held.sort();
+        assert_eq!(
+            held,
+            [
+                Held::Key(16),
+                Held::Key(65),
+                Held::LeftButton,
+                Held::MiddleButton
+            ]
+        );
+    }
+
+    #[cfg(all(windows, feature = "keymap"))]
+    #[test]
+    #[ignore = "需要注册大漠插件"]
+    fn plugin_release_all() {
+        unsafe {
+            let dm = Dmsoft::new().unwrap();
+            assert_eq!(dm.KeyDown(KeyMap::KEY_SHIFT).unwrap(), 1);
+            {
+                let _ctrl = dm.key_down_guard(KeyMap::KEY_CTRL).unwrap();
+                assert_eq!(dm.held_keys(), [Held::Key(16), Held::Key(17)]);
+            }
+            assert_eq!(dm.held_keys(), [Held::Key(16)]);
+            dm.release_all().unwrap();
+            assert!(dm.held_keys().is_empty());
+        }
+    }
+}
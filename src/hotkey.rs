@@ -0,0 +1,261 @@
+//! 全局热键: 在后台线程中用独立的 [`Dmsoft`] 轮询 `GetKeyState`, 检测到组合键按下时触发动作
+//!
+//! 暂停, 恢复和停止直接作用于 [`CancelToken`], 所有动作(包括自定义事件)同时通过通道或者回调通知脚本.
+//! 主线程的 [`Dmsoft`] 设置同一个令牌后, 辅助函数中的等待会在停止时返回 [`Error::Cancelled`](crate::Error::Cancelled).
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{cancel::CancelToken, chord::Chord, Dmsoft, Error, Result};
+
+/// 热键触发的动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HotkeyAction {
+    /// 暂停 [`CancelToken::pause`]
+    Pause,
+    /// 恢复 [`CancelToken::resume`]
+    Resume,
+    /// 切换暂停状态 [`CancelToken::toggle_pause`]
+    TogglePause,
+    /// 停止 [`CancelToken::cancel`]
+    Stop,
+    /// 自定义事件, 只通知脚本
+    Event(u32),
+}
+
+/// 热键监听器
+/// # Examples
+/// ```
+/// let token = CancelToken::new();
+/// let dm = Dmsoft::new();
+/// dm.set_cancel_token(Some(token.clone()));
+/// let _hotkeys = HotkeyListener::new(token.clone())
+///     .with("ctrl+alt+end".parse().unwrap(), HotkeyAction::Stop)
+///     .with("ctrl+alt+p".parse().unwrap(), HotkeyAction::TogglePause)
+///     .spawn();
+/// match dm.play(&timeline, &PlayOptions { loops: 0, ..PlayOptions::default() }) {
+///     Err(Error::Cancelled) => println!("已停止"),
+///     result => result.unwrap(),
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct HotkeyListener {
+    hotkeys: Vec<(Chord, HotkeyAction)>,
+    interval: Duration,
+    token: CancelToken,
+}
+
+impl HotkeyListener {
+    /// 新建一个监听器, 暂停, 恢复和停止作用于`token`
+    pub fn new(token: CancelToken) -> Self {
+        Self {
+            hotkeys: Vec::new(),
+            interval: Duration::from_millis(20),
+            token,
+        }
+    }
+
+    /// 添加一个热键, 返回自身
+    pub fn with(mut self, chord: Chord, action: HotkeyAction) -> Self {
+        self.hotkeys.push((chord, action));
+        self
+    }
+
+    /// 设置轮询间隔, 默认为20毫秒
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// 作用的取消令牌
+    pub fn token(&self) -> &CancelToken {
+        &self.token
+    }
+
+    /// 启动后台线程, 触发的动作通过 [`HotkeyHandle::try_recv`] 等函数接收
+    pub fn spawn(self) -> HotkeyHandle {
+        let (sender, receiver) = mpsc::channel();
+        let mut handle = self.spawn_with(move |action| {
+            let _ = sender.send(action);
+        });
+        handle.events = Some(receiver);
+        handle
+    }
+
+    /// 启动后台线程, 触发动作时在后台线程中调用`callback`
+    ///
+    /// `GetKeyState` 失败时这一轮保持组合键之前的状态, 继续轮询, 停止热键不会因为一次失败而失效.
+    /// 第一次失败的错误由 [`HotkeyHandle::stop`] 返回
+    pub fn spawn_with(
+        self,
+        mut callback: impl FnMut(HotkeyAction) + Send + 'static,
+    ) -> HotkeyHandle {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        let thread = thread::spawn(move || unsafe {
+            let dm = Dmsoft::new().map_err(Error::WinError)?;
+            let mut active = vec![false; self.hotkeys.len()];
+            let mut result = Ok(());
+            while !stopped.load(Ordering::SeqCst) {
+                for (i, (chord, action)) in self.hotkeys.iter().enumerate() {
+                    let down = match is_down(&dm, chord) {
+                        Ok(down) => Some(down),
+                        Err(e) => {
+                            if result.is_ok() {
+                                result = Err(e);
+                            }
+                            None
+                        }
+                    };
+                    if pressed(&mut active[i], down) {
+                        self.trigger(*action);
+                        callback(*action);
+                    }
+                }
+                thread::sleep(self.interval);
+            }
+            result
+        });
+        HotkeyHandle {
+            stop,
+            thread: Some(thread),
+            events: None,
+        }
+    }
+
+    /// 把动作作用于取消令牌
+    fn trigger(&self, action: HotkeyAction) {
+        match action {
+            HotkeyAction::Pause => self.token.pause(),
+            HotkeyAction::Resume => self.token.resume(),
+            HotkeyAction::TogglePause => {
+                self.token.toggle_pause();
+            }
+            HotkeyAction::Stop => self.token.cancel(),
+            HotkeyAction::Event(_) => {}
+        }
+    }
+}
+
+/// 更新组合键的按下状态, 从松开变为按下时返回 `true`, 按住不放时只触发一次
+///
+/// `down` 为 `None` 表示这一轮查询失败, 保持之前的状态
+fn pressed(active: &mut bool, down: Option<bool>) -> bool {
+    let Some(down) = down else {
+        return false;
+    };
+    let pressed = down && !*active;
+    *active = down;
+    pressed
+}
+
+/// 组合键的所有键是否都按下
+unsafe fn is_down(dm: &Dmsoft, chord: &Chord) -> Result<bool> {
+    let key = chord.key();
+    for key in chord.modifiers().iter().chain([&key]) {
+        if dm.GetKeyState(*key)? == 0 {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// 正在运行的热键监听线程, 销毁时停止
+#[derive(Debug)]
+pub struct HotkeyHandle {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<Result<()>>>,
+    events: Option<mpsc::Receiver<HotkeyAction>>,
+}
+
+impl HotkeyHandle {
+    /// 取出一个触发的动作, 没有时返回 `None`. 通过 [`HotkeyListener::spawn_with`] 启动时总是返回 `None`
+    pub fn try_recv(&self) -> Option<HotkeyAction> {
+        self.events.as_ref()?.try_recv().ok()
+    }
+
+    /// 等待一个触发的动作, 超时返回 `None`
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<HotkeyAction> {
+        self.events.as_ref()?.recv_timeout(timeout).ok()
+    }
+
+    /// 后台线程是否还在运行, 创建 [`Dmsoft`] 失败时线程会退出
+    pub fn is_running(&self) -> bool {
+        self.thread
+            .as_ref()
+            .is_some_and(|thread| !thread.is_finished())
+    }
+
+    /// 停止监听并等待后台线程退出, 返回创建 [`Dmsoft`] 的错误或者第一次 `GetKeyState` 失败的错误
+    pub fn stop(mut self) -> Result<()> {
+        self.stop.store(true, Ordering::SeqCst);
+        match self.thread.take().map(JoinHandle::join) {
+            Some(Ok(result)) => result,
+            Some(Err(panic)) => std::panic::resume_unwind(panic),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for HotkeyHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 依次输入每一轮的状态, 返回触发的轮次
+    fn triggers(states: &[Option<bool>]) -> Vec<usize> {
+        let mut active = false;
+        states
+            .iter()
+            .enumerate()
+            .filter(|&(_, &down)| pressed(&mut active, down))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    #[test]
+    fn edge_triggered() {
+        let (up, down) = (Some(false), Some(true));
+        assert_eq!(triggers(&[up, down, down, down, up, down]), [1, 5]);
+        assert_eq!(triggers(&[down, up, up, down]), [0, 3]);
+        assert!(triggers(&[up, up]).is_empty());
+    }
+
+    #[test]
+    fn errors_keep_state() {
+        let (up, down) = (Some(false), Some(true));
+        // 按住时查询失败, 不会再次触发
+        assert_eq!(triggers(&[down, None, down, up]), [0]);
+        // 松开时查询失败, 之后按下照常触发
+        assert_eq!(triggers(&[up, None, down]), [2]);
+        assert_eq!(triggers(&[None, None, down]), [2]);
+    }
+
+    #[test]
+    fn trigger_token() {
+        let token = CancelToken::new();
+        let listener = HotkeyListener::new(token.clone());
+        listener.trigger(HotkeyAction::Pause);
+        assert!(token.is_paused());
+        listener.trigger(HotkeyAction::TogglePause);
+        assert!(!token.is_paused());
+        listener.trigger(HotkeyAction::Event(1));
+        assert!(!token.is_paused() && !token.is_cancelled());
+        listener.trigger(HotkeyAction::Stop);
+        assert!(token.is_cancelled());
+    }
+}
@@ -0,0 +1,392 @@
+//! 键鼠参数: 把分散在各个全局设置函数中的键盘鼠标参数合并为一个配置, 一次设置, 可以读取, 可以临时切换
+//!
+//! 和 [`OcrConfig`](crate::ocr_config::OcrConfig) 一样, 插件没有读取这些参数的接口,
+//! 读取到的是通过本绑定设置过的值(包括直接调用 `SetMouseDelay` 等函数), 没有设置过的参数为插件的默认值.
+//!
+//! `EnableMouseSync` 等参数只能在绑定之后设置, 所以 [`Dmsoft::apply_input_config`] 只调用值有变化的设置函数,
+//! 没有绑定时也可以修改延时等参数.
+
+use std::fmt;
+
+use crate::{check_all, Dmsoft, Result};
+
+/// 绑定窗口时的键鼠模式, 对应 `SetMouseDelay` / `SetKeypadDelay` 的`tpe`参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputMode {
+    /// 前台模式 "normal"
+    Normal,
+    /// windows消息模式 "windows"
+    Windows,
+    /// dx模式 "dx"
+    Dx,
+}
+
+impl InputMode {
+    /// 所有模式
+    pub const ALL: [InputMode; 3] = [InputMode::Normal, InputMode::Windows, InputMode::Dx];
+
+    /// 插件使用的字符串
+    pub fn as_str(self) -> &'static str {
+        match self {
+            InputMode::Normal => "normal",
+            InputMode::Windows => "windows",
+            InputMode::Dx => "dx",
+        }
+    }
+}
+
+impl fmt::Display for InputMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 每种模式下的延时, 单位毫秒
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModeDelays {
+    /// 前台模式
+    pub normal: i32,
+    /// windows消息模式
+    pub windows: i32,
+    /// dx模式
+    pub dx: i32,
+}
+
+impl ModeDelays {
+    /// `mode`对应的延时
+    pub fn get(&self, mode: InputMode) -> i32 {
+        match mode {
+            InputMode::Normal => self.normal,
+            InputMode::Windows => self.windows,
+            InputMode::Dx => self.dx,
+        }
+    }
+
+    /// 设置`mode`对应的延时
+    pub fn set(&mut self, mode: InputMode, delay: i32) {
+        match mode {
+            InputMode::Normal => self.normal = delay,
+            InputMode::Windows => self.windows = delay,
+            InputMode::Dx => self.dx = delay,
+        }
+    }
+}
+
+/// `EnableRealMouse` 模拟真实鼠标移动的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RealMouseMode {
+    /// 关闭模拟
+    #[default]
+    Off = 0,
+    /// 直线模拟
+    Line = 1,
+    /// 随机曲线, 更接近真实
+    Curve = 2,
+    /// 小弧线
+    SmallArc = 3,
+    /// 大弧线
+    LargeArc = 4,
+}
+
+/// `EnableRealMouse` 的参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RealMouse {
+    /// 模拟方式, 默认关闭
+    pub mode: RealMouseMode,
+    /// 每次移动一步的延时, 单位毫秒, 默认为20
+    pub delay: i32,
+    /// 每次移动的最大步长, 单位像素, 默认为30
+    pub step: i32,
+}
+
+impl Default for RealMouse {
+    fn default() -> Self {
+        Self {
+            mode: RealMouseMode::Off,
+            delay: 20,
+            step: 30,
+        }
+    }
+}
+
+/// 所有键鼠参数, 默认值和插件一致
+/// # Examples
+/// ```
+/// let config = InputConfig {
+///     real_keypad: true,
+///     mouse_sync: Some(100),
+///     ..InputConfig::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InputConfig {
+    /// 鼠标单击或者双击时, 鼠标按下和弹起的时间间隔(`SetMouseDelay`), 默认为 normal 30, windows 10, dx 40
+    pub mouse_delay: ModeDelays,
+    /// 键盘按下和弹起的时间间隔(`SetKeypadDelay`), 默认为 normal 30, windows 10, dx 50
+    pub keypad_delay: ModeDelays,
+    /// dx鼠标同步模式的超时时间(`EnableMouseSync`), 单位毫秒, `None` 表示关闭, 默认关闭
+    pub mouse_sync: Option<i32>,
+    /// dx键盘同步模式的超时时间(`EnableKeypadSync`), 单位毫秒, `None` 表示关闭, 默认关闭
+    pub keypad_sync: Option<i32>,
+    /// `EnableKeypadMsg`, 默认开启
+    pub keypad_msg: bool,
+    /// `EnableMouseMsg`, 默认开启
+    pub mouse_msg: bool,
+    /// `EnableKeypadPatch`, 默认关闭
+    pub keypad_patch: bool,
+    /// 模拟真实鼠标移动(`EnableRealMouse`), 默认关闭
+    pub real_mouse: RealMouse,
+    /// 键盘动作模拟真实操作, 点击延时随机(`EnableRealKeypad`), 默认关闭
+    pub real_keypad: bool,
+}
+
+impl Default for InputConfig {
+    fn default() -> Self {
+        Self {
+            mouse_delay: ModeDelays {
+                normal: 30,
+                windows: 10,
+                dx: 40,
+            },
+            keypad_delay: ModeDelays {
+                normal: 30,
+                windows: 10,
+                dx: 50,
+            },
+            mouse_sync: None,
+            keypad_sync: None,
+            keypad_msg: true,
+            mouse_msg: true,
+            keypad_patch: false,
+            real_mouse: RealMouse::default(),
+            real_keypad: false,
+        }
+    }
+}
+
+/// 临时切换的键鼠参数, 离开作用域时恢复之前的参数
+#[derive(Debug)]
+pub struct InputConfigGuard<'a> {
+    dm: &'a Dmsoft,
+    previous: InputConfig,
+}
+
+impl InputConfigGuard<'_> {
+    /// 切换之前的参数
+    pub fn previous(&self) -> &InputConfig {
+        &self.previous
+    }
+}
+
+impl Drop for InputConfigGuard<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self.dm.apply_input_config(&self.previous);
+        }
+    }
+}
+
+/// 键鼠参数辅助函数
+impl Dmsoft {
+    /// 当前的键鼠参数
+    pub fn input_config(&self) -> InputConfig {
+        *self.input_config.read().unwrap()
+    }
+
+    /// 一次设置所有键鼠参数
+    ///
+    /// 只调用和 [`Dmsoft::input_config`] 相比值有变化的设置函数, 按延时, 同步, 消息, 补丁, 仿真的顺序设置.
+    /// 中途失败也会继续设置剩下的参数, 最后返回最先失败的那个设置的错误(插件返回0时为 [`Error::CallFailed`](crate::Error::CallFailed))
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let mut config = dm.input_config();
+    /// config.keypad_delay.dx = 80;
+    /// dm.apply_input_config(&config).unwrap();
+    /// ```
+    pub unsafe fn apply_input_config(&self, config: &InputConfig) -> Result<()> {
+        let calls: Vec<_> = changes(&self.input_config(), config)
+            .into_iter()
+            .map(|setting| match setting {
+                Setting::MouseDelay(mode, delay) => {
+                    (self.SetMouseDelay(mode, delay), "SetMouseDelay")
+                }
+                Setting::KeypadDelay(mode, delay) => {
+                    (self.SetKeypadDelay(mode, delay), "SetKeypadDelay")
+                }
+                Setting::MouseSync(time_out) => (
+                    self.EnableMouseSync(time_out.is_some(), time_out.unwrap_or(0)),
+                    "EnableMouseSync",
+                ),
+                Setting::KeypadSync(time_out) => (
+                    self.EnableKeypadSync(time_out.is_some(), time_out.unwrap_or(0)),
+                    "EnableKeypadSync",
+                ),
+                Setting::KeypadMsg(en) => (self.EnableKeypadMsg(en), "EnableKeypadMsg"),
+                Setting::MouseMsg(en) => (self.EnableMouseMsg(en), "EnableMouseMsg"),
+                Setting::KeypadPatch(en) => (self.EnableKeypadPatch(en), "EnableKeypadPatch"),
+                Setting::RealMouse(real_mouse) => {
+                    (self.EnableRealMouse(&real_mouse), "EnableRealMouse")
+                }
+                Setting::RealKeypad(en) => (self.EnableRealKeypad(en), "EnableRealKeypad"),
+            })
+            .collect();
+        check_all(calls)
+    }
+
+    /// 临时切换键鼠参数, 返回的 [`InputConfigGuard`] 离开作用域时恢复之前的参数
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// {
+    ///     let _guard = dm.scoped_input_config(&InputConfig { real_keypad: true, ..dm.input_config() }).unwrap();
+    ///     dm.KeyPressStr("hello", 50).unwrap();
+    /// }
+    /// // 恢复为之前的参数
+    /// ```
+    pub unsafe fn scoped_input_config(&self, config: &InputConfig) -> Result<InputConfigGuard<'_>> {
+        let previous = self.input_config();
+        let guard = InputConfigGuard { dm: self, previous };
+        self.apply_input_config(config)?;
+        Ok(guard)
+    }
+
+    /// 设置成功后记录键鼠参数
+    pub(crate) fn track_input_config(&self, ret: i32, update: impl FnOnce(&mut InputConfig)) {
+        if ret != 0 {
+            update(&mut self.input_config.write().unwrap());
+        }
+    }
+}
+
+/// 一次设置函数调用
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Setting {
+    MouseDelay(InputMode, i32),
+    KeypadDelay(InputMode, i32),
+    MouseSync(Option<i32>),
+    KeypadSync(Option<i32>),
+    KeypadMsg(bool),
+    MouseMsg(bool),
+    KeypadPatch(bool),
+    RealMouse(RealMouse),
+    RealKeypad(bool),
+}
+
+/// 从`from`切换到`to`需要调用的设置函数, 按延时, 同步, 消息, 补丁, 仿真的顺序
+fn changes(from: &InputConfig, to: &InputConfig) -> Vec<Setting> {
+    let mut changes = Vec::new();
+    for mode in InputMode::ALL {
+        if from.mouse_delay.get(mode) != to.mouse_delay.get(mode) {
+            changes.push(Setting::MouseDelay(mode, to.mouse_delay.get(mode)));
+        }
+        if from.keypad_delay.get(mode) != to.keypad_delay.get(mode) {
+            changes.push(Setting::KeypadDelay(mode, to.keypad_delay.get(mode)));
+        }
+    }
+    let others = [
+        (
+            from.mouse_sync != to.mouse_sync,
+            Setting::MouseSync(to.mouse_sync),
+        ),
+        (
+            from.keypad_sync != to.keypad_sync,
+            Setting::KeypadSync(to.keypad_sync),
+        ),
+        (
+            from.keypad_msg != to.keypad_msg,
+            Setting::KeypadMsg(to.keypad_msg),
+        ),
+        (
+            from.mouse_msg != to.mouse_msg,
+            Setting::MouseMsg(to.mouse_msg),
+        ),
+        (
+            from.keypad_patch != to.keypad_patch,
+            Setting::KeypadPatch(to.keypad_patch),
+        ),
+        (
+            from.real_mouse != to.real_mouse,
+            Setting::RealMouse(to.real_mouse),
+        ),
+        (
+            from.real_keypad != to.real_keypad,
+            Setting::RealKeypad(to.real_keypad),
+        ),
+    ];
+    changes.extend(
+        others
+            .into_iter()
+            .filter(|(changed, _)| *changed)
+            .map(|(_, setting)| setting),
+    );
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plugin_defaults() {
+        let config = InputConfig::default();
+        assert!(config.keypad_msg && config.mouse_msg);
+        assert!(!config.keypad_patch && !config.real_keypad);
+        assert_eq!((config.mouse_sync, config.keypad_sync), (None, None));
+        assert_eq!(config.real_mouse.mode, RealMouseMode::Off);
+    }
+
+    #[test]
+    fn no_changes() {
+        let config = InputConfig::default();
+        assert!(changes(&config, &config).is_empty());
+    }
+
+    #[test]
+    fn delays_only() {
+        let from = InputConfig::default();
+        let mut to = from;
+        to.keypad_delay.dx = 80;
+        to.mouse_delay.set(InputMode::Normal, 5);
+        // 只改延时时不会调用只能在绑定之后调用的函数
+        assert_eq!(
+            changes(&from, &to),
+            [
+                Setting::MouseDelay(InputMode::Normal, 5),
+                Setting::KeypadDelay(InputMode::Dx, 80)
+            ]
+        );
+        // 恢复时只改回延时
+        assert_eq!(
+            changes(&to, &from),
+            [
+                Setting::MouseDelay(InputMode::Normal, 30),
+                Setting::KeypadDelay(InputMode::Dx, 50)
+            ]
+        );
+    }
+
+    #[test]
+    fn ordered_changes() {
+        let from = InputConfig::default();
+        let to = InputConfig {
+            real_keypad: true,
+            mouse_msg: false,
+            mouse_sync: Some(100),
+            real_mouse: RealMouse {
+                mode: RealMouseMode::Curve,
+                ..RealMouse::default()
+            },
+            ..from
+        };
+        assert_eq!(
+            changes(&from, &to),
+            [
+                Setting::MouseSync(Some(100)),
+                Setting::MouseMsg(false),
+                Setting::RealMouse(to.real_mouse),
+                Setting::RealKeypad(true)
+            ]
+        );
+    }
+}
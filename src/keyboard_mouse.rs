@@ -1,6 +1,13 @@
 use std::mem::ManuallyDrop;
 
-use crate::{Dmsoft, KeyMap, Result};
+use windows::Win32::System::Com::VARIANT;
+
+use crate::{
+    held::Held,
+    input_config::{InputMode, RealMouse},
+    pic::parse_point,
+    CursorShape, Dmsoft, KeyMap, Point, Result,
+};
 #[allow(non_snake_case)]
 impl Dmsoft {
     /// 按住指定的虚拟键码
@@ -23,8 +30,10 @@ impl Dmsoft {
 
         let result = self.Invoke(NAME, &mut args)?;
         let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_held(result, Held::Key(vk.get_id()), true);
 
-        Ok(result.Anonymous.lVal)
+        Ok(result)
     }
 
     /// 弹起来虚拟键
@@ -47,8 +56,10 @@ impl Dmsoft {
 
         let result = self.Invoke(NAME, &mut args)?;
         let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_held(result, Held::Key(vk.get_id()), false);
 
-        Ok(result.Anonymous.lVal)
+        Ok(result)
     }
 
     /// 按下鼠标左键
@@ -152,8 +163,10 @@ impl Dmsoft {
         static NAME: &str = "LeftDown";
         let result = self.Invoke(NAME, &mut [])?;
         let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_held(result, Held::LeftButton, true);
 
-        Ok(result.Anonymous.lVal)
+        Ok(result)
     }
 
     /// 弹起鼠标左键
@@ -173,8 +186,10 @@ impl Dmsoft {
         static NAME: &str = "LeftUp";
         let result = self.Invoke(NAME, &mut [])?;
         let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_held(result, Held::LeftButton, false);
 
-        Ok(result.Anonymous.lVal)
+        Ok(result)
     }
 
     /// 按住鼠标右键
@@ -194,8 +209,10 @@ impl Dmsoft {
         static NAME: &str = "RightDown";
         let result = self.Invoke(NAME, &mut [])?;
         let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_held(result, Held::RightButton, true);
 
-        Ok(result.Anonymous.lVal)
+        Ok(result)
     }
 
     /// 弹起鼠标右键
@@ -215,6 +232,96 @@ impl Dmsoft {
         static NAME: &str = "RightUp";
         let result = self.Invoke(NAME, &mut [])?;
         let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_held(result, Held::RightButton, false);
+
+        Ok(result)
+    }
+
+    /// 按住鼠标中键
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::MiddleDown()
+    /// ```
+    /// # Args
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.MiddleDown().unwrap();
+    /// ```
+    pub unsafe fn MiddleDown(&self) -> Result<i32> {
+        static NAME: &str = "MiddleDown";
+        let result = self.Invoke(NAME, &mut [])?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_held(result, Held::MiddleButton, true);
+
+        Ok(result)
+    }
+
+    /// 弹起鼠标中键
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::MiddleUp()
+    /// ```
+    /// # Args
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.MiddleUp().unwrap();
+    /// ```
+    pub unsafe fn MiddleUp(&self) -> Result<i32> {
+        static NAME: &str = "MiddleUp";
+        let result = self.Invoke(NAME, &mut [])?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_held(result, Held::MiddleButton, false);
+
+        Ok(result)
+    }
+
+    /// 滚轮向上滚
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::WheelUp()
+    /// ```
+    /// # Args
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.WheelUp().unwrap();
+    /// ```
+    pub unsafe fn WheelUp(&self) -> Result<i32> {
+        static NAME: &str = "WheelUp";
+        let result = self.Invoke(NAME, &mut [])?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 滚轮向下滚
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::WheelDown()
+    /// ```
+    /// # Args
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.WheelDown().unwrap();
+    /// ```
+    pub unsafe fn WheelDown(&self) -> Result<i32> {
+        static NAME: &str = "WheelDown";
+        let result = self.Invoke(NAME, &mut [])?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
 
         Ok(result.Anonymous.lVal)
     }
@@ -293,4 +400,683 @@ impl Dmsoft {
 
         Ok(result.Anonymous.lVal)
     }
+
+    /// 按下指定的虚拟键码
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::KeyPressChar(const TCHAR * key_str)
+    /// ```
+    /// # Args
+    /// * `key_str:&str`: 字符串描述的键码. 大小写无所谓, 同 [`KeyMap`] 的 `key_str`
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.KeyPressChar("enter").unwrap();
+    /// let status = dm.KeyPressChar("1").unwrap();
+    /// ```
+    pub unsafe fn KeyPressChar(&self, key_str: &str) -> Result<i32> {
+        static NAME: &str = "KeyPressChar";
+        let mut args = [Dmsoft::bstrVal(key_str)];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 根据指定的字符串序列, 依次按顺序按下其中的字符
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::KeyPressStr(const TCHAR * key_str,long delay)
+    /// ```
+    /// # Args
+    /// * `key_str:&str`: 需要按下的字符串序列. 比如"1234","abcd","7389,1462"等
+    /// * `delay:i32`: 每按下一个按键, 需要延时多久. 单位毫秒. 这个值越大, 按的速度越慢
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.KeyPressStr("123,456", 20).unwrap();
+    /// ```
+    /// # Note
+    /// 在某些情况下, SendString和SendString2都无法输入文字时, 可以考虑用这个来输入.
+    /// 但这个接口只支持标准的文字, 比如中文就不支持
+    pub unsafe fn KeyPressStr(&self, key_str: &str, delay: i32) -> Result<i32> {
+        static NAME: &str = "KeyPressStr";
+        let mut args = [Dmsoft::longVar(delay), Dmsoft::bstrVal(key_str)];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 向指定窗口发送文本数据
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SendString(long hwnd,const TCHAR * str)
+    /// ```
+    /// # Args
+    /// * `hwnd:i32`: 指定的窗口句柄. 如果为0, 则对当前激活的窗口发送
+    /// * `str:&str`: 发送的文本数据
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let hwnd = 0;
+    /// let status = dm.SendString(hwnd, "你好").unwrap();
+    /// ```
+    /// # Note
+    /// 有些窗口此接口不一定有效, 可以尝试 `SendString2` 或者 `SendStringIme`
+    pub unsafe fn SendString(&self, hwnd: i32, str: &str) -> Result<i32> {
+        static NAME: &str = "SendString";
+        let mut args = [Dmsoft::bstrVal(str), Dmsoft::longVar(hwnd)];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 向指定窗口发送文本数据, 和 `SendString` 使用的方式不同
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SendString2(long hwnd,const TCHAR * str)
+    /// ```
+    /// # Args
+    /// * `hwnd:i32`: 指定的窗口句柄. 如果为0, 则对当前激活的窗口发送
+    /// * `str:&str`: 发送的文本数据
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let hwnd = 0;
+    /// let status = dm.SendString2(hwnd, "你好").unwrap();
+    /// ```
+    pub unsafe fn SendString2(&self, hwnd: i32, str: &str) -> Result<i32> {
+        static NAME: &str = "SendString2";
+        let mut args = [Dmsoft::bstrVal(str), Dmsoft::longVar(hwnd)];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 向绑定的窗口发送文本数据, 通过输入法的方式发送
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SendStringIme(const TCHAR * str)
+    /// ```
+    /// # Args
+    /// * `str:&str`: 发送的文本数据
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.SendStringIme("你好").unwrap();
+    /// ```
+    /// # Note
+    /// 必须先绑定窗口, 并且绑定的窗口需要有输入焦点
+    pub unsafe fn SendStringIme(&self, str: &str) -> Result<i32> {
+        static NAME: &str = "SendStringIme";
+        let mut args = [Dmsoft::bstrVal(str)];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 向指定窗口发送粘贴命令, 把剪贴板的内容发送到目标窗口
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SendPaste(long hwnd)
+    /// ```
+    /// # Args
+    /// * `hwnd:i32`: 指定的窗口句柄. 如果为0, 则对当前激活的窗口发送
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let hwnd = 0;
+    /// dm.SetClipboard("你好").unwrap();
+    /// let status = dm.SendPaste(hwnd).unwrap();
+    /// ```
+    pub unsafe fn SendPaste(&self, hwnd: i32) -> Result<i32> {
+        static NAME: &str = "SendPaste";
+        let mut args = [Dmsoft::longVar(hwnd)];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 把鼠标移动到目的范围内的任意一点
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::MoveToEx(long x,long y,long w,long h)
+    /// ```
+    /// # Args
+    /// * `x:i32`: X坐标
+    /// * `y:i32`: Y坐标
+    /// * `w:i32`: 宽度(从x计算起)
+    /// * `h:i32`: 高度(从y计算起)
+    /// # Return
+    /// `Option<Point>`: 实际移动到的坐标, 失败时为 `None`
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// // 移动到(100,100)-(110,110)范围内的随机一点
+    /// let pos = dm.MoveToEx(100,100,10,10).unwrap();
+    /// ```
+    /// # Note
+    /// 此函数的意思是移动鼠标到指定的范围(x,y,x+w,y+h)内的任意一点
+    pub unsafe fn MoveToEx(&self, x: i32, y: i32, w: i32, h: i32) -> Result<Option<Point>> {
+        static NAME: &str = "MoveToEx";
+        let mut args = [
+            Dmsoft::longVar(h),
+            Dmsoft::longVar(w),
+            Dmsoft::longVar(y),
+            Dmsoft::longVar(x),
+        ];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(parse_point(&result))
+    }
+
+    /// 获取鼠标位置
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::GetCursorPos(long * x,long * y)
+    /// ```
+    /// # Args
+    /// # Return
+    /// `Option<Point>`: 鼠标的坐标, 失败时为 `None`
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// if let Some(pos) = dm.GetCursorPos().unwrap() {
+    ///     println!("{},{}", pos.x, pos.y);
+    /// }
+    /// ```
+    /// # Note
+    /// 此接口在3.1223版本之后, 返回的值的定义修改. 同大多数接口一样, 返回的x,y坐标是根据绑定的鼠标参数来决定.
+    /// 如果绑定了窗口, 那么获取的坐标是相对于绑定窗口, 否则是屏幕坐标
+    pub unsafe fn GetCursorPos(&self) -> Result<Option<Point>> {
+        static NAME: &str = "GetCursorPos";
+        let mut px = VARIANT::default();
+        let mut py = VARIANT::default();
+        let mut args = [Dmsoft::pvarVal(&mut py), Dmsoft::pvarVal(&mut px)];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let pos = Point::new(
+            px.Anonymous.Anonymous.Anonymous.lVal,
+            py.Anonymous.Anonymous.Anonymous.lVal,
+        );
+
+        Ok((result.Anonymous.lVal != 0).then_some(pos))
+    }
+
+    /// 获取鼠标特征码. 当 `BindWindow` 或者 `BindWindowEx` 中的mouse参数含有dx.mouse.cursor时,
+    /// 获取到的是后台鼠标特征, 否则是前台鼠标特征
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::GetCursorShape()
+    /// ```
+    /// # Args
+    /// # Return
+    /// `CursorShape`: 鼠标特征码, 失败时为空
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let shape = dm.GetCursorShape().unwrap();
+    /// println!("{}", shape);
+    /// ```
+    pub unsafe fn GetCursorShape(&self) -> Result<CursorShape> {
+        static NAME: &str = "GetCursorShape";
+        let result = self.Invoke(NAME, &mut [])?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(CursorShape::new(result))
+    }
+
+    /// 获取鼠标特征码, 可以指定特征码的计算方式
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::GetCursorShapeEx(long tpe)
+    /// ```
+    /// # Args
+    /// * `tpe:i32`: 获取鼠标特征码的方式. 和工具中的方式1 方式2对应. 方式1此参数值为0, 方式2此参数值为1
+    /// # Return
+    /// `CursorShape`: 鼠标特征码, 失败时为空
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let shape = dm.GetCursorShapeEx(1).unwrap();
+    /// ```
+    /// # Note
+    /// 有些时候, 有些游戏的鼠标形状很特殊, 用方式1无法区分不同的形状时可以用方式2
+    pub unsafe fn GetCursorShapeEx(&self, tpe: i32) -> Result<CursorShape> {
+        static NAME: &str = "GetCursorShapeEx";
+        let mut args = [Dmsoft::longVar(tpe)];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(CursorShape::new(result))
+    }
+
+    /// 获取鼠标热点位置. (参考工具中抓取鼠标后, 那个闪动的点就是热点坐标, 不是鼠标坐标)
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::GetCursorSpot()
+    /// ```
+    /// # Args
+    /// # Return
+    /// `Option<Point>`: 热点相对于鼠标图片左上角的坐标, 失败时为 `None`
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let spot = dm.GetCursorSpot().unwrap();
+    /// ```
+    pub unsafe fn GetCursorSpot(&self) -> Result<Option<Point>> {
+        static NAME: &str = "GetCursorSpot";
+        let result = self.Invoke(NAME, &mut [])?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(parse_point(&result))
+    }
+
+    /// 获取系统鼠标的移动速度
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::GetMouseSpeed()
+    /// ```
+    /// # Args
+    /// # Return
+    /// `i32`: 0: 失败 其他值: 当前系统鼠标的移动速度. 为1-11, 6为系统默认值
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let speed = dm.GetMouseSpeed().unwrap();
+    /// ```
+    pub unsafe fn GetMouseSpeed(&self) -> Result<i32> {
+        static NAME: &str = "GetMouseSpeed";
+        let result = self.Invoke(NAME, &mut [])?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 设置系统鼠标的移动速度
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SetMouseSpeed(long speed)
+    /// ```
+    /// # Args
+    /// * `speed:i32`: 鼠标移动速度, 最小1, 最大11. 居中为6. 推荐设置为6
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.SetMouseSpeed(6).unwrap();
+    /// ```
+    pub unsafe fn SetMouseSpeed(&self, speed: i32) -> Result<i32> {
+        static NAME: &str = "SetMouseSpeed";
+        let mut args = [Dmsoft::longVar(speed)];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 设置当前系统鼠标的精确度开关
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::EnableMouseAccuracy(long en)
+    /// ```
+    /// # Args
+    /// * `en:bool`: false 关闭指针精确度开关. true 打开指针精确度开关. 一般推荐关闭
+    /// # Return
+    /// `i32`: 设置之前的精确度开关
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let previous = dm.EnableMouseAccuracy(false).unwrap();
+    /// ```
+    pub unsafe fn EnableMouseAccuracy(&self, en: bool) -> Result<i32> {
+        static NAME: &str = "EnableMouseAccuracy";
+        let mut args = [Dmsoft::longVar(en as i32)];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 设置鼠标单击或者双击时, 鼠标按下和弹起的时间间隔
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SetMouseDelay(const TCHAR * tpe,long delay)
+    /// ```
+    /// # Args
+    /// * `tpe:InputMode`: 鼠标模式. 默认延时 normal 30, windows 10, dx 40
+    /// * `delay:i32`: 延时, 单位毫秒
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.SetMouseDelay(InputMode::Dx, 10).unwrap();
+    /// ```
+    /// # Note
+    /// 高级用户使用. 某些窗口可能需要调整这个参数才可以正常点击
+    pub unsafe fn SetMouseDelay(&self, tpe: InputMode, delay: i32) -> Result<i32> {
+        static NAME: &str = "SetMouseDelay";
+        let mut args = [Dmsoft::longVar(delay), Dmsoft::bstrVal(tpe.as_str())];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_input_config(result, |config| config.mouse_delay.set(tpe, delay));
+
+        Ok(result)
+    }
+
+    /// 设置按键时, 键盘按下和弹起的时间间隔
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SetKeypadDelay(const TCHAR * tpe,long delay)
+    /// ```
+    /// # Args
+    /// * `tpe:InputMode`: 键盘模式. 默认延时 normal 30, windows 10, dx 50
+    /// * `delay:i32`: 延时, 单位毫秒
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.SetKeypadDelay(InputMode::Dx, 10).unwrap();
+    /// ```
+    /// # Note
+    /// 高级用户使用. 某些窗口可能需要调整这个参数才可以正常按键
+    pub unsafe fn SetKeypadDelay(&self, tpe: InputMode, delay: i32) -> Result<i32> {
+        static NAME: &str = "SetKeypadDelay";
+        let mut args = [Dmsoft::longVar(delay), Dmsoft::bstrVal(tpe.as_str())];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_input_config(result, |config| config.keypad_delay.set(tpe, delay));
+
+        Ok(result)
+    }
+
+    /// 设置dx鼠标是否采用同步模式, 默认关闭
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::EnableMouseSync(long en,long time_out)
+    /// ```
+    /// # Args
+    /// * `en:bool`: 是否开启同步模式
+    /// * `time_out:i32`: 同步等待的最大时间, 单位毫秒
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.EnableMouseSync(true, 100).unwrap();
+    /// ```
+    /// # Note
+    /// 只对dx鼠标有效
+    pub unsafe fn EnableMouseSync(&self, en: bool, time_out: i32) -> Result<i32> {
+        static NAME: &str = "EnableMouseSync";
+        let mut args = [Dmsoft::longVar(time_out), Dmsoft::longVar(en as i32)];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_input_config(result, |config| config.mouse_sync = en.then_some(time_out));
+
+        Ok(result)
+    }
+
+    /// 设置dx键盘是否采用同步模式, 默认关闭
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::EnableKeypadSync(long en,long time_out)
+    /// ```
+    /// # Args
+    /// * `en:bool`: 是否开启同步模式
+    /// * `time_out:i32`: 同步等待的最大时间, 单位毫秒
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.EnableKeypadSync(true, 100).unwrap();
+    /// ```
+    /// # Note
+    /// 只对dx键盘有效
+    pub unsafe fn EnableKeypadSync(&self, en: bool, time_out: i32) -> Result<i32> {
+        static NAME: &str = "EnableKeypadSync";
+        let mut args = [Dmsoft::longVar(time_out), Dmsoft::longVar(en as i32)];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_input_config(result, |config| config.keypad_sync = en.then_some(time_out));
+
+        Ok(result)
+    }
+
+    /// 开启或者关闭键盘消息, 默认开启
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::EnableKeypadMsg(long en)
+    /// ```
+    /// # Args
+    /// * `en:bool`: 是否开启
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.EnableKeypadMsg(true).unwrap();
+    /// ```
+    pub unsafe fn EnableKeypadMsg(&self, en: bool) -> Result<i32> {
+        static NAME: &str = "EnableKeypadMsg";
+        let mut args = [Dmsoft::longVar(en as i32)];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_input_config(result, |config| config.keypad_msg = en);
+
+        Ok(result)
+    }
+
+    /// 开启或者关闭鼠标消息, 默认开启
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::EnableMouseMsg(long en)
+    /// ```
+    /// # Args
+    /// * `en:bool`: 是否开启
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.EnableMouseMsg(true).unwrap();
+    /// ```
+    pub unsafe fn EnableMouseMsg(&self, en: bool) -> Result<i32> {
+        static NAME: &str = "EnableMouseMsg";
+        let mut args = [Dmsoft::longVar(en as i32)];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_input_config(result, |config| config.mouse_msg = en);
+
+        Ok(result)
+    }
+
+    /// 开启或者关闭键盘补丁, 默认关闭
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::EnableKeypadPatch(long en)
+    /// ```
+    /// # Args
+    /// * `en:bool`: 是否开启
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.EnableKeypadPatch(true).unwrap();
+    /// ```
+    pub unsafe fn EnableKeypadPatch(&self, en: bool) -> Result<i32> {
+        static NAME: &str = "EnableKeypadPatch";
+        let mut args = [Dmsoft::longVar(en as i32)];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_input_config(result, |config| config.keypad_patch = en);
+
+        Ok(result)
+    }
+
+    /// 设置是否模拟真实鼠标移动, 开启后 `MoveTo` 等函数会分多步移动到目标点
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::EnableRealMouse(long en,long mousedelay,long mousestep)
+    /// ```
+    /// # Args
+    /// * `real_mouse:&RealMouse`: 模拟方式, 每步的延时(毫秒)和每步的最大步长(像素)
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.EnableRealMouse(&RealMouse { mode: RealMouseMode::Curve, delay: 20, step: 30 }).unwrap();
+    /// ```
+    pub unsafe fn EnableRealMouse(&self, real_mouse: &RealMouse) -> Result<i32> {
+        static NAME: &str = "EnableRealMouse";
+        let mut args = [
+            Dmsoft::longVar(real_mouse.step),
+            Dmsoft::longVar(real_mouse.delay),
+            Dmsoft::longVar(real_mouse.mode as i32),
+        ];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_input_config(result, |config| config.real_mouse = *real_mouse);
+
+        Ok(result)
+    }
+
+    /// 设置键盘动作是否模拟真实操作, 开启后按键延时随机, 默认关闭
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::EnableRealKeypad(long en)
+    /// ```
+    /// # Args
+    /// * `en:bool`: 是否开启
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.EnableRealKeypad(true).unwrap();
+    /// ```
+    pub unsafe fn EnableRealKeypad(&self, en: bool) -> Result<i32> {
+        static NAME: &str = "EnableRealKeypad";
+        let mut args = [Dmsoft::longVar(en as i32)];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_input_config(result, |config| config.real_keypad = en);
+
+        Ok(result)
+    }
+
+    /// 获取指定的按键状态.(前台信息,不是后台)
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::GetKeyState(long vk)
+    /// ```
+    /// # Args
+    /// * `vk:KeyMap<'a>`:  虚拟按键码
+    /// # Return
+    /// `i32`: 0: 弹起 1: 按下
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let state = dm.GetKeyState(KeyMap::KEY_SHIFT).unwrap();
+    /// ```
+    pub unsafe fn GetKeyState(&self, vk: KeyMap) -> Result<i32> {
+        static NAME: &str = "GetKeyState";
+        let mut args = [Dmsoft::longVar(vk.get_id())];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 等待指定的按键按下(前台, 不是后台)
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::WaitKey(long key_code,long time_out)
+    /// ```
+    /// # Args
+    /// * `vk:KeyMap<'a>`: 等待的虚拟按键码, id为0时等待任意键
+    /// * `time_out:i32`: 等待多久, 单位毫秒. 0表示一直等待
+    /// # Return
+    /// `i32`: 0: 超时 1: 指定的按键按下(`vk`不为0时) 其他值: 按下的虚拟按键码(`vk`为0时)
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// // 最多等待5秒, 直到按下F1
+    /// let ret = dm.WaitKey(KeyMap::KEY_F1, 5000).unwrap();
+    /// ```
+    pub unsafe fn WaitKey(&self, vk: KeyMap, time_out: i32) -> Result<i32> {
+        static NAME: &str = "WaitKey";
+        let mut args = [Dmsoft::longVar(time_out), Dmsoft::longVar(vk.get_id())];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+
+        Ok(result.Anonymous.lVal)
+    }
 }
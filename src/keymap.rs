@@ -3,6 +3,8 @@
 #![allow(dead_code)]
 #![warn(missing_docs)]
 
+use std::{fmt, str::FromStr};
+
 use super::KeyMap;
 
 macro_rules! key {
@@ -15,95 +17,494 @@ macro_rules! key {
             };
         }
     };
+    ($ident:ident, vk_only $name:expr, $id:expr) => {
+        impl KeyMap<'_> {
+            #[doc= concat!( "名字: ", stringify!($name), "\tid: ",stringify!($id), "\t大漠没有这个键的按键名, 只能使用虚拟键码") ]
+            pub const $ident: KeyMap<'static> = KeyMap {
+                key_str: "",
+                id: $id,
+            };
+        }
+    };
+}
+
+/// 批量定义按键, 同时生成包含所有按键的 [`KeyMap::ALL`] 和 [`VK_NAMES`]
+///
+/// `named` 中是大漠键码对应表里的按键, `vk_only` 中是大漠没有按键名的按键和解析, 显示时使用的名字
+macro_rules! keys {
+    (
+        named { $($ident:ident, $key_str:expr, $id:expr;)* }
+        vk_only { $($vk_ident:ident, $vk_name:expr, $vk_id:expr;)* }
+    ) => {
+        $(key!($ident, $key_str, $id);)*
+        $(key!($vk_ident, vk_only $vk_name, $vk_id);)*
+
+        impl KeyMap<'_> {
+            /// 所有按键
+            pub const ALL: &'static [KeyMap<'static>] =
+                &[$(KeyMap::$ident,)* $(KeyMap::$vk_ident),*];
+        }
+
+        /// 大漠没有按键名的按键在解析和显示时使用的名字, 这些名字不能传给 `KeyPressChar` 等函数
+        const VK_NAMES: &[(&str, i32)] = &[$(($vk_name, $vk_id)),*];
+    };
 }
 
 // pub const ONC:KeyMap = KeyMap{key_str: "1", id:49};
 
-key!(KEY_0, "0", 48);
-key!(KEY_1, "1", 49);
-key!(KEY_2, "2", 50);
-key!(KEY_3, "3", 51);
-key!(KEY_4, "4", 52);
-key!(KEY_5, "5", 53);
-key!(KEY_6, "6", 54);
-key!(KEY_7, "7", 55);
-key!(KEY_8, "8", 56);
-key!(KEY_9, "9", 57);
-
-key!(KEY_MINUS, "-", 189);
-key!(KEY_EQUAL, "=", 187);
-
-key!(KEY_BACK, "back", 8);
-
-key!(KEY_A, "a", 65);
-key!(KEY_B, "b", 66);
-key!(KEY_C, "c", 67);
-key!(KEY_D, "d", 68);
-key!(KEY_E, "e", 69);
-key!(KEY_F, "f", 70);
-key!(KEY_G, "g", 71);
-key!(KEY_H, "h", 72);
-key!(KEY_I, "i", 73);
-key!(KEY_J, "j", 74);
-key!(KEY_K, "k", 75);
-key!(KEY_L, "l", 76);
-key!(KEY_M, "m", 77);
-key!(KEY_N, "n", 78);
-key!(KEY_O, "o", 79);
-key!(KEY_P, "p", 80);
-key!(KEY_Q, "q", 81);
-key!(KEY_R, "r", 82);
-key!(KEY_S, "s", 83);
-key!(KEY_T, "t", 84);
-key!(KEY_U, "u", 85);
-key!(KEY_V, "v", 86);
-key!(KEY_W, "w", 87);
-key!(KEY_X, "x", 88);
-key!(KEY_Y, "y", 89);
-key!(KEY_Z, "z", 90);
-
-key!(KEY_CTRL, "ctrl", 17);
-key!(KEY_ALT, "alt", 18);
-key!(KEY_SHIFT, "shift", 16);
-key!(KEY_WIN, "win", 91);
-key!(KEY_SPACE, "space", 32);
-key!(KEY_CAP, "cap", 20);
-key!(KEY_TAB, "tab", 9);
-key!(KEY_WAVY_LINES, "~", 192);
-key!(KEY_ESC, "esc", 27);
-key!(KEY_ENTER, "enter", 13);
-
-key!(KEY_UP, "up", 38);
-key!(KEY_DOWN, "down", 40);
-key!(KEY_LEFT, "left", 37);
-key!(KEY_RIGHT, "right", 39);
-
-key!(KEY_OPTION, "option", 93);
-key!(KEY_PRINT, "print", 44);
-key!(KEY_DELETE, "delete", 46);
-key!(KEY_HOME, "home", 36);
-key!(KEY_END, "end", 35);
-key!(KEY_PGUP, "pgup", 33);
-key!(KEY_PGDN, "pgdn", 34);
-
-key!(KEY_F1, "f1", 112);
-key!(KEY_F2, "f2", 113);
-key!(KEY_F3, "f3", 114);
-key!(KEY_F4, "f4", 115);
-key!(KEY_F5, "f5", 116);
-key!(KEY_F6, "f6", 117);
-key!(KEY_F7, "f7", 118);
-key!(KEY_F8, "f8", 119);
-key!(KEY_F9, "f9", 120);
-key!(KEY_F10, "f10", 121);
-key!(KEY_F11, "f11", 122);
-key!(KEY_F12, "f12", 123);
-
-key!(KEY_OPEN_BRACKET, "[", 219);
-key!(KEY_CLOSE_BRACKET, "]", 221);
-key!(KEY_BACKSLASH, "\\", 220);
-key!(KEY_SEMICOLON, ";", 186);
-key!(KEY_SINGLE_QUOTES, "'", 222);
-key!(KEY_COMMA, ",", 188);
-key!(KEY_DOT, ".", 190);
-key!(KEY_SLASH, "/", 191);
+keys! {
+    named {
+        KEY_0, "0", 48;
+        KEY_1, "1", 49;
+        KEY_2, "2", 50;
+        KEY_3, "3", 51;
+        KEY_4, "4", 52;
+        KEY_5, "5", 53;
+        KEY_6, "6", 54;
+        KEY_7, "7", 55;
+        KEY_8, "8", 56;
+        KEY_9, "9", 57;
+
+        KEY_MINUS, "-", 189;
+        KEY_EQUAL, "=", 187;
+
+        KEY_BACK, "back", 8;
+
+        KEY_A, "a", 65;
+        KEY_B, "b", 66;
+        KEY_C, "c", 67;
+        KEY_D, "d", 68;
+        KEY_E, "e", 69;
+        KEY_F, "f", 70;
+        KEY_G, "g", 71;
+        KEY_H, "h", 72;
+        KEY_I, "i", 73;
+        KEY_J, "j", 74;
+        KEY_K, "k", 75;
+        KEY_L, "l", 76;
+        KEY_M, "m", 77;
+        KEY_N, "n", 78;
+        KEY_O, "o", 79;
+        KEY_P, "p", 80;
+        KEY_Q, "q", 81;
+        KEY_R, "r", 82;
+        KEY_S, "s", 83;
+        KEY_T, "t", 84;
+        KEY_U, "u", 85;
+        KEY_V, "v", 86;
+        KEY_W, "w", 87;
+        KEY_X, "x", 88;
+        KEY_Y, "y", 89;
+        KEY_Z, "z", 90;
+
+        KEY_CTRL, "ctrl", 17;
+        KEY_ALT, "alt", 18;
+        KEY_SHIFT, "shift", 16;
+        KEY_WIN, "win", 91;
+        KEY_SPACE, "space", 32;
+        KEY_CAP, "cap", 20;
+        KEY_TAB, "tab", 9;
+        KEY_WAVY_LINES, "~", 192;
+        KEY_ESC, "esc", 27;
+        KEY_ENTER, "enter", 13;
+
+        KEY_UP, "up", 38;
+        KEY_DOWN, "down", 40;
+        KEY_LEFT, "left", 37;
+        KEY_RIGHT, "right", 39;
+
+        KEY_OPTION, "option", 93;
+        KEY_PRINT, "print", 44;
+        KEY_DELETE, "delete", 46;
+        KEY_HOME, "home", 36;
+        KEY_END, "end", 35;
+        KEY_PGUP, "pgup", 33;
+        KEY_PGDN, "pgdn", 34;
+
+        KEY_F1, "f1", 112;
+        KEY_F2, "f2", 113;
+        KEY_F3, "f3", 114;
+        KEY_F4, "f4", 115;
+        KEY_F5, "f5", 116;
+        KEY_F6, "f6", 117;
+        KEY_F7, "f7", 118;
+        KEY_F8, "f8", 119;
+        KEY_F9, "f9", 120;
+        KEY_F10, "f10", 121;
+        KEY_F11, "f11", 122;
+        KEY_F12, "f12", 123;
+
+        KEY_OPEN_BRACKET, "[", 219;
+        KEY_CLOSE_BRACKET, "]", 221;
+        KEY_BACKSLASH, "\\", 220;
+        KEY_SEMICOLON, ";", 186;
+        KEY_SINGLE_QUOTES, "'", 222;
+        KEY_COMMA, ",", 188;
+        KEY_DOT, ".", 190;
+        KEY_SLASH, "/", 191;
+    }
+
+    vk_only {
+        KEY_INSERT, "insert", 45;
+        KEY_PAUSE, "pause", 19;
+        KEY_SCROLL_LOCK, "scroll", 145;
+        KEY_NUM_LOCK, "numlock", 144;
+        KEY_CLEAR, "clear", 12;
+        KEY_SELECT, "select", 41;
+        KEY_EXECUTE, "execute", 43;
+        KEY_HELP, "help", 47;
+        KEY_SLEEP, "sleep", 95;
+
+        KEY_NUM_0, "num0", 96;
+        KEY_NUM_1, "num1", 97;
+        KEY_NUM_2, "num2", 98;
+        KEY_NUM_3, "num3", 99;
+        KEY_NUM_4, "num4", 100;
+        KEY_NUM_5, "num5", 101;
+        KEY_NUM_6, "num6", 102;
+        KEY_NUM_7, "num7", 103;
+        KEY_NUM_8, "num8", 104;
+        KEY_NUM_9, "num9", 105;
+        KEY_NUM_MULTIPLY, "num*", 106;
+        KEY_NUM_ADD, "num+", 107;
+        KEY_NUM_SEPARATOR, "numsep", 108;
+        KEY_NUM_SUBTRACT, "num-", 109;
+        KEY_NUM_DECIMAL, "num.", 110;
+        KEY_NUM_DIVIDE, "num/", 111;
+
+        KEY_F13, "f13", 124;
+        KEY_F14, "f14", 125;
+        KEY_F15, "f15", 126;
+        KEY_F16, "f16", 127;
+        KEY_F17, "f17", 128;
+        KEY_F18, "f18", 129;
+        KEY_F19, "f19", 130;
+        KEY_F20, "f20", 131;
+        KEY_F21, "f21", 132;
+        KEY_F22, "f22", 133;
+        KEY_F23, "f23", 134;
+        KEY_F24, "f24", 135;
+
+        KEY_LSHIFT, "lshift", 160;
+        KEY_RSHIFT, "rshift", 161;
+        KEY_LCTRL, "lctrl", 162;
+        KEY_RCTRL, "rctrl", 163;
+        KEY_LALT, "lalt", 164;
+        KEY_RALT, "ralt", 165;
+        KEY_RWIN, "rwin", 92;
+
+        KEY_BROWSER_BACK, "browser_back", 166;
+        KEY_BROWSER_FORWARD, "browser_forward", 167;
+        KEY_BROWSER_REFRESH, "browser_refresh", 168;
+        KEY_BROWSER_STOP, "browser_stop", 169;
+        KEY_BROWSER_SEARCH, "browser_search", 170;
+        KEY_BROWSER_FAVORITES, "browser_favorites", 171;
+        KEY_BROWSER_HOME, "browser_home", 172;
+
+        KEY_VOLUME_MUTE, "volume_mute", 173;
+        KEY_VOLUME_DOWN, "volume_down", 174;
+        KEY_VOLUME_UP, "volume_up", 175;
+        KEY_MEDIA_NEXT, "media_next", 176;
+        KEY_MEDIA_PREV, "media_prev", 177;
+        KEY_MEDIA_STOP, "media_stop", 178;
+        KEY_MEDIA_PLAY_PAUSE, "media_play_pause", 179;
+        KEY_LAUNCH_MAIL, "launch_mail", 180;
+        KEY_LAUNCH_MEDIA, "launch_media", 181;
+        KEY_LAUNCH_APP1, "launch_app1", 182;
+        KEY_LAUNCH_APP2, "launch_app2", 183;
+
+        KEY_OEM_102, "oem_102", 226;
+
+        KEY_LBUTTON, "lbutton", 1;
+        KEY_RBUTTON, "rbutton", 2;
+        KEY_CANCEL, "cancel", 3;
+        KEY_MBUTTON, "mbutton", 4;
+        KEY_XBUTTON1, "xbutton1", 5;
+        KEY_XBUTTON2, "xbutton2", 6;
+    }
+}
+
+/// 按键名的别名, 解析时先转换为小写再查找, 目标是大漠的按键名或者 [`VK_NAMES`] 中的名字
+const ALIASES: &[(&str, &str)] = &[
+    ("control", "ctrl"),
+    ("menu", "alt"),
+    ("windows", "win"),
+    ("lwin", "win"),
+    ("return", "enter"),
+    ("escape", "esc"),
+    ("backspace", "back"),
+    ("bs", "back"),
+    ("caps", "cap"),
+    ("capslock", "cap"),
+    ("capital", "cap"),
+    ("apps", "option"),
+    ("context", "option"),
+    ("prtsc", "print"),
+    ("printscreen", "print"),
+    ("snapshot", "print"),
+    ("del", "delete"),
+    ("ins", "insert"),
+    ("pageup", "pgup"),
+    ("prior", "pgup"),
+    ("pagedown", "pgdn"),
+    ("pagedn", "pgdn"),
+    ("next", "pgdn"),
+    ("break", "pause"),
+    ("scrolllock", "scroll"),
+    ("scrlk", "scroll"),
+    ("numpad0", "num0"),
+    ("numpad1", "num1"),
+    ("numpad2", "num2"),
+    ("numpad3", "num3"),
+    ("numpad4", "num4"),
+    ("numpad5", "num5"),
+    ("numpad6", "num6"),
+    ("numpad7", "num7"),
+    ("numpad8", "num8"),
+    ("numpad9", "num9"),
+    ("multiply", "num*"),
+    ("add", "num+"),
+    ("separator", "numsep"),
+    ("subtract", "num-"),
+    ("decimal", "num."),
+    ("divide", "num/"),
+    ("lcontrol", "lctrl"),
+    ("rcontrol", "rctrl"),
+    ("lmenu", "lalt"),
+    ("rmenu", "ralt"),
+    ("arrowup", "up"),
+    ("arrowdown", "down"),
+    ("arrowleft", "left"),
+    ("arrowright", "right"),
+    ("tilde", "~"),
+    ("grave", "~"),
+    ("`", "~"),
+    ("minus", "-"),
+    ("equal", "="),
+    ("equals", "="),
+    ("lbracket", "["),
+    ("rbracket", "]"),
+    ("backslash", "\\"),
+    ("semicolon", ";"),
+    ("quote", "'"),
+    ("comma", ","),
+    ("period", "."),
+    ("dot", "."),
+    ("slash", "/"),
+];
+
+/// 按键名解析失败
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseKeyError(String);
+
+impl fmt::Display for ParseKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "未知的按键: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseKeyError {}
+
+impl KeyMap<'_> {
+    /// 按虚拟键码查找按键, 没有这个键码时返回 `None`
+    pub fn from_id(id: i32) -> Option<KeyMap<'static>> {
+        KeyMap::ALL.iter().find(|key| key.id == id).copied()
+    }
+
+    /// 大漠的键码对应表中有这个键的按键名时返回 `true`, 否则只能使用虚拟键码
+    pub fn has_key_str(&self) -> bool {
+        !self.key_str.is_empty()
+    }
+
+    /// 显示和解析使用的名字: 有按键名时为按键名, 否则为 [`VK_NAMES`] 中的名字
+    pub(crate) fn name(&self) -> &str {
+        if self.has_key_str() {
+            return self.key_str;
+        }
+        VK_NAMES
+            .iter()
+            .find(|(_, id)| *id == self.id)
+            .map_or("", |(name, _)| name)
+    }
+}
+
+/// 解析按键名, 不区分大小写, 支持常用的别名(比如 "pageup", "control", "return")
+///
+/// 除了大漠的按键名, 也可以解析大漠没有按键名的键(比如 "num0", "lshift", "insert"),
+/// 这些键的 [`KeyMap::get_key_str`] 为空, 只能使用虚拟键码
+/// # Examples
+/// ```
+/// let key: KeyMap = "PageUp".parse().unwrap();
+/// assert_eq!(key, KeyMap::KEY_PGUP);
+/// let key: KeyMap = "numpad0".parse().unwrap();
+/// assert_eq!((key.get_key_str(), key.get_id()), ("", 96));
+/// ```
+impl FromStr for KeyMap<'static> {
+    type Err = ParseKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        let name = ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == name)
+            .map_or(name.as_str(), |(_, key_str)| key_str);
+        let id = VK_NAMES
+            .iter()
+            .find(|(vk_name, _)| *vk_name == name)
+            .map(|&(_, id)| id);
+        KeyMap::ALL
+            .iter()
+            .find(|key| key.has_key_str() && key.key_str == name || Some(key.id) == id)
+            .copied()
+            .ok_or_else(|| ParseKeyError(s.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    /// 大漠文档键码对应表中的所有按键名和虚拟键码
+    const DM_TABLE: &[(&str, i32)] = &[
+        ("1", 49),
+        ("2", 50),
+        ("3", 51),
+        ("4", 52),
+        ("5", 53),
+        ("6", 54),
+        ("7", 55),
+        ("8", 56),
+        ("9", 57),
+        ("0", 48),
+        ("-", 189),
+        ("=", 187),
+        ("back", 8),
+        ("a", 65),
+        ("b", 66),
+        ("c", 67),
+        ("d", 68),
+        ("e", 69),
+        ("f", 70),
+        ("g", 71),
+        ("h", 72),
+        ("i", 73),
+        ("j", 74),
+        ("k", 75),
+        ("l", 76),
+        ("m", 77),
+        ("n", 78),
+        ("o", 79),
+        ("p", 80),
+        ("q", 81),
+        ("r", 82),
+        ("s", 83),
+        ("t", 84),
+        ("u", 85),
+        ("v", 86),
+        ("w", 87),
+        ("x", 88),
+        ("y", 89),
+        ("z", 90),
+        ("ctrl", 17),
+        ("alt", 18),
+        ("shift", 16),
+        ("win", 91),
+        ("space", 32),
+        ("cap", 20),
+        ("tab", 9),
+        ("~", 192),
+        ("esc", 27),
+        ("enter", 13),
+        ("up", 38),
+        ("down", 40),
+        ("left", 37),
+        ("right", 39),
+        ("option", 93),
+        ("print", 44),
+        ("delete", 46),
+        ("home", 36),
+        ("end", 35),
+        ("pgup", 33),
+        ("pgdn", 34),
+        ("f1", 112),
+        ("f2", 113),
+        ("f3", 114),
+        ("f4", 115),
+        ("f5", 116),
+        ("f6", 117),
+        ("f7", 118),
+        ("f8", 119),
+        ("f9", 120),
+        ("f10", 121),
+        ("f11", 122),
+        ("f12", 123),
+        ("[", 219),
+        ("]", 221),
+        ("\\", 220),
+        (";", 186),
+        ("'", 222),
+        (",", 188),
+        (".", 190),
+        ("/", 191),
+    ];
+
+    #[test]
+    fn key_str_matches_dm_table() {
+        let named: Vec<(&str, i32)> = KeyMap::ALL
+            .iter()
+            .filter(|key| key.has_key_str())
+            .map(|key| (key.get_key_str(), key.get_id()))
+            .collect();
+        let mut expected = DM_TABLE.to_vec();
+        expected.sort_by_key(|&(_, id)| id);
+        let mut named_sorted = named.clone();
+        named_sorted.sort_by_key(|&(_, id)| id);
+        assert_eq!(named_sorted, expected);
+
+        assert_eq!(KeyMap::KEY_NUM_0.get_key_str(), "");
+        assert_eq!(KeyMap::KEY_LSHIFT.get_key_str(), "");
+        assert_eq!(KeyMap::KEY_BROWSER_BACK.get_key_str(), "");
+    }
+
+    #[test]
+    fn unique_ids_and_names() {
+        let ids: HashSet<i32> = KeyMap::ALL.iter().map(|key| key.get_id()).collect();
+        assert_eq!(ids.len(), KeyMap::ALL.len());
+        let names: HashSet<&str> = KeyMap::ALL.iter().map(|key| key.name()).collect();
+        assert_eq!(names.len(), KeyMap::ALL.len());
+        assert!(!names.contains(""));
+        // 别名不能和按键名重复, 并且都能解析
+        for (alias, target) in ALIASES {
+            assert!(!names.contains(alias), "{alias}");
+            assert!(names.contains(target), "{alias} -> {target}");
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        for &key in KeyMap::ALL {
+            let name = key.to_string();
+            assert_eq!(name.parse::<KeyMap>(), Ok(key), "{name}");
+            assert_eq!(name.to_uppercase().parse::<KeyMap>(), Ok(key), "{name}");
+            assert_eq!(KeyMap::from_id(key.get_id()), Some(key));
+        }
+        assert_eq!(KeyMap::from_id(0), None);
+        assert_eq!(KeyMap::from_id(255), None);
+    }
+
+    #[test]
+    fn aliases() {
+        let parse = |s: &str| s.parse::<KeyMap>();
+        assert_eq!(parse(" Return "), Ok(KeyMap::KEY_ENTER));
+        assert_eq!(parse("numpad0"), Ok(KeyMap::KEY_NUM_0));
+        assert_eq!(parse("num0"), Ok(KeyMap::KEY_NUM_0));
+        assert_eq!(parse("LShift"), Ok(KeyMap::KEY_LSHIFT));
+        assert_eq!(parse("`"), Ok(KeyMap::KEY_WAVY_LINES));
+        assert_eq!(KeyMap::KEY_NUM_0.to_string(), "num0");
+        assert_eq!(KeyMap::KEY_NUM_0.get_id(), 96);
+        assert_eq!(parse(""), Err(ParseKeyError(String::new())));
+        assert!(parse("num").is_err());
+    }
+}
@@ -4,21 +4,27 @@
 #![allow(clippy::missing_safety_doc, clippy::too_many_arguments)]
 
 use std::{
-    collections::HashMap,
+    borrow::Cow,
+    collections::{HashMap, HashSet},
     ffi::c_char,
+    fmt,
     mem::ManuallyDrop,
-    os::windows::prelude::OsStrExt,
-    path::Path,
+    path::PathBuf,
     ptr,
     sync::RwLock,
 };
 
+#[cfg(all(windows, feature = "reg"))]
+use std::{os::windows::prelude::OsStrExt, path::Path};
+
 // use once_cell::sync::OnceCell;
 use windows::{
     core::{BSTR, HSTRING, PCWSTR},
     Win32::System::Com::{self, IDispatch, DISPPARAMS, VARIANT, VARIANT_0, VARIANT_0_0},
 };
 
+use crate::{cancel::CancelToken, held::Held, input_config::InputConfig, ocr_config::OcrConfig};
+
 #[cfg(feature = "reg")]
 // #[link(name = "DmReg", kind = "static")]
 extern "system" {
@@ -28,7 +34,7 @@ extern "system" {
     pub fn SetDllPathW(path: *const c_char, status: usize) -> usize;
 }
 
-#[cfg(feature = "reg")]
+#[cfg(all(windows, feature = "reg"))]
 #[allow(missing_docs)]
 pub unsafe fn set_dll_path(dm_path: impl AsRef<Path>) -> usize {
     let v: Vec<_> = dm_path
@@ -485,6 +491,34 @@ impl CastToVTVar for VARIANT {
 #[cfg(feature = "keymap")]
 pub mod keymap;
 
+pub mod bmp;
+pub mod cancel;
+#[cfg(feature = "keymap")]
+pub mod chord;
+pub mod click;
+pub mod color;
+pub mod dict;
+pub mod dict_file;
+pub mod find_str;
+#[cfg(test)]
+mod fixtures;
+#[cfg(feature = "font")]
+pub mod font_dict;
+pub mod fuzzy;
+pub mod held;
+#[cfg(feature = "keymap")]
+pub mod hotkey;
+pub mod input_config;
+pub mod ocr;
+pub mod ocr_config;
+pub mod pic;
+pub mod pic_match;
+pub mod timeline;
+pub mod trainer;
+pub mod trajectory;
+#[cfg(feature = "keymap")]
+pub mod typing;
+
 /// 在windows-rs 中并未搜索到此参数 使用本地定义 来源:
 /// [Windows LOCALE_USER_DEFAULT](https://docs.microsoft.com/en-us/windows/win32/intl/locale-user-default)
 pub const LOCALE_USER_DEFAULT: u32 = 0x0400;
@@ -496,6 +530,22 @@ pub struct Dmsoft {
     obj: IDispatch,
     /// Invoke ID 缓存
     catch: RwLock<HashMap<&'static str, i32>>,
+    /// 通过绑定设置过的识字参数
+    ocr_config: RwLock<OcrConfig>,
+    /// 通过绑定按住但还没有弹起的键
+    held: RwLock<HashSet<Held>>,
+    /// 通过绑定设置过的键鼠参数
+    input_config: RwLock<InputConfig>,
+    /// 辅助函数中的等待检查的取消令牌
+    cancel: RwLock<Option<CancelToken>>,
+}
+
+impl Drop for Dmsoft {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self.release_all();
+        }
+    }
 }
 
 /// 异常枚举
@@ -505,11 +555,39 @@ pub enum Error {
     WinError(windows::core::Error),
     /// 从缓存区获取的 Invoke ID 为 `-1`
     IdError,
+    /// 文件不存在(相对于 `GetPath` 设置的全局路径)
+    FileNotFound(PathBuf),
+    /// 插件函数返回了失败(0), 参数为函数名
+    CallFailed(&'static str),
+    /// 被 [`CancelToken`](cancel::CancelToken) 取消
+    Cancelled,
+    /// 读写本地文件(截图, 字库等)时产生的错误
+    Io(std::io::Error),
+    /// 按键输入方式无法输入这个字符(美式键盘上不能直接按出)
+    CannotType(char),
 }
 
 /// API Result
 type Result<T> = core::result::Result<T, Error>;
 
+/// 把插件返回的0转换为 [`Error::CallFailed`]
+pub(crate) fn check(ret: Result<i32>, name: &'static str) -> Result<()> {
+    match ret? {
+        0 => Err(Error::CallFailed(name)),
+        _ => Ok(()),
+    }
+}
+
+/// 依次 [`check`] 多个已经执行的调用, 返回第一个错误
+pub(crate) fn check_all(
+    calls: impl IntoIterator<Item = (Result<i32>, &'static str)>,
+) -> Result<()> {
+    calls
+        .into_iter()
+        .map(|(ret, name)| check(ret, name))
+        .fold(Ok(()), Result::and)
+}
+
 /// 大漠插件绑定
 #[allow(non_snake_case)]
 impl Dmsoft {
@@ -525,6 +603,10 @@ impl Dmsoft {
         Ok(Self {
             obj: r,
             catch: RwLock::new(HashMap::new()),
+            ocr_config: RwLock::new(OcrConfig::default()),
+            held: RwLock::new(HashSet::new()),
+            input_config: RwLock::new(InputConfig::default()),
+            cancel: RwLock::new(None),
         })
     }
 
@@ -608,6 +690,29 @@ impl Dmsoft {
         let result = ManuallyDrop::into_inner(result.Anonymous.bstrVal);
         Ok(result.try_into().unwrap())
     }
+
+    /// 获取全局路径.(可用于调试)
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::GetPath()
+    /// ```
+    ///
+    /// # Args
+    /// # Return
+    /// `String`: 以字符串的形式返回当前设置的全局路径
+    ///
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let path = dm.GetPath().unwrap();
+    /// ```
+    pub unsafe fn GetPath(&self) -> Result<String> {
+        static NAME: &str = "GetPath";
+        let result = self.Invoke(NAME, &mut [])?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = ManuallyDrop::into_inner(result.Anonymous.bstrVal);
+        Ok(result.try_into().unwrap())
+    }
 }
 
 mod keyboard_mouse;
@@ -673,6 +778,50 @@ impl Dmsoft {
         Ok(result.Anonymous.lVal)
     }
 
+    /// 设置剪贴板的内容
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SetClipboard(const TCHAR * data)
+    /// ```
+    /// # Args
+    /// * `data:&str`: 需要设置的剪贴板内容
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.SetClipboard("abc").unwrap();
+    /// ```
+    pub unsafe fn SetClipboard(&self, data: &str) -> Result<i32> {
+        static NAME: &str = "SetClipboard";
+        let mut args = [Dmsoft::bstrVal(data)];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 获取剪贴板的内容
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::GetClipboard()
+    /// ```
+    /// # Args
+    /// # Return
+    /// `String`: 以字符串表示的剪贴板内容
+    ///
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let text = dm.GetClipboard().unwrap();
+    /// ```
+    pub unsafe fn GetClipboard(&self) -> Result<String> {
+        static NAME: &str = "GetClipboard";
+        let result = self.Invoke(NAME, &mut [])?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = ManuallyDrop::into_inner(result.Anonymous.bstrVal);
+        Ok(result.try_into().unwrap())
+    }
+
     // TODO: 其他函数映射
 }
 
@@ -792,24 +941,123 @@ impl Dmsoft {
     }
 }
 
-///
-#[derive(Debug)]
+/// 按键, 包含大漠的按键名和虚拟键码
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct KeyMap<'a> {
     key_str: &'a str,
     id: i32,
 }
 
 impl<'a> KeyMap<'a> {
-    ///
+    /// 新建一个按键
     pub fn new(key_str: &'a str, id: i32) -> Self {
         Self { key_str, id }
     }
+    /// 大漠的按键名, 用于 `KeyPressChar` 等函数
     ///
+    /// 大漠的键码对应表中没有这个键时为空字符串, 比如 [`KeyMap::KEY_NUM_0`], 这时只能使用 [`KeyMap::get_id`]
     pub fn get_key_str(&self) -> &'a str {
         self.key_str
     }
-    ///
+    /// 虚拟键码, 用于 `KeyPress` 等函数
     pub fn get_id(&self) -> i32 {
         self.id
     }
 }
+
+/// 输出按键名, 大漠没有按键名的键输出解析时使用的名字(比如 "num0")
+impl fmt::Display for KeyMap<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[cfg(feature = "keymap")]
+        let name = self.name();
+        #[cfg(not(feature = "keymap"))]
+        let name = self.key_str;
+        f.write_str(name)
+    }
+}
+
+/// 坐标点
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point {
+    /// X坐标
+    pub x: i32,
+    /// Y坐标
+    pub y: i32,
+}
+
+impl Point {
+    /// 新建一个坐标点
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+/// 鼠标特征码, 由 `GetCursorShape` / `GetCursorShapeEx` 返回, 比较时不区分大小写
+/// # Examples
+/// ```
+/// // 特征码用大漠工具抓取, 这里的值只是示例
+/// const HAND: CursorShape = CursorShape::from_static("6e4fd7b3");
+/// let dm = Dmsoft::new();
+/// if dm.GetCursorShape().unwrap() == HAND {
+///     println!("鼠标悬停在链接上");
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CursorShape(Cow<'static, str>);
+
+impl CursorShape {
+    /// 新建一个特征码
+    pub fn new(shape: impl Into<String>) -> Self {
+        Self(Cow::Owned(shape.into()))
+    }
+
+    /// 用字符串常量新建一个特征码, 可以用于定义常量
+    pub const fn from_static(shape: &'static str) -> Self {
+        Self(Cow::Borrowed(shape))
+    }
+
+    /// 特征码字符串
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// 是否为空, 获取失败时插件返回空的特征码
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl PartialEq for CursorShape {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Eq for CursorShape {}
+
+impl PartialEq<str> for CursorShape {
+    fn eq(&self, other: &str) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialEq<&str> for CursorShape {
+    fn eq(&self, other: &&str) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+}
+
+impl std::hash::Hash for CursorShape {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for b in self.0.bytes() {
+            state.write_u8(b.to_ascii_lowercase());
+        }
+        state.write_u8(0xff);
+    }
+}
+
+impl fmt::Display for CursorShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
@@ -0,0 +1,657 @@
+//! 识字算法的纯 Rust 实现, 使用大漠字库识别内存中的位图, 可以在没有插件的环境下对截图做识字测试
+//!
+//! * 按 [`ColorSpec`] 把区域二值化, 符合颜色的像素为字的点
+//! * 按行间距把点分成多行: 连续空行数量不小于行间距时分行(对应 `SetMinRowGap`)
+//! * 列间距为0时在每一行中逐列匹配字库, 可以识别连体字; 不为0时先按列间距切分字符再整体匹配(对应 `SetMinColGap`)
+//! * 字库点阵和区域中的点不同的数量不超过 (1-sim) * 点数 时算作匹配, 同一位置多个字匹配时取点数最多的
+//!
+//! 区域(x1, y1, x2, y2)包含左上角, 不包含右下角, 超出位图的部分会被忽略. 返回的坐标为字左上角在位图中的坐标.
+
+use crate::{
+    bmp::Bmp,
+    color::ColorSpec,
+    dict_file::{Dict, Glyph},
+    pic_match::max_miss,
+    Dmsoft, Point, Result,
+};
+
+/// 识别出的一个字符
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecognizedChar {
+    /// 字符
+    pub ch: String,
+    /// 字符左上角的坐标
+    pub pos: Point,
+}
+
+/// 识别出的一个单词
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Word {
+    /// 单词
+    pub text: String,
+    /// 第一个字符左上角的坐标
+    pub pos: Point,
+}
+
+/// 识字器, 保存了字库中所有字的点阵
+/// # Examples
+/// ```
+/// let dict = Dict::open("main.txt").unwrap();
+/// let frame = Bmp::open("screen.bmp").unwrap();
+/// let color: ColorSpec = "ffffff-000000".parse().unwrap();
+/// let s = Recognizer::new(&dict).ocr(&frame, 0, 0, 2000, 2000, &color, 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Recognizer {
+    glyphs: Vec<(String, Glyph)>,
+    row_gap: u32,
+    col_gap: u32,
+    word_gap: u32,
+}
+
+/// 识别过程中的字符, 额外记录宽度和所在的行
+struct Matched {
+    ch: RecognizedChar,
+    width: u32,
+    row: usize,
+}
+
+impl Recognizer {
+    /// 从字库创建识字器, 点阵不合法的条目会被跳过
+    ///
+    /// 默认行间距为1, 列间距为0, 词间距为5
+    pub fn new(dict: &Dict) -> Self {
+        let glyphs = dict
+            .iter()
+            .filter_map(|entry| Some((entry.text.clone(), entry.glyph()?)))
+            .filter(|(_, glyph)| glyph.count_ones() > 0)
+            .collect();
+        Self {
+            glyphs,
+            row_gap: 1,
+            col_gap: 0,
+            word_gap: 5,
+        }
+    }
+
+    /// 设置最小行间距, 返回自身
+    pub fn with_row_gap(mut self, row_gap: u32) -> Self {
+        self.row_gap = row_gap;
+        self
+    }
+
+    /// 设置最小列间距, 返回自身. 不为0时不能识别连体字
+    pub fn with_col_gap(mut self, col_gap: u32) -> Self {
+        self.col_gap = col_gap;
+        self
+    }
+
+    /// 设置词间距, 返回自身. 同一行中相邻字符的间距不小于词间距时分为两个单词
+    pub fn with_word_gap(mut self, word_gap: u32) -> Self {
+        self.word_gap = word_gap;
+        self
+    }
+
+    /// 识别区域内的所有字符, 按从上到下, 从左到右的顺序排列
+    pub fn recognize(
+        &self,
+        frame: &Bmp,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        color: &ColorSpec,
+        sim: f64,
+    ) -> Vec<RecognizedChar> {
+        self.matched(frame, (x1, y1, x2, y2), color, sim)
+            .into_iter()
+            .map(|m| m.ch)
+            .collect()
+    }
+
+    /// 识别区域内的字符串, 对应 `Ocr`
+    pub fn ocr(
+        &self,
+        frame: &Bmp,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        color: &ColorSpec,
+        sim: f64,
+    ) -> String {
+        self.recognize(frame, x1, y1, x2, y2, color, sim)
+            .into_iter()
+            .map(|c| c.ch)
+            .collect()
+    }
+
+    /// 识别区域内的单词, 按词间距和行分词
+    pub fn words(
+        &self,
+        frame: &Bmp,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        color: &ColorSpec,
+        sim: f64,
+    ) -> Vec<Word> {
+        let mut words: Vec<Word> = Vec::new();
+        let mut last: Option<(usize, i32)> = None;
+        for m in self.matched(frame, (x1, y1, x2, y2), color, sim) {
+            let joined = last.is_some_and(|(row, right)| {
+                row == m.row && m.ch.pos.x - right < self.word_gap as i32
+            });
+            last = Some((m.row, m.ch.pos.x + m.width as i32));
+            match words.last_mut() {
+                Some(word) if joined => word.text.push_str(&m.ch.ch),
+                _ => words.push(Word {
+                    text: m.ch.ch,
+                    pos: m.ch.pos,
+                }),
+            }
+        }
+        words
+    }
+
+    fn matched(
+        &self,
+        frame: &Bmp,
+        region: (i32, i32, i32, i32),
+        color: &ColorSpec,
+        sim: f64,
+    ) -> Vec<Matched> {
+        let (mask, x1, y1) = binarize(frame, region, color);
+        let mut result = Vec::new();
+        for (row, (top, bottom)) in self.rows(&mask).into_iter().enumerate() {
+            let found = self.match_row(&mask, top, bottom, sim);
+            result.extend(found.into_iter().map(|(ch, x, y, width)| Matched {
+                ch: RecognizedChar {
+                    ch,
+                    pos: Point::new((x1 + x) as i32, (y1 + y) as i32),
+                },
+                width,
+                row,
+            }));
+        }
+        result
+    }
+
+    /// 区域内没有被字库识别的点, 按行间距和列间距(至少为1)切分为单个点阵, 返回点阵左上角的坐标和点阵
+    pub(crate) fn unknown_glyphs(
+        &self,
+        frame: &Bmp,
+        region: (i32, i32, i32, i32),
+        color: &ColorSpec,
+        sim: f64,
+    ) -> Vec<(Point, Glyph)> {
+        let (mut mask, x1, y1) = binarize(frame, region, color);
+        let mut result = Vec::new();
+        for (top, bottom) in self.rows(&mask) {
+            for (_, x, _, width) in self.match_row(&mask, top, bottom, sim) {
+                for y in top..bottom {
+                    for x in x..x + width {
+                        mask.set(x, y, false);
+                    }
+                }
+            }
+            let filled: Vec<bool> = (0..mask.width())
+                .map(|x| (top..bottom).any(|y| mask.get(x, y)))
+                .collect();
+            for (left, right) in runs(&filled, self.col_gap.max(1)) {
+                let rows: Vec<u32> = (top..bottom)
+                    .filter(|&y| (left..right).any(|x| mask.get(x, y)))
+                    .collect();
+                let (Some(&first), Some(&last)) = (rows.first(), rows.last()) else {
+                    continue;
+                };
+                let mut glyph = Glyph::new(right - left, last - first + 1);
+                for y in first..=last {
+                    for x in left..right {
+                        glyph.set(x - left, y - first, mask.get(x, y));
+                    }
+                }
+                let pos = Point::new((x1 + left) as i32, (y1 + first) as i32);
+                result.push((pos, glyph));
+            }
+        }
+        result
+    }
+
+    /// 识别一行中的字符, 返回 (字符, x, y, 宽度)
+    fn match_row(
+        &self,
+        mask: &Glyph,
+        top: u32,
+        bottom: u32,
+        sim: f64,
+    ) -> Vec<(String, u32, u32, u32)> {
+        if self.col_gap == 0 {
+            self.scan_row(mask, top, bottom, sim)
+        } else {
+            self.split_row(mask, top, bottom, sim)
+        }
+    }
+
+    /// 按行间距分行, 返回每一行的 [top, bottom)
+    fn rows(&self, mask: &Glyph) -> Vec<(u32, u32)> {
+        let filled: Vec<bool> = (0..mask.height())
+            .map(|y| (0..mask.width()).any(|x| mask.get(x, y)))
+            .collect();
+        runs(&filled, self.row_gap.max(1))
+    }
+
+    /// 列间距为0: 逐列尝试匹配字库
+    fn scan_row(
+        &self,
+        mask: &Glyph,
+        top: u32,
+        bottom: u32,
+        sim: f64,
+    ) -> Vec<(String, u32, u32, u32)> {
+        let mut found = Vec::new();
+        let mut x = 0;
+        while x < mask.width() {
+            if !(top..bottom).any(|y| mask.get(x, y)) {
+                x += 1;
+                continue;
+            }
+            let best = self
+                .glyphs
+                .iter()
+                .filter(|(_, g)| g.height() <= bottom - top && x + g.width() <= mask.width())
+                .flat_map(|(text, g)| {
+                    (top..=bottom - g.height()).filter_map(move |y| {
+                        let miss = misses(mask, x, y, g, sim)?;
+                        Some((g.count_ones(), std::cmp::Reverse(miss), text, y, g.width()))
+                    })
+                })
+                .max_by_key(|&(ones, miss, ..)| (ones, miss));
+            match best {
+                Some((_, _, text, y, width)) => {
+                    found.push((text.clone(), x, y, width));
+                    x += width;
+                }
+                None => x += 1,
+            }
+        }
+        found
+    }
+
+    /// 列间距不为0: 按列间距切分字符后整体匹配字库
+    fn split_row(
+        &self,
+        mask: &Glyph,
+        top: u32,
+        bottom: u32,
+        sim: f64,
+    ) -> Vec<(String, u32, u32, u32)> {
+        let filled: Vec<bool> = (0..mask.width())
+            .map(|x| (top..bottom).any(|y| mask.get(x, y)))
+            .collect();
+        runs(&filled, self.col_gap)
+            .into_iter()
+            .filter_map(|(left, right)| {
+                let rows: Vec<u32> = (top..bottom)
+                    .filter(|&y| (left..right).any(|x| mask.get(x, y)))
+                    .collect();
+                let (y, height) = (*rows.first()?, rows.last()? - rows.first()? + 1);
+                self.glyphs
+                    .iter()
+                    .filter(|(_, g)| g.width() == right - left && g.height() == height)
+                    .filter_map(|(text, g)| Some((misses(mask, left, y, g, sim)?, text)))
+                    .min_by_key(|&(miss, _)| miss)
+                    .map(|(_, text)| (text.clone(), left, y, right - left))
+            })
+            .collect()
+    }
+}
+
+/// 把区域二值化, 返回点阵和区域左上角在位图中的坐标
+fn binarize(
+    frame: &Bmp,
+    (x1, y1, x2, y2): (i32, i32, i32, i32),
+    color: &ColorSpec,
+) -> (Glyph, u32, u32) {
+    let clamp = |v: i32, max: u32| v.clamp(0, max as i32) as u32;
+    let (x1, x2) = (clamp(x1, frame.width()), clamp(x2, frame.width()));
+    let (y1, y2) = (clamp(y1, frame.height()), clamp(y2, frame.height()));
+    let mut mask = Glyph::new(x2.saturating_sub(x1), y2.saturating_sub(y1));
+    for y in 0..mask.height() {
+        for x in 0..mask.width() {
+            mask.set(x, y, color.matches(frame.pixel(x1 + x, y1 + y)));
+        }
+    }
+    (mask, x1, y1)
+}
+
+/// 把连续的 `true` 合并为区间 [start, end), 中间连续 `false` 的数量小于`gap`时不分开
+fn runs(filled: &[bool], gap: u32) -> Vec<(u32, u32)> {
+    let mut result: Vec<(u32, u32)> = Vec::new();
+    for (i, _) in filled.iter().enumerate().filter(|(_, &f)| f) {
+        let i = i as u32;
+        match result.last_mut() {
+            Some((_, end)) if i - *end < gap => *end = i + 1,
+            _ => result.push((i, i + 1)),
+        }
+    }
+    result
+}
+
+/// 点阵放在(x,y)时不同的点数, 超过相似度允许的数量时返回 `None`
+fn misses(mask: &Glyph, x: u32, y: u32, glyph: &Glyph, sim: f64) -> Option<usize> {
+    let (mut total, mut miss) = (0, 0);
+    for dy in 0..glyph.height() {
+        for dx in 0..glyph.width() {
+            let (a, b) = (mask.get(x + dx, y + dy), glyph.get(dx, dy));
+            total += (a || b) as usize;
+            miss += (a != b) as usize;
+        }
+    }
+    (miss <= max_miss(total, sim)).then_some(miss)
+}
+
+/// 把字符按行分组, 纵坐标和行首字符相差不超过`tolerance`的字符属于同一行
+///
+/// 返回的行按从上到下排列, 每行中的字符按从左到右排列
+pub fn group_lines(chars: &[RecognizedChar], tolerance: i32) -> Vec<Vec<RecognizedChar>> {
+    let mut sorted = chars.to_vec();
+    sorted.sort_by_key(|c| (c.pos.y, c.pos.x));
+    let mut lines: Vec<Vec<RecognizedChar>> = Vec::new();
+    for c in sorted {
+        match lines
+            .iter_mut()
+            .find(|line| (line[0].pos.y - c.pos.y).abs() <= tolerance)
+        {
+            Some(line) => line.push(c),
+            None => lines.push(vec![c]),
+        }
+    }
+    for line in &mut lines {
+        line.sort_by_key(|c| c.pos.x);
+    }
+    lines
+}
+
+/// 把同一行的字符分为单词, 相邻两个字符左上角的横坐标相差超过`max_advance`(一般为字宽加上词间距)时分开
+pub fn group_words(line: &[RecognizedChar], max_advance: i32) -> Vec<Word> {
+    let mut words: Vec<Word> = Vec::new();
+    let mut last_x = None;
+    for c in line {
+        match words.last_mut() {
+            Some(word) if last_x.is_some_and(|x| c.pos.x - x <= max_advance) => {
+                word.text.push_str(&c.ch)
+            }
+            _ => words.push(Word {
+                text: c.ch.clone(),
+                pos: c.pos,
+            }),
+        }
+        last_x = Some(c.pos.x);
+    }
+    words
+}
+
+/// 解析 `OcrEx` 返回的 "char$x$y|char$x$y" 格式, 字符本身可以是 '$'
+pub(crate) fn parse_chars(ret: &str) -> Vec<RecognizedChar> {
+    ret.split('|')
+        .filter_map(|item| {
+            let mut it = item.rsplitn(3, '$');
+            let y = it.next()?.trim().parse().ok()?;
+            let x = it.next()?.trim().parse().ok()?;
+            Some(RecognizedChar {
+                ch: it.next()?.to_owned(),
+                pos: Point::new(x, y),
+            })
+        })
+        .collect()
+}
+
+/// 识字辅助函数
+impl Dmsoft {
+    /// 用 `GetWordResultCount` / `GetWordResultPos` / `GetWordResultStr` 解析 `GetWords` 或者
+    /// `GetWordsNoDict` 的返回值, 插件没有公开这个格式串的格式, 所以不在本地解析
+    ///
+    /// 取不到坐标的词组会被跳过
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let s = dm.GetWords(0,0,2000,2000,"ffffff-000000",1.0).unwrap();
+    /// for word in dm.word_results(&s).unwrap() {
+    ///     println!("{}: {},{}", word.text, word.pos.x, word.pos.y);
+    /// }
+    /// ```
+    pub unsafe fn word_results(&self, ret: &str) -> Result<Vec<Word>> {
+        let mut words = Vec::new();
+        for index in 0..self.GetWordResultCount(ret)? {
+            if let Some(pos) = self.GetWordResultPos(ret, index)? {
+                words.push(Word {
+                    text: self.GetWordResultStr(ret, index)?,
+                    pos,
+                });
+            }
+        }
+        Ok(words)
+    }
+
+    /// 调用 `GetWords` 识别词组, 并用 [`Dmsoft::word_results`] 解析为 [`Word`] 列表
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// for word in dm.get_words(0,0,2000,2000,"ffffff-000000",1.0).unwrap() {
+    ///     println!("{}: {},{}", word.text, word.pos.x, word.pos.y);
+    /// }
+    /// ```
+    pub unsafe fn get_words(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        color: &str,
+        sim: f64,
+    ) -> Result<Vec<Word>> {
+        let ret = self.GetWords(x1, y1, x2, y2, color, sim)?;
+        self.word_results(&ret)
+    }
+
+    /// 调用 `GetWordsNoDict` 识别词组(不使用字库), 并用 [`Dmsoft::word_results`] 解析为 [`Word`] 列表
+    pub unsafe fn get_words_no_dict(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        color: &str,
+    ) -> Result<Vec<Word>> {
+        let ret = self.GetWordsNoDict(x1, y1, x2, y2, color)?;
+        self.word_results(&ret)
+    }
+}
+
+/// 测试图片和字库在 `tests/fixtures/ocr` 下:
+/// * `dict.txt`: GBK 编码, 5x7(数字 "1" 为3x7)的 0-9 和 7x7 的 "口"
+/// * `text.bmp`: 120x60, 背景为 000000-606060 的噪点, 字为 f4f4f4-ffffff 的白色
+/// * 区域 (0,0,40,24): 第一行 "1" (2,2) 和 "2" (20,2), 空3行后第二行 "3" (10,12) 和 "4" (28,12)
+/// * 区域 (40,0,120,12): 红色(f8-ff,0-7,0-7)的 "5" (42,2), 空1列后 "6" (48,2), 连在一起的 "7" (56,2) 和 "8" (61,2), 空6列后 "9" (72,2)
+/// * 区域 (0,30,40,40): 有3个点不同(15个点, 并集16个点)的 "9" (2,32) 和 "口" (12,32)
+///
+/// 期望的结果是按插件文档中行间距, 列间距和相似度的说明确定的. 在注册了插件的 Windows 上运行
+/// `cargo test -- --ignored plugin_ocr_fixtures`, 和插件的识别结果比较
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    const ROWS: (i32, i32, i32, i32) = (0, 0, 40, 24);
+    const COLS: (i32, i32, i32, i32) = (40, 0, 120, 12);
+    const SIM: (i32, i32, i32, i32) = (0, 30, 40, 40);
+    const WHITE: &str = "f8f8f8-080808";
+    const WHITE_RED: &str = "f8f8f8-080808|fc0404-040404";
+
+    fn recognizer() -> Recognizer {
+        Recognizer::new(&Dict::open(fixtures::path("ocr", "dict.txt")).unwrap())
+    }
+
+    /// 用给定的行间距和列间距识别 `text.bmp` 中的区域
+    fn ocr(
+        (x1, y1, x2, y2): (i32, i32, i32, i32),
+        row_gap: u32,
+        col_gap: u32,
+        color: &str,
+        sim: f64,
+    ) -> String {
+        recognizer()
+            .with_row_gap(row_gap)
+            .with_col_gap(col_gap)
+            .ocr(
+                &fixtures::bmp("ocr", "text.bmp"),
+                x1,
+                y1,
+                x2,
+                y2,
+                &color.parse().unwrap(),
+                sim,
+            )
+    }
+
+    #[test]
+    fn row_gap() {
+        // 两行之间空3行, 行间距不超过3时分为两行, 否则合为一行按列从左到右识别
+        assert_eq!(ocr(ROWS, 1, 0, WHITE, 1.0), "1234");
+        assert_eq!(ocr(ROWS, 3, 0, WHITE, 1.0), "1234");
+        assert_eq!(ocr(ROWS, 4, 0, WHITE, 1.0), "1324");
+    }
+
+    #[test]
+    fn col_gap() {
+        // 列间距为0时可以识别连体字, 为1时 "78" 无法切开, 为2时 "56" 也无法切开
+        assert_eq!(ocr(COLS, 1, 0, WHITE_RED, 1.0), "56789");
+        assert_eq!(ocr(COLS, 1, 1, WHITE_RED, 1.0), "569");
+        assert_eq!(ocr(COLS, 1, 2, WHITE_RED, 1.0), "9");
+    }
+
+    #[test]
+    fn colors() {
+        // 只有白色时红色的 "5" 不是字的点
+        assert_eq!(ocr(COLS, 1, 0, WHITE, 1.0), "6789");
+        assert_eq!(ocr(COLS, 1, 0, "fc0404-040404", 1.0), "5");
+        assert_eq!(ocr(COLS, 1, 0, "b@303030-303030", 1.0), "56789");
+        assert_eq!(ocr(COLS, 1, 0, "#f9-06", 1.0), "6789");
+        assert_eq!(ocr(COLS, 1, 0, "#f9-06|fc0404-040404", 1.0), "56789");
+    }
+
+    #[test]
+    fn similarity() {
+        // 16个点中3个不同, 相似度不超过 1-3/16 时才能识别
+        assert_eq!(ocr(SIM, 1, 0, WHITE, 1.0), "口");
+        assert_eq!(ocr(SIM, 1, 0, WHITE, 0.85), "口");
+        assert_eq!(ocr(SIM, 1, 0, WHITE, 0.8), "9口");
+        assert_eq!(ocr(SIM, 1, 1, WHITE, 0.8), "9口");
+    }
+
+    #[test]
+    fn positions_and_words() {
+        let frame = fixtures::bmp("ocr", "text.bmp");
+        let color = WHITE_RED.parse().unwrap();
+        let chars = recognizer().recognize(&frame, 0, 0, 120, 12, &color, 1.0);
+        let found: Vec<(&str, i32, i32)> = chars
+            .iter()
+            .map(|c| (c.ch.as_str(), c.pos.x, c.pos.y))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("1", 2, 2),
+                ("2", 20, 2),
+                ("5", 42, 2),
+                ("6", 48, 2),
+                ("7", 56, 2),
+                ("8", 61, 2),
+                ("9", 72, 2)
+            ]
+        );
+
+        // "6" 和 "7" 之间空3列, "8" 和 "9" 之间空6列
+        let text =
+            |words: Vec<Word>| -> Vec<String> { words.into_iter().map(|w| w.text).collect() };
+        let words = recognizer().words(&frame, COLS.0, COLS.1, COLS.2, COLS.3, &color, 1.0);
+        assert_eq!(words[0].pos, Point::new(42, 2));
+        assert_eq!(text(words), ["5678", "9"]);
+        let words = recognizer()
+            .with_word_gap(3)
+            .words(&frame, 40, 0, 120, 12, &color, 1.0);
+        assert_eq!(text(words), ["56", "78", "9"]);
+
+        // 分行后的单词不会跨行
+        let words = recognizer().words(&frame, 0, 0, 40, 24, &color, 1.0);
+        assert_eq!(text(words), ["1", "2", "3", "4"]);
+    }
+
+    #[test]
+    fn color_spec_ranges() {
+        let spec: ColorSpec = WHITE_RED.parse().unwrap();
+        assert_eq!(spec.items().len(), 2);
+        assert_eq!(spec.to_string(), WHITE_RED);
+        let spec: ColorSpec = "b@303030-303030".parse().unwrap();
+        assert!(spec.is_background());
+        assert_eq!(spec.to_string(), "b@303030-303030");
+        assert!("f8f8f8-080808|".parse::<ColorSpec>().is_err());
+        assert!("f8f8f8-0808|#f9".parse::<ColorSpec>().is_err());
+    }
+
+    /// 用插件对同一张图识字, 和 [`Recognizer`] 的结果比较
+    #[cfg(windows)]
+    #[test]
+    #[ignore = "需要注册大漠插件"]
+    fn plugin_ocr_fixtures() {
+        use crate::dict::DictSlot;
+
+        unsafe {
+            let dm = fixtures::plugin("ocr", "text.bmp");
+            assert_eq!(dm.SetDict(DictSlot::default(), "dict.txt").unwrap(), 1);
+            for (region, row_gap, col_gap, color, sim) in [
+                (ROWS, 3, 0, WHITE, 1.0),
+                (ROWS, 4, 0, WHITE, 1.0),
+                (COLS, 1, 0, WHITE_RED, 1.0),
+                (COLS, 1, 1, WHITE_RED, 1.0),
+                (COLS, 1, 2, WHITE_RED, 1.0),
+                (COLS, 1, 0, "b@303030-303030", 1.0),
+                (COLS, 1, 0, "#f9-06|fc0404-040404", 1.0),
+                (SIM, 1, 0, WHITE, 0.85),
+                (SIM, 1, 0, WHITE, 0.8),
+            ] {
+                let (x1, y1, x2, y2) = region;
+                dm.SetMinRowGap(row_gap as i32).unwrap();
+                dm.SetMinColGap(col_gap as i32).unwrap();
+                let found = dm.Ocr(x1, y1, x2, y2, color, sim).unwrap();
+                let expected = ocr(region, row_gap, col_gap, color, sim);
+                assert_eq!(
+                    found, expected,
+                    "{region:?} {row_gap} {col_gap} {color} {sim}"
+                );
+            }
+        }
+    }
+
+    /// `get_words` 通过 `GetWordResultCount` 等函数取出的词组都在区域内
+    #[cfg(windows)]
+    #[test]
+    #[ignore = "需要注册大漠插件"]
+    fn plugin_word_results() {
+        use crate::dict::DictSlot;
+
+        unsafe {
+            let dm = fixtures::plugin("ocr", "text.bmp");
+            assert_eq!(dm.SetDict(DictSlot::default(), "dict.txt").unwrap(), 1);
+            let (x1, y1, x2, y2) = COLS;
+            let words = dm.get_words(x1, y1, x2, y2, WHITE_RED, 1.0).unwrap();
+            assert!(!words.is_empty());
+            for word in words {
+                assert!(!word.text.is_empty());
+                assert!((x1..x2).contains(&word.pos.x) && (y1..y2).contains(&word.pos.y));
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! 识字参数: 把分散在各个全局设置函数中的识字参数合并为一个配置, 一次设置, 可以读取, 可以临时切换
+//!
+//! 插件没有读取这些参数的接口, 读取到的是通过本绑定设置过的值(包括直接调用 `SetMinRowGap` 等函数),
+//! 没有设置过的参数为插件的默认值.
+
+use crate::{check_all, Dmsoft, Result};
+
+/// 所有识字参数, 默认值和插件一致
+/// # Examples
+/// ```
+/// let config = OcrConfig {
+///     exact_ocr: true,
+///     word_gap: 8,
+///     ..OcrConfig::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OcrConfig {
+    /// 识字的最小行间距(`SetMinRowGap`), 默认为1
+    pub min_row_gap: i32,
+    /// 识字的最小列间距(`SetMinColGap`), 默认为0. 不为0时不能识别连体字
+    pub min_col_gap: i32,
+    /// 是否开启精准识别(`SetExactOcr`), 默认关闭
+    pub exact_ocr: bool,
+    /// 词组识别的单词最小间距(`SetWordGap`), 默认为5
+    pub word_gap: i32,
+    /// 词组识别的平均行高(`SetWordLineHeight`), 默认为10
+    pub word_line_height: i32,
+    /// 不使用字库的词组识别的最小行间距(`SetRowGapNoDict`), 默认为1
+    pub row_gap_no_dict: i32,
+    /// 不使用字库的词组识别的最小列间距(`SetColGapNoDict`), 默认为1
+    pub col_gap_no_dict: i32,
+    /// 不使用字库的词组识别的单词最小间距(`SetWordGapNoDict`), 默认为5
+    pub word_gap_no_dict: i32,
+    /// 不使用字库的词组识别的平均行高(`SetWordLineHeightNoDict`), 默认为10
+    pub word_line_height_no_dict: i32,
+}
+
+impl Default for OcrConfig {
+    fn default() -> Self {
+        Self {
+            min_row_gap: 1,
+            min_col_gap: 0,
+            exact_ocr: false,
+            word_gap: 5,
+            word_line_height: 10,
+            row_gap_no_dict: 1,
+            col_gap_no_dict: 1,
+            word_gap_no_dict: 5,
+            word_line_height_no_dict: 10,
+        }
+    }
+}
+
+/// 临时切换的识字参数, 离开作用域时恢复之前的参数
+#[derive(Debug)]
+pub struct OcrConfigGuard<'a> {
+    dm: &'a Dmsoft,
+    previous: OcrConfig,
+}
+
+impl OcrConfigGuard<'_> {
+    /// 切换之前的参数
+    pub fn previous(&self) -> &OcrConfig {
+        &self.previous
+    }
+}
+
+impl Drop for OcrConfigGuard<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self.dm.apply_ocr_config(&self.previous);
+        }
+    }
+}
+
+/// 识字参数辅助函数
+impl Dmsoft {
+    /// 当前的识字参数
+    pub fn ocr_config(&self) -> OcrConfig {
+        *self.ocr_config.read().unwrap()
+    }
+
+    /// 一次设置所有识字参数
+    ///
+    /// 每个参数都会尝试设置, 即使前面的设置已经失败. 有设置失败时返回按设置顺序的第一个错误:
+    /// 插件返回0时为 [`Error::CallFailed`](crate::Error::CallFailed), 调用本身出错时为对应的错误
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// dm.apply_ocr_config(&OcrConfig { min_col_gap: 1, ..OcrConfig::default() }).unwrap();
+    /// assert_eq!(dm.ocr_config().min_col_gap, 1);
+    /// ```
+    pub unsafe fn apply_ocr_config(&self, config: &OcrConfig) -> Result<()> {
+        // 先执行所有设置, 再检查结果, 一个设置失败不会跳过后面的设置
+        let calls = [
+            (self.SetMinRowGap(config.min_row_gap), "SetMinRowGap"),
+            (self.SetMinColGap(config.min_col_gap), "SetMinColGap"),
+            (self.SetExactOcr(config.exact_ocr), "SetExactOcr"),
+            (self.SetWordGap(config.word_gap), "SetWordGap"),
+            (
+                self.SetWordLineHeight(config.word_line_height),
+                "SetWordLineHeight",
+            ),
+            (
+                self.SetRowGapNoDict(config.row_gap_no_dict),
+                "SetRowGapNoDict",
+            ),
+            (
+                self.SetColGapNoDict(config.col_gap_no_dict),
+                "SetColGapNoDict",
+            ),
+            (
+                self.SetWordGapNoDict(config.word_gap_no_dict),
+                "SetWordGapNoDict",
+            ),
+            (
+                self.SetWordLineHeightNoDict(config.word_line_height_no_dict),
+                "SetWordLineHeightNoDict",
+            ),
+        ];
+        check_all(calls)
+    }
+
+    /// 临时切换识字参数, 返回的 [`OcrConfigGuard`] 离开作用域时恢复之前的参数
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// {
+    ///     let _guard = dm.scoped_ocr_config(&OcrConfig { exact_ocr: true, ..dm.ocr_config() }).unwrap();
+    ///     let s = dm.Ocr(0,0,2000,2000,"ffffff-000000",1.0).unwrap();
+    /// }
+    /// // 恢复为之前的参数
+    /// ```
+    pub unsafe fn scoped_ocr_config(&self, config: &OcrConfig) -> Result<OcrConfigGuard<'_>> {
+        let previous = self.ocr_config();
+        let guard = OcrConfigGuard { dm: self, previous };
+        self.apply_ocr_config(config)?;
+        Ok(guard)
+    }
+
+    /// 设置成功后记录识字参数
+    pub(crate) fn track_ocr_config(&self, ret: i32, update: impl FnOnce(&mut OcrConfig)) {
+        if ret != 0 {
+            update(&mut self.ocr_config.write().unwrap());
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! 找图相关的类型: 图片列表, 查找方向, 返回值解析
+
+use std::{fmt, marker::PhantomData, path::Path};
+
+use crate::{check, Dmsoft, Error, Point, Result};
+
+/// 找图的查找方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Dir {
+    /// 0: 从左到右,从上到下
+    #[default]
+    LeftTop = 0,
+    /// 1: 从左到右,从下到上
+    LeftBottom = 1,
+    /// 2: 从右到左,从上到下
+    RightTop = 2,
+    /// 3: 从右到左,从下到上
+    RightBottom = 3,
+}
+
+/// 找图使用的图片列表, 转换为字符串后为 "a.bmp|b.bmp" 格式
+///
+/// 图片文件不存在时插件只会当作没有找到, 可以先用 [`Dmsoft::check_pics`] 检查.
+/// 通过 `LoadPicByte` 加载到内存中的图片没有对应的文件, 需要用 [`PicSet::in_memory`] 标记
+/// # Examples
+/// ```
+/// let pics = PicSet::new().with("test.bmp").with("test2.bmp");
+/// assert_eq!(pics.to_string(), "test.bmp|test2.bmp");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PicSet {
+    names: Vec<String>,
+    in_memory: bool,
+}
+
+impl PicSet {
+    /// 新建一个空的图片列表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加一张图片, 返回自身
+    pub fn with(mut self, name: impl Into<String>) -> Self {
+        self.push(name);
+        self
+    }
+
+    /// 添加一张图片
+    pub fn push(&mut self, name: impl Into<String>) {
+        self.names.push(name.into());
+    }
+
+    /// 标记图片已经通过 `LoadPicByte` 加载到内存中, [`Dmsoft::check_pics`] 不再检查图片文件, 返回自身
+    pub fn in_memory(mut self) -> Self {
+        self.in_memory = true;
+        self
+    }
+
+    /// 是否标记为内存中的图片
+    pub fn is_in_memory(&self) -> bool {
+        self.in_memory
+    }
+
+    /// 所有图片名
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// 第`index`张图片的名字
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.names.get(index).map(String::as_str)
+    }
+
+    /// 图片数量
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// 列表为空时返回 `true`
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// 检查所有图片文件是否存在, 相对路径相对于`base`(一般为 `GetPath` 的返回值)
+    ///
+    /// 遇到第一个不存在的文件时返回 [`Error::FileNotFound`]. 带通配符的图片名(比如 "*.bmp")不检查
+    pub fn check(&self, base: impl AsRef<Path>) -> Result<()> {
+        match self
+            .names
+            .iter()
+            .filter(|name| !name.contains(['*', '?']))
+            .map(|name| base.as_ref().join(name))
+            .find(|path| !path.is_file())
+        {
+            Some(path) => Err(Error::FileNotFound(path)),
+            None => Ok(()),
+        }
+    }
+
+    /// 把插件返回的图片序号转换为 (图片名, 坐标), 序号为`-1`或者越界时返回 `None`
+    pub(crate) fn matched(&self, index: i32, pos: Point) -> Option<(String, Point)> {
+        let name = self.get(usize::try_from(index).ok()?)?;
+        Some((name.to_owned(), pos))
+    }
+
+    /// 解析 "id,x,y|id,x,y" 格式的返回串
+    pub(crate) fn matched_list(&self, ret: &str) -> Vec<(String, Point)> {
+        parse_list(ret)
+            .filter_map(|(index, pos)| self.matched(index.parse().ok()?, pos))
+            .collect()
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for PicSet {
+    fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+        Self {
+            names: iter.into_iter().map(Into::into).collect(),
+            in_memory: false,
+        }
+    }
+}
+
+impl fmt::Display for PicSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.names.join("|"))
+    }
+}
+
+/// 内存图片列表, 借用内存中完整的 BMP 文件数据(比如 `include_bytes!` 嵌入的图片),
+/// 转换为字符串后为 "addr,size|addr,size" 格式, 用于 `FindPicMem` 等函数
+///
+/// 列表借用了图片数据, 保证在调用插件期间数据不会被释放
+/// # Examples
+/// ```
+/// static TEST: &[u8] = include_bytes!("test.bmp");
+/// let data = std::fs::read("test2.bmp").unwrap();
+/// let pics = PicMemSet::new().with("test", TEST).with("test2", &data);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PicMemSet<'a> {
+    pics: Vec<(String, &'a [u8])>,
+}
+
+impl<'a> PicMemSet<'a> {
+    /// 新建一个空的内存图片列表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加一张图片, `name` 为找到图片时返回的名字, `data` 为完整的24位 BMP 文件数据. 返回自身
+    pub fn with(mut self, name: impl Into<String>, data: &'a [u8]) -> Self {
+        self.push(name, data);
+        self
+    }
+
+    /// 添加一张图片, `name` 为找到图片时返回的名字, `data` 为完整的24位 BMP 文件数据
+    pub fn push(&mut self, name: impl Into<String>, data: &'a [u8]) {
+        self.pics.push((name.into(), data));
+    }
+
+    /// 第`index`张图片的名字
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.pics.get(index).map(|(name, _)| name.as_str())
+    }
+
+    /// 第`index`张图片的数据
+    pub fn data(&self, index: usize) -> Option<&'a [u8]> {
+        self.pics.get(index).map(|(_, data)| *data)
+    }
+
+    /// 图片数量
+    pub fn len(&self) -> usize {
+        self.pics.len()
+    }
+
+    /// 列表为空时返回 `true`
+    pub fn is_empty(&self) -> bool {
+        self.pics.is_empty()
+    }
+
+    /// 把插件返回的图片序号转换为 (图片名, 坐标), 序号为`-1`或者越界时返回 `None`
+    pub(crate) fn matched(&self, index: i32, pos: Point) -> Option<(String, Point)> {
+        let name = self.get(usize::try_from(index).ok()?)?;
+        Some((name.to_owned(), pos))
+    }
+
+    /// 解析 "id,x,y|id,x,y" 格式的返回串
+    pub(crate) fn matched_list(&self, ret: &str) -> Vec<(String, Point)> {
+        parse_list(ret)
+            .filter_map(|(index, pos)| self.matched(index.parse().ok()?, pos))
+            .collect()
+    }
+}
+
+/// 输出 "addr,size|addr,size" 格式, 地址为10进制
+impl fmt::Display for PicMemSet<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (_, data)) in self.pics.iter().enumerate() {
+            if i > 0 {
+                f.write_str("|")?;
+            }
+            write!(f, "{},{}", data.as_ptr() as usize, data.len())?;
+        }
+        Ok(())
+    }
+}
+
+/// 通过 `LoadPic` / `LoadPicByte` 预先加载的图片, 离开作用域时调用 `FreePic` 释放
+///
+/// 由 [`Dmsoft::load_pics`] 和 [`Dmsoft::load_pic_bytes`] 创建, 通过 [`LoadedPics::pics`] 获取图片列表用于找图.
+/// # Examples
+/// ```
+/// let dm = Dmsoft::new();
+/// let loaded = dm.load_pics(PicSet::new().with("test.bmp").with("test2.bmp")).unwrap();
+/// let found = dm.FindPic(0,0,2000,2000,loaded.pics(),DeltaColor::ZERO,0.9,Dir::LeftTop).unwrap();
+/// // loaded 离开作用域时释放图片
+/// ```
+#[derive(Debug)]
+pub struct LoadedPics<'a> {
+    dm: &'a Dmsoft,
+    pics: PicSet,
+    /// `LoadPicByte` 加载的图片数据需要在释放之前一直有效
+    _data: PhantomData<&'a [u8]>,
+}
+
+impl LoadedPics<'_> {
+    /// 已加载的图片列表
+    pub fn pics(&self) -> &PicSet {
+        &self.pics
+    }
+}
+
+impl Drop for LoadedPics<'_> {
+    fn drop(&mut self) {
+        if !self.pics.is_empty() {
+            unsafe {
+                let _ = self.dm.FreePic(&self.pics);
+            }
+        }
+    }
+}
+
+/// 解析 "index|x|y" 格式的返回串
+pub(crate) fn parse_index_pos(ret: &str) -> Option<(i32, Point)> {
+    let mut it = ret.split('|').map(|v| v.trim().parse::<i32>());
+    match (it.next()?, it.next()?, it.next()?) {
+        (Ok(index), Ok(x), Ok(y)) => Some((index, Point::new(x, y))),
+        _ => None,
+    }
+}
+
+/// 解析 "x,y" 格式的返回串
+pub(crate) fn parse_point(ret: &str) -> Option<Point> {
+    let (x, y) = ret.split_once(',')?;
+    Some(Point::new(x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+/// 解析 "name,x,y|name,x,y" 格式的返回串, 名字中可以包含 ','
+pub(crate) fn parse_list(ret: &str) -> impl Iterator<Item = (&str, Point)> {
+    ret.split('|').filter_map(|item| {
+        let mut it = item.rsplitn(3, ',');
+        let y = it.next()?.trim().parse().ok()?;
+        let x = it.next()?.trim().parse().ok()?;
+        Some((it.next()?, Point::new(x, y)))
+    })
+}
+
+/// 找图辅助函数
+impl Dmsoft {
+    /// 检查图片列表中的文件是否都存在于 `GetPath` 设置的全局路径下, 不存在时返回 [`Error::FileNotFound`]
+    ///
+    /// 找图函数不会自动检查, 图片文件不存在时插件只会当作没有找到. 需要区分这两种情况时, 在找图之前调用此函数.
+    /// 标记为 [`PicSet::in_memory`] 的列表和带通配符的图片名不检查
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// dm.SetPath("./pic").unwrap();
+    /// let pics = PicSet::new().with("test.bmp");
+    /// dm.check_pics(&pics).unwrap();
+    /// ```
+    pub unsafe fn check_pics(&self, pics: &PicSet) -> Result<()> {
+        if pics.in_memory || pics.is_empty() {
+            return Ok(());
+        }
+        pics.check(self.GetPath()?)
+    }
+
+    /// 通过 `LoadPic` 预先加载图片, 返回的 [`LoadedPics`] 离开作用域时调用 `FreePic` 释放
+    ///
+    /// `LoadPic` 返回失败时返回 [`Error::CallFailed`]
+    pub unsafe fn load_pics(&self, pics: PicSet) -> Result<LoadedPics<'_>> {
+        check(self.LoadPic(&pics), "LoadPic")?;
+        Ok(LoadedPics {
+            dm: self,
+            pics,
+            _data: PhantomData,
+        })
+    }
+
+    /// 通过 `LoadPicByte` 预先加载内存中的图片, 图片名为 `PicMemSet` 中的名字,
+    /// 返回的 [`LoadedPics`] 离开作用域时调用 `FreePic` 释放
+    ///
+    /// 图片数据必须比返回值存活的更久. 任意一张图片加载失败时, 释放已经加载的图片并返回 [`Error::CallFailed`]
+    /// # Examples
+    /// ```
+    /// static TEST: &[u8] = include_bytes!("test.bmp");
+    /// let dm = Dmsoft::new();
+    /// let loaded = dm.load_pic_bytes(&PicMemSet::new().with("test.bmp", TEST)).unwrap();
+    /// let found = dm.FindPic(0,0,2000,2000,loaded.pics(),DeltaColor::ZERO,0.9,Dir::LeftTop).unwrap();
+    /// ```
+    pub unsafe fn load_pic_bytes<'a>(&'a self, pics: &PicMemSet<'a>) -> Result<LoadedPics<'a>> {
+        let mut loaded = LoadedPics {
+            dm: self,
+            pics: PicSet::new().in_memory(),
+            _data: PhantomData,
+        };
+        for (name, data) in &pics.pics {
+            check(self.LoadPicByte(data, name), "LoadPicByte")?;
+            loaded.pics.push(name.as_str());
+        }
+        Ok(loaded)
+    }
+}
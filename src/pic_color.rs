@@ -2,7 +2,11 @@ use std::mem::ManuallyDrop;
 
 use windows::Win32::System::Com::VARIANT;
 
-use crate::{Dmsoft, Result};
+use crate::{
+    color::DeltaColor,
+    pic::{self, Dir, PicMemSet, PicSet},
+    Dmsoft, Point, Result,
+};
 #[allow(non_snake_case)]
 impl Dmsoft {
     /// 抓取指定区域(x1, y1, x2, y2)的图像,保存为file(24位位图)
@@ -44,6 +48,35 @@ impl Dmsoft {
         Ok(result.Anonymous.lVal)
     }
 
+    /// 设定图色的获取方式,默认是显示器或者后台窗口(具体参考BindWindow)
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SetDisplayInput(const TCHAR * mode)
+    /// ```
+    /// # Args
+    /// * `mode:&str`: 图色输入模式
+    ///     * "screen": 默认的模式,表示使用显示器或者后台窗口
+    ///     * "pic:file": 指定输入模式为指定的图片,所有和图色相关的函数(文字识别,找图,找色等)均视为对此图片进行处理.
+    ///       图片可以是相对于 `SetPath` 的路径,也可以是绝对路径
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// dm.SetDisplayInput("pic:test.bmp").unwrap();
+    /// // 对 test.bmp 找图
+    /// dm.SetDisplayInput("screen").unwrap();
+    /// ```
+    /// # Note
+    /// 设定以后,此图片就已经加入了缓冲,如果更改了源图片内容,需要先调用 `FreePic` 释放此缓冲,再重新设置
+    pub unsafe fn SetDisplayInput(&self, mode: &str) -> Result<i32> {
+        static NAME: &str = "SetDisplayInput";
+        let mut args = [Dmsoft::bstrVal(mode)];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        Ok(result.Anonymous.lVal)
+    }
+
     /// 查找指定区域内的颜色,颜色格式"RRGGBB-DRDGDB",注意,和按键的颜色格式相反
     /// # The function prototype
     /// ```C++
@@ -292,4 +325,1030 @@ impl Dmsoft {
         let result = ManuallyDrop::into_inner(result.Anonymous.bstrVal);
         Ok(result.try_into().unwrap())
     }
+
+    /// 查找指定区域内的图片,位图必须是24位色格式,支持透明色,当图像上下左右4个顶点的颜色一样时,则这个颜色将作为透明色处理. 这个函数可以查找多个图片,只返回第一个找到的X Y坐标.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::FindPic(long x1,long y1,long x2,long y2,const TCHAR * pic_name,const TCHAR * delta_color,double sim,long dir,long * x,long * y)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `pic_name:&PicSet`: 图片名,可以是多个图片,比如"test.bmp|test2.bmp|test3.bmp"
+    /// * `delta_color:DeltaColor`: 颜色色偏,比如"203040" 表示RGB的色偏分别是20 30 40 (这里是16进制表示)
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// * `dir:Dir`: 查找方向
+    ///     * 0: 从左到右,从上到下
+    ///     * 1: 从左到右,从下到上
+    ///     * 2: 从右到左,从上到下
+    ///     * 3: 从右到左,从下到上
+    /// # Return
+    /// `Option<(String, Point)>`: 找到的图片名和图片左上角的坐标, 没找到返回 `None`
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let pics = PicSet::new().with("test.bmp").with("test2.bmp");
+    /// if let Some((name, pos)) = dm.FindPic(0,0,2000,2000,&pics,DeltaColor::splat(0x20),0.9,Dir::LeftTop).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    /// ```
+    pub unsafe fn FindPic(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        pic_name: &PicSet,
+        delta_color: DeltaColor,
+        sim: f64,
+        dir: Dir,
+    ) -> Result<Option<(String, Point)>> {
+        static NAME: &str = "FindPic";
+        let mut px = VARIANT::default();
+        let mut py = VARIANT::default();
+        let mut args = [
+            Dmsoft::pvarVal(&mut py),
+            Dmsoft::pvarVal(&mut px),
+            Dmsoft::longVar(dir as i32),
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(&delta_color.to_string()),
+            Dmsoft::bstrVal(&pic_name.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let pos = Point::new(
+            px.Anonymous.Anonymous.Anonymous.lVal,
+            py.Anonymous.Anonymous.Anonymous.lVal,
+        );
+
+        Ok(pic_name.matched(result.Anonymous.lVal, pos))
+    }
+
+    /// 查找指定区域内的图片,位图必须是24位色格式,支持透明色,当图像上下左右4个顶点的颜色一样时,则这个颜色将作为透明色处理. 这个函数可以查找多个图片,只返回第一个找到的X Y坐标. 易语言用不了直接取变量的方式,所以有了这个函数.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::FindPicE(long x1,long y1,long x2,long y2,const TCHAR * pic_name,const TCHAR * delta_color,double sim,long dir)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `pic_name:&PicSet`: 图片名,可以是多个图片,比如"test.bmp|test2.bmp|test3.bmp"
+    /// * `delta_color:DeltaColor`: 颜色色偏,比如"203040" 表示RGB的色偏分别是20 30 40 (这里是16进制表示)
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// * `dir:Dir`: 查找方向
+    ///     * 0: 从左到右,从上到下
+    ///     * 1: 从左到右,从下到上
+    ///     * 2: 从右到左,从上到下
+    ///     * 3: 从右到左,从下到上
+    /// # Return
+    /// `Option<(String, Point)>`: 找到的图片名和图片左上角的坐标, 没找到返回 `None` (插件返回"index|x|y"格式, 在本地转换)
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let pics = PicSet::new().with("test.bmp").with("test2.bmp");
+    /// if let Some((name, pos)) = dm.FindPicE(0,0,2000,2000,&pics,DeltaColor::splat(0x20),0.9,Dir::LeftTop).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    /// ```
+    pub unsafe fn FindPicE(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        pic_name: &PicSet,
+        delta_color: DeltaColor,
+        sim: f64,
+        dir: Dir,
+    ) -> Result<Option<(String, Point)>> {
+        static NAME: &str = "FindPicE";
+        let mut args = [
+            Dmsoft::longVar(dir as i32),
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(&delta_color.to_string()),
+            Dmsoft::bstrVal(&pic_name.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(pic::parse_index_pos(&result).and_then(|(index, pos)| pic_name.matched(index, pos)))
+    }
+
+    /// 查找指定区域内的图片,位图必须是24位色格式,支持透明色,当图像上下左右4个顶点的颜色一样时,则这个颜色将作为透明色处理. 这个函数可以查找多个图片,并且返回所有找到的图像的坐标.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::FindPicEx(long x1,long y1,long x2,long y2,const TCHAR * pic_name,const TCHAR * delta_color,double sim,long dir)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `pic_name:&PicSet`: 图片名,可以是多个图片,比如"test.bmp|test2.bmp|test3.bmp"
+    /// * `delta_color:DeltaColor`: 颜色色偏,比如"203040" 表示RGB的色偏分别是20 30 40 (这里是16进制表示)
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// * `dir:Dir`: 查找方向
+    ///     * 0: 从左到右,从上到下
+    ///     * 1: 从左到右,从下到上
+    ///     * 2: 从右到左,从上到下
+    ///     * 3: 从右到左,从下到上
+    /// # Return
+    /// `Vec<(String, Point)>`: 所有找到的图片名和图片左上角的坐标 (插件返回"id,x,y|id,x,y"格式, 在本地转换)
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let pics = PicSet::new().with("test.bmp").with("test2.bmp");
+    /// for (name, pos) in dm.FindPicEx(0,0,2000,2000,&pics,DeltaColor::splat(0x20),0.9,Dir::LeftTop).unwrap() {
+    ///     println!("{}: {},{}", name, pos.x, pos.y);
+    /// }
+    /// ```
+    pub unsafe fn FindPicEx(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        pic_name: &PicSet,
+        delta_color: DeltaColor,
+        sim: f64,
+        dir: Dir,
+    ) -> Result<Vec<(String, Point)>> {
+        static NAME: &str = "FindPicEx";
+        let mut args = [
+            Dmsoft::longVar(dir as i32),
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(&delta_color.to_string()),
+            Dmsoft::bstrVal(&pic_name.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(pic_name.matched_list(&result))
+    }
+
+    /// 查找指定区域内的图片,位图必须是24位色格式,支持透明色,当图像上下左右4个顶点的颜色一样时,则这个颜色将作为透明色处理. 这个函数可以查找多个图片,只返回第一个找到的X Y坐标. 此函数同FindPic,只是返回的是找到的图片名,而不是序号.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::FindPicS(long x1,long y1,long x2,long y2,const TCHAR * pic_name,const TCHAR * delta_color,double sim,long dir,long * x,long * y)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `pic_name:&PicSet`: 图片名,可以是多个图片,比如"test.bmp|test2.bmp|test3.bmp"
+    /// * `delta_color:DeltaColor`: 颜色色偏,比如"203040" 表示RGB的色偏分别是20 30 40 (这里是16进制表示)
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// * `dir:Dir`: 查找方向
+    ///     * 0: 从左到右,从上到下
+    ///     * 1: 从左到右,从下到上
+    ///     * 2: 从右到左,从上到下
+    ///     * 3: 从右到左,从下到上
+    /// # Return
+    /// `Option<(String, Point)>`: 找到的图片名和图片左上角的坐标, 没找到返回 `None`
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let pics = PicSet::new().with("test.bmp").with("test2.bmp");
+    /// if let Some((name, pos)) = dm.FindPicS(0,0,2000,2000,&pics,DeltaColor::splat(0x20),0.9,Dir::LeftTop).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    /// ```
+    pub unsafe fn FindPicS(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        pic_name: &PicSet,
+        delta_color: DeltaColor,
+        sim: f64,
+        dir: Dir,
+    ) -> Result<Option<(String, Point)>> {
+        static NAME: &str = "FindPicS";
+        let mut px = VARIANT::default();
+        let mut py = VARIANT::default();
+        let mut args = [
+            Dmsoft::pvarVal(&mut py),
+            Dmsoft::pvarVal(&mut px),
+            Dmsoft::longVar(dir as i32),
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(&delta_color.to_string()),
+            Dmsoft::bstrVal(&pic_name.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let pos = Point::new(
+            px.Anonymous.Anonymous.Anonymous.lVal,
+            py.Anonymous.Anonymous.Anonymous.lVal,
+        );
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok((!result.is_empty()).then_some((result, pos)))
+    }
+
+    /// 查找指定区域内的图片,位图必须是24位色格式,支持透明色,当图像上下左右4个顶点的颜色一样时,则这个颜色将作为透明色处理. 这个函数可以查找多个图片,并且返回所有找到的图像的坐标. 此函数同FindPicEx,只是返回的是找到的图片名,而不是序号.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::FindPicExS(long x1,long y1,long x2,long y2,const TCHAR * pic_name,const TCHAR * delta_color,double sim,long dir)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `pic_name:&PicSet`: 图片名,可以是多个图片,比如"test.bmp|test2.bmp|test3.bmp"
+    /// * `delta_color:DeltaColor`: 颜色色偏,比如"203040" 表示RGB的色偏分别是20 30 40 (这里是16进制表示)
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// * `dir:Dir`: 查找方向
+    ///     * 0: 从左到右,从上到下
+    ///     * 1: 从左到右,从下到上
+    ///     * 2: 从右到左,从上到下
+    ///     * 3: 从右到左,从下到上
+    /// # Return
+    /// `Vec<(String, Point)>`: 所有找到的图片名和图片左上角的坐标 (插件返回"file,x,y|file,x,y"格式, 在本地转换)
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let pics = PicSet::new().with("test.bmp").with("test2.bmp");
+    /// for (name, pos) in dm.FindPicExS(0,0,2000,2000,&pics,DeltaColor::splat(0x20),0.9,Dir::LeftTop).unwrap() {
+    ///     println!("{}: {},{}", name, pos.x, pos.y);
+    /// }
+    /// ```
+    pub unsafe fn FindPicExS(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        pic_name: &PicSet,
+        delta_color: DeltaColor,
+        sim: f64,
+        dir: Dir,
+    ) -> Result<Vec<(String, Point)>> {
+        static NAME: &str = "FindPicExS";
+        let mut args = [
+            Dmsoft::longVar(dir as i32),
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(&delta_color.to_string()),
+            Dmsoft::bstrVal(&pic_name.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(pic::parse_list(&result)
+            .map(|(name, pos)| (name.to_owned(), pos))
+            .collect())
+    }
+
+    /// 查找指定区域内的图片,位图必须是24位色格式,支持透明色,当图像上下左右4个顶点的颜色一样时,则这个颜色将作为透明色处理. 这个函数可以查找多个图片,只返回第一个找到的X Y坐标. 此函数同FindPic,只是相似度的算法不同,sim为0-100的整数.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::FindPicSim(long x1,long y1,long x2,long y2,const TCHAR * pic_name,const TCHAR * delta_color,long sim,long dir,long * x,long * y)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `pic_name:&PicSet`: 图片名,可以是多个图片,比如"test.bmp|test2.bmp|test3.bmp"
+    /// * `delta_color:DeltaColor`: 颜色色偏,比如"203040" 表示RGB的色偏分别是20 30 40 (这里是16进制表示)
+    /// * `sim:i32`: 相似度,和算法相关,取值范围0-100,越大越相似
+    /// * `dir:Dir`: 查找方向
+    ///     * 0: 从左到右,从上到下
+    ///     * 1: 从左到右,从下到上
+    ///     * 2: 从右到左,从上到下
+    ///     * 3: 从右到左,从下到上
+    /// # Return
+    /// `Option<(String, Point)>`: 找到的图片名和图片左上角的坐标, 没找到返回 `None`
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let pics = PicSet::new().with("test.bmp").with("test2.bmp");
+    /// if let Some((name, pos)) = dm.FindPicSim(0,0,2000,2000,&pics,DeltaColor::splat(0x20),90,Dir::LeftTop).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    /// ```
+    pub unsafe fn FindPicSim(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        pic_name: &PicSet,
+        delta_color: DeltaColor,
+        sim: i32,
+        dir: Dir,
+    ) -> Result<Option<(String, Point)>> {
+        static NAME: &str = "FindPicSim";
+        let mut px = VARIANT::default();
+        let mut py = VARIANT::default();
+        let mut args = [
+            Dmsoft::pvarVal(&mut py),
+            Dmsoft::pvarVal(&mut px),
+            Dmsoft::longVar(dir as i32),
+            Dmsoft::longVar(sim),
+            Dmsoft::bstrVal(&delta_color.to_string()),
+            Dmsoft::bstrVal(&pic_name.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let pos = Point::new(
+            px.Anonymous.Anonymous.Anonymous.lVal,
+            py.Anonymous.Anonymous.Anonymous.lVal,
+        );
+
+        Ok(pic_name.matched(result.Anonymous.lVal, pos))
+    }
+
+    /// 查找指定区域内的图片,位图必须是24位色格式,支持透明色,当图像上下左右4个顶点的颜色一样时,则这个颜色将作为透明色处理. 此函数同FindPicE,只是相似度的算法不同,sim为0-100的整数.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::FindPicSimE(long x1,long y1,long x2,long y2,const TCHAR * pic_name,const TCHAR * delta_color,long sim,long dir)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `pic_name:&PicSet`: 图片名,可以是多个图片,比如"test.bmp|test2.bmp|test3.bmp"
+    /// * `delta_color:DeltaColor`: 颜色色偏,比如"203040" 表示RGB的色偏分别是20 30 40 (这里是16进制表示)
+    /// * `sim:i32`: 相似度,和算法相关,取值范围0-100,越大越相似
+    /// * `dir:Dir`: 查找方向
+    ///     * 0: 从左到右,从上到下
+    ///     * 1: 从左到右,从下到上
+    ///     * 2: 从右到左,从上到下
+    ///     * 3: 从右到左,从下到上
+    /// # Return
+    /// `Option<(String, Point)>`: 找到的图片名和图片左上角的坐标, 没找到返回 `None` (插件返回"index|x|y"格式, 在本地转换)
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let pics = PicSet::new().with("test.bmp").with("test2.bmp");
+    /// if let Some((name, pos)) = dm.FindPicSimE(0,0,2000,2000,&pics,DeltaColor::splat(0x20),90,Dir::LeftTop).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    /// ```
+    pub unsafe fn FindPicSimE(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        pic_name: &PicSet,
+        delta_color: DeltaColor,
+        sim: i32,
+        dir: Dir,
+    ) -> Result<Option<(String, Point)>> {
+        static NAME: &str = "FindPicSimE";
+        let mut args = [
+            Dmsoft::longVar(dir as i32),
+            Dmsoft::longVar(sim),
+            Dmsoft::bstrVal(&delta_color.to_string()),
+            Dmsoft::bstrVal(&pic_name.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(pic::parse_index_pos(&result).and_then(|(index, pos)| pic_name.matched(index, pos)))
+    }
+
+    /// 查找指定区域内的图片,位图必须是24位色格式,支持透明色,当图像上下左右4个顶点的颜色一样时,则这个颜色将作为透明色处理. 此函数同FindPicEx,只是相似度的算法不同,sim为0-100的整数.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::FindPicSimEx(long x1,long y1,long x2,long y2,const TCHAR * pic_name,const TCHAR * delta_color,long sim,long dir)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `pic_name:&PicSet`: 图片名,可以是多个图片,比如"test.bmp|test2.bmp|test3.bmp"
+    /// * `delta_color:DeltaColor`: 颜色色偏,比如"203040" 表示RGB的色偏分别是20 30 40 (这里是16进制表示)
+    /// * `sim:i32`: 相似度,和算法相关,取值范围0-100,越大越相似
+    /// * `dir:Dir`: 查找方向
+    ///     * 0: 从左到右,从上到下
+    ///     * 1: 从左到右,从下到上
+    ///     * 2: 从右到左,从上到下
+    ///     * 3: 从右到左,从下到上
+    /// # Return
+    /// `Vec<(String, Point)>`: 所有找到的图片名和图片左上角的坐标 (插件返回"id,x,y|id,x,y"格式, 在本地转换)
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let pics = PicSet::new().with("test.bmp").with("test2.bmp");
+    /// for (name, pos) in dm.FindPicSimEx(0,0,2000,2000,&pics,DeltaColor::splat(0x20),90,Dir::LeftTop).unwrap() {
+    ///     println!("{}: {},{}", name, pos.x, pos.y);
+    /// }
+    /// ```
+    pub unsafe fn FindPicSimEx(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        pic_name: &PicSet,
+        delta_color: DeltaColor,
+        sim: i32,
+        dir: Dir,
+    ) -> Result<Vec<(String, Point)>> {
+        static NAME: &str = "FindPicSimEx";
+        let mut args = [
+            Dmsoft::longVar(dir as i32),
+            Dmsoft::longVar(sim),
+            Dmsoft::bstrVal(&delta_color.to_string()),
+            Dmsoft::bstrVal(&pic_name.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(pic_name.matched_list(&result))
+    }
+
+    /// 查找指定区域内的图片,位图必须是24位色格式,支持透明色,当图像上下左右4个顶点的颜色一样时,则这个颜色将作为透明色处理. 这个函数要求图片是数据地址. 这个函数可以查找多个图片,只返回第一个找到的X Y坐标.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::FindPicMem(long x1,long y1,long x2,long y2,const TCHAR * pic_info,const TCHAR * delta_color,double sim,long dir,long * x,long * y)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `pic_info:&PicMemSet`: 图片数据地址集合. 插件接收的格式为"地址1,长度1|地址2,长度2.....|地址n,长度n", 由 `PicMemSet` 生成
+    /// * `delta_color:DeltaColor`: 颜色色偏,比如"203040" 表示RGB的色偏分别是20 30 40 (这里是16进制表示)
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// * `dir:Dir`: 查找方向
+    ///     * 0: 从左到右,从上到下
+    ///     * 1: 从左到右,从下到上
+    ///     * 2: 从右到左,从上到下
+    ///     * 3: 从右到左,从下到上
+    /// # Return
+    /// `Option<(String, Point)>`: 找到的图片名和图片左上角的坐标, 没找到返回 `None`
+    /// # Examples
+    /// ```
+    /// static TEST: &[u8] = include_bytes!("test.bmp");
+    /// let dm = Dmsoft::new();
+    /// let pics = PicMemSet::new().with("test", TEST);
+    /// if let Some((name, pos)) = dm.FindPicMem(0,0,2000,2000,&pics,DeltaColor::splat(0x20),0.9,Dir::LeftTop).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    /// ```
+    pub unsafe fn FindPicMem(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        pic_info: &PicMemSet<'_>,
+        delta_color: DeltaColor,
+        sim: f64,
+        dir: Dir,
+    ) -> Result<Option<(String, Point)>> {
+        static NAME: &str = "FindPicMem";
+        let mut px = VARIANT::default();
+        let mut py = VARIANT::default();
+        let mut args = [
+            Dmsoft::pvarVal(&mut py),
+            Dmsoft::pvarVal(&mut px),
+            Dmsoft::longVar(dir as i32),
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(&delta_color.to_string()),
+            Dmsoft::bstrVal(&pic_info.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let pos = Point::new(
+            px.Anonymous.Anonymous.Anonymous.lVal,
+            py.Anonymous.Anonymous.Anonymous.lVal,
+        );
+
+        Ok(pic_info.matched(result.Anonymous.lVal, pos))
+    }
+
+    /// 查找指定区域内的图片,位图必须是24位色格式,支持透明色,当图像上下左右4个顶点的颜色一样时,则这个颜色将作为透明色处理. 这个函数要求图片是数据地址. 这个函数可以查找多个图片,只返回第一个找到的X Y坐标. 易语言用不了直接取变量的方式,所以有了这个函数.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::FindPicMemE(long x1,long y1,long x2,long y2,const TCHAR * pic_info,const TCHAR * delta_color,double sim,long dir)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `pic_info:&PicMemSet`: 图片数据地址集合. 插件接收的格式为"地址1,长度1|地址2,长度2.....|地址n,长度n", 由 `PicMemSet` 生成
+    /// * `delta_color:DeltaColor`: 颜色色偏,比如"203040" 表示RGB的色偏分别是20 30 40 (这里是16进制表示)
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// * `dir:Dir`: 查找方向
+    ///     * 0: 从左到右,从上到下
+    ///     * 1: 从左到右,从下到上
+    ///     * 2: 从右到左,从上到下
+    ///     * 3: 从右到左,从下到上
+    /// # Return
+    /// `Option<(String, Point)>`: 找到的图片名和图片左上角的坐标, 没找到返回 `None` (插件返回"index|x|y"格式, 在本地转换)
+    /// # Examples
+    /// ```
+    /// static TEST: &[u8] = include_bytes!("test.bmp");
+    /// let dm = Dmsoft::new();
+    /// let pics = PicMemSet::new().with("test", TEST);
+    /// if let Some((name, pos)) = dm.FindPicMemE(0,0,2000,2000,&pics,DeltaColor::splat(0x20),0.9,Dir::LeftTop).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    /// ```
+    pub unsafe fn FindPicMemE(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        pic_info: &PicMemSet<'_>,
+        delta_color: DeltaColor,
+        sim: f64,
+        dir: Dir,
+    ) -> Result<Option<(String, Point)>> {
+        static NAME: &str = "FindPicMemE";
+        let mut args = [
+            Dmsoft::longVar(dir as i32),
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(&delta_color.to_string()),
+            Dmsoft::bstrVal(&pic_info.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(pic::parse_index_pos(&result).and_then(|(index, pos)| pic_info.matched(index, pos)))
+    }
+
+    /// 查找指定区域内的图片,位图必须是24位色格式,支持透明色,当图像上下左右4个顶点的颜色一样时,则这个颜色将作为透明色处理. 这个函数要求图片是数据地址. 这个函数可以查找多个图片,并且返回所有找到的图像的坐标.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::FindPicMemEx(long x1,long y1,long x2,long y2,const TCHAR * pic_info,const TCHAR * delta_color,double sim,long dir)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `pic_info:&PicMemSet`: 图片数据地址集合. 插件接收的格式为"地址1,长度1|地址2,长度2.....|地址n,长度n", 由 `PicMemSet` 生成
+    /// * `delta_color:DeltaColor`: 颜色色偏,比如"203040" 表示RGB的色偏分别是20 30 40 (这里是16进制表示)
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// * `dir:Dir`: 查找方向
+    ///     * 0: 从左到右,从上到下
+    ///     * 1: 从左到右,从下到上
+    ///     * 2: 从右到左,从上到下
+    ///     * 3: 从右到左,从下到上
+    /// # Return
+    /// `Vec<(String, Point)>`: 所有找到的图片名和图片左上角的坐标 (插件返回"id,x,y|id,x,y"格式, 在本地转换)
+    /// # Examples
+    /// ```
+    /// static TEST: &[u8] = include_bytes!("test.bmp");
+    /// let dm = Dmsoft::new();
+    /// let pics = PicMemSet::new().with("test", TEST);
+    /// for (name, pos) in dm.FindPicMemEx(0,0,2000,2000,&pics,DeltaColor::splat(0x20),0.9,Dir::LeftTop).unwrap() {
+    ///     println!("{}: {},{}", name, pos.x, pos.y);
+    /// }
+    /// ```
+    pub unsafe fn FindPicMemEx(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        pic_info: &PicMemSet<'_>,
+        delta_color: DeltaColor,
+        sim: f64,
+        dir: Dir,
+    ) -> Result<Vec<(String, Point)>> {
+        static NAME: &str = "FindPicMemEx";
+        let mut args = [
+            Dmsoft::longVar(dir as i32),
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(&delta_color.to_string()),
+            Dmsoft::bstrVal(&pic_info.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(pic_info.matched_list(&result))
+    }
+
+    /// 查找指定区域内的图片,位图必须是24位色格式,支持透明色,当图像上下左右4个顶点的颜色一样时,则这个颜色将作为透明色处理. 这个函数要求图片是数据地址. 此函数同FindPicMem,只是相似度的算法不同,sim为0-100的整数.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::FindPicSimMem(long x1,long y1,long x2,long y2,const TCHAR * pic_info,const TCHAR * delta_color,long sim,long dir,long * x,long * y)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `pic_info:&PicMemSet`: 图片数据地址集合. 插件接收的格式为"地址1,长度1|地址2,长度2.....|地址n,长度n", 由 `PicMemSet` 生成
+    /// * `delta_color:DeltaColor`: 颜色色偏,比如"203040" 表示RGB的色偏分别是20 30 40 (这里是16进制表示)
+    /// * `sim:i32`: 相似度,和算法相关,取值范围0-100,越大越相似
+    /// * `dir:Dir`: 查找方向
+    ///     * 0: 从左到右,从上到下
+    ///     * 1: 从左到右,从下到上
+    ///     * 2: 从右到左,从上到下
+    ///     * 3: 从右到左,从下到上
+    /// # Return
+    /// `Option<(String, Point)>`: 找到的图片名和图片左上角的坐标, 没找到返回 `None`
+    /// # Examples
+    /// ```
+    /// static TEST: &[u8] = include_bytes!("test.bmp");
+    /// let dm = Dmsoft::new();
+    /// let pics = PicMemSet::new().with("test", TEST);
+    /// if let Some((name, pos)) = dm.FindPicSimMem(0,0,2000,2000,&pics,DeltaColor::splat(0x20),90,Dir::LeftTop).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    /// ```
+    pub unsafe fn FindPicSimMem(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        pic_info: &PicMemSet<'_>,
+        delta_color: DeltaColor,
+        sim: i32,
+        dir: Dir,
+    ) -> Result<Option<(String, Point)>> {
+        static NAME: &str = "FindPicSimMem";
+        let mut px = VARIANT::default();
+        let mut py = VARIANT::default();
+        let mut args = [
+            Dmsoft::pvarVal(&mut py),
+            Dmsoft::pvarVal(&mut px),
+            Dmsoft::longVar(dir as i32),
+            Dmsoft::longVar(sim),
+            Dmsoft::bstrVal(&delta_color.to_string()),
+            Dmsoft::bstrVal(&pic_info.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let pos = Point::new(
+            px.Anonymous.Anonymous.Anonymous.lVal,
+            py.Anonymous.Anonymous.Anonymous.lVal,
+        );
+
+        Ok(pic_info.matched(result.Anonymous.lVal, pos))
+    }
+
+    /// 查找指定区域内的图片,位图必须是24位色格式,支持透明色,当图像上下左右4个顶点的颜色一样时,则这个颜色将作为透明色处理. 这个函数要求图片是数据地址. 此函数同FindPicMemE,只是相似度的算法不同,sim为0-100的整数.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::FindPicSimMemE(long x1,long y1,long x2,long y2,const TCHAR * pic_info,const TCHAR * delta_color,long sim,long dir)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `pic_info:&PicMemSet`: 图片数据地址集合. 插件接收的格式为"地址1,长度1|地址2,长度2.....|地址n,长度n", 由 `PicMemSet` 生成
+    /// * `delta_color:DeltaColor`: 颜色色偏,比如"203040" 表示RGB的色偏分别是20 30 40 (这里是16进制表示)
+    /// * `sim:i32`: 相似度,和算法相关,取值范围0-100,越大越相似
+    /// * `dir:Dir`: 查找方向
+    ///     * 0: 从左到右,从上到下
+    ///     * 1: 从左到右,从下到上
+    ///     * 2: 从右到左,从上到下
+    ///     * 3: 从右到左,从下到上
+    /// # Return
+    /// `Option<(String, Point)>`: 找到的图片名和图片左上角的坐标, 没找到返回 `None` (插件返回"index|x|y"格式, 在本地转换)
+    /// # Examples
+    /// ```
+    /// static TEST: &[u8] = include_bytes!("test.bmp");
+    /// let dm = Dmsoft::new();
+    /// let pics = PicMemSet::new().with("test", TEST);
+    /// if let Some((name, pos)) = dm.FindPicSimMemE(0,0,2000,2000,&pics,DeltaColor::splat(0x20),90,Dir::LeftTop).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    /// ```
+    pub unsafe fn FindPicSimMemE(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        pic_info: &PicMemSet<'_>,
+        delta_color: DeltaColor,
+        sim: i32,
+        dir: Dir,
+    ) -> Result<Option<(String, Point)>> {
+        static NAME: &str = "FindPicSimMemE";
+        let mut args = [
+            Dmsoft::longVar(dir as i32),
+            Dmsoft::longVar(sim),
+            Dmsoft::bstrVal(&delta_color.to_string()),
+            Dmsoft::bstrVal(&pic_info.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(pic::parse_index_pos(&result).and_then(|(index, pos)| pic_info.matched(index, pos)))
+    }
+
+    /// 查找指定区域内的图片,位图必须是24位色格式,支持透明色,当图像上下左右4个顶点的颜色一样时,则这个颜色将作为透明色处理. 这个函数要求图片是数据地址. 此函数同FindPicMemEx,只是相似度的算法不同,sim为0-100的整数.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::FindPicSimMemEx(long x1,long y1,long x2,long y2,const TCHAR * pic_info,const TCHAR * delta_color,long sim,long dir)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `pic_info:&PicMemSet`: 图片数据地址集合. 插件接收的格式为"地址1,长度1|地址2,长度2.....|地址n,长度n", 由 `PicMemSet` 生成
+    /// * `delta_color:DeltaColor`: 颜色色偏,比如"203040" 表示RGB的色偏分别是20 30 40 (这里是16进制表示)
+    /// * `sim:i32`: 相似度,和算法相关,取值范围0-100,越大越相似
+    /// * `dir:Dir`: 查找方向
+    ///     * 0: 从左到右,从上到下
+    ///     * 1: 从左到右,从下到上
+    ///     * 2: 从右到左,从上到下
+    ///     * 3: 从右到左,从下到上
+    /// # Return
+    /// `Vec<(String, Point)>`: 所有找到的图片名和图片左上角的坐标 (插件返回"id,x,y|id,x,y"格式, 在本地转换)
+    /// # Examples
+    /// ```
+    /// static TEST: &[u8] = include_bytes!("test.bmp");
+    /// let dm = Dmsoft::new();
+    /// let pics = PicMemSet::new().with("test", TEST);
+    /// for (name, pos) in dm.FindPicSimMemEx(0,0,2000,2000,&pics,DeltaColor::splat(0x20),90,Dir::LeftTop).unwrap() {
+    ///     println!("{}: {},{}", name, pos.x, pos.y);
+    /// }
+    /// ```
+    pub unsafe fn FindPicSimMemEx(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        pic_info: &PicMemSet<'_>,
+        delta_color: DeltaColor,
+        sim: i32,
+        dir: Dir,
+    ) -> Result<Vec<(String, Point)>> {
+        static NAME: &str = "FindPicSimMemEx";
+        let mut args = [
+            Dmsoft::longVar(dir as i32),
+            Dmsoft::longVar(sim),
+            Dmsoft::bstrVal(&delta_color.to_string()),
+            Dmsoft::bstrVal(&pic_info.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(pic_info.matched_list(&result))
+    }
+
+    /// 对指定的数据地址和长度,组合成新的参数. FindPicMem FindPicMemE 以及FindPicMemEx专用
+    ///
+    /// 返回的字符串中保存的是图片数据的地址, 不再受借用检查保护, 推荐直接使用 `PicMemSet`.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::AppendPicAddr(const TCHAR * pic_info,long addr,long size)
+    /// ```
+    /// # Args
+    /// * `pic_info:&str`: 老的地址描述串
+    /// * `pic:&[u8]`: 完整的24位 BMP 文件数据, 地址和长度由此计算
+    /// # Return
+    /// `String`: 新的地址描述串
+    /// # Examples
+    /// ```
+    /// static TEST: &[u8] = include_bytes!("test.bmp");
+    /// let dm = Dmsoft::new();
+    /// let pic_info = dm.AppendPicAddr("", TEST).unwrap();
+    /// ```
+    pub unsafe fn AppendPicAddr(&self, pic_info: &str, pic: &[u8]) -> Result<String> {
+        static NAME: &str = "AppendPicAddr";
+        let mut args = [
+            Dmsoft::longVar(pic.len() as i32),
+            Dmsoft::longVar(pic.as_ptr() as i32),
+            Dmsoft::bstrVal(pic_info),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = ManuallyDrop::into_inner(result.Anonymous.bstrVal);
+        Ok(result.try_into().unwrap())
+    }
+
+    /// 预先加载指定的图片,这样在操作任何和图片相关的函数时,将省去了加载图片的时间。调用此函数后,没必要一直保持图片数据.
+    ///
+    /// 推荐使用 `Dmsoft::load_pics`, 返回的 `LoadedPics` 离开作用域时会自动调用 `FreePic`
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::LoadPic(const TCHAR * pic_name)
+    /// ```
+    /// # Args
+    /// * `pic_name:&PicSet`: 文件名 比如"1.bmp|2.bmp|3.bmp" 等,可以使用通配符,比如"*.bmp" 这个对应了所有的bmp文件
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.LoadPic(&PicSet::new().with("*.bmp")).unwrap();
+    /// ```
+    pub unsafe fn LoadPic(&self, pic_name: &PicSet) -> Result<i32> {
+        static NAME: &str = "LoadPic";
+        let mut args = [Dmsoft::bstrVal(&pic_name.to_string())];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 预先加载指定的图片,这样在操作任何和图片相关的函数时,将省去了加载图片的时间。图片数据来自内存.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::LoadPicByte(long addr,long size,const TCHAR * name)
+    /// ```
+    /// # Args
+    /// * `pic:&[u8]`: 完整的24位 BMP 文件数据, 地址和长度由此计算
+    /// * `name:&str`: 文件名,指定这个地址对应的图片名. 用于找图时使用.
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// static TEST: &[u8] = include_bytes!("test.bmp");
+    /// let dm = Dmsoft::new();
+    /// let status = dm.LoadPicByte(TEST, "test.bmp").unwrap();
+    /// // 内存中的图片没有对应的文件, 找图时不检查
+    /// let pics = PicSet::new().with("test.bmp").in_memory();
+    /// let found = dm.FindPic(0,0,2000,2000,&pics,DeltaColor::ZERO,0.9,Dir::LeftTop).unwrap();
+    /// ```
+    pub unsafe fn LoadPicByte(&self, pic: &[u8], name: &str) -> Result<i32> {
+        static NAME: &str = "LoadPicByte";
+        let mut args = [
+            Dmsoft::bstrVal(name),
+            Dmsoft::longVar(pic.len() as i32),
+            Dmsoft::longVar(pic.as_ptr() as i32),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 释放指定的图片,此函数不必要调用,除非你想节省内存.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::FreePic(const TCHAR * pic_name)
+    /// ```
+    /// # Args
+    /// * `pic_name:&PicSet`: 文件名比如"1.bmp|2.bmp|3.bmp" 等,可以使用通配符,比如"*.bmp" 这个对应了所有的bmp文件
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.FreePic(&PicSet::new().with("*.bmp")).unwrap();
+    /// ```
+    pub unsafe fn FreePic(&self, pic_name: &PicSet) -> Result<i32> {
+        static NAME: &str = "FreePic";
+        let mut args = [Dmsoft::bstrVal(&pic_name.to_string())];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 设置是否开启或者关闭插件内部的图片缓存机制. (默认是打开).
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::EnablePicCache(long en)
+    /// ```
+    /// # Args
+    /// * `enable:bool`: `false`: 关闭 `true`: 打开
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.EnablePicCache(false).unwrap();
+    /// ```
+    /// # Note
+    /// * 有些时候,系统内存比较吃紧,这时候再打开内部缓存,可能会导致缓存分配在虚拟内存,这样频繁换页,反而导致图色效率下降.这时候就建议关闭图色缓存.
+    /// * 所有图色缓存机制都是对本对象的,也就是说,调用图色缓存机制的函数仅仅对本对象生效. 每个对象都有一个图色缓存队列.
+    pub unsafe fn EnablePicCache(&self, enable: bool) -> Result<i32> {
+        static NAME: &str = "EnablePicCache";
+        let mut args = [Dmsoft::longVar(enable as i32)];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 获取指定图片的尺寸，如果指定的图片已经被加入缓存，则从缓存中获取信息. 此接口也会把此图片加入缓存. (当图色缓存机制打开时,具体参考EnablePicCache)
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::GetPicSize(const TCHAR * pic_name)
+    /// ```
+    /// # Args
+    /// * `pic_name:&str`: 文件名 比如"1.bmp"
+    /// # Return
+    /// `Option<(i32, i32)>`: 图片的宽和高, 失败返回 `None` (插件返回"w,h"格式, 在本地转换)
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// if let Some((w, h)) = dm.GetPicSize("1.bmp").unwrap() {
+    ///     println!("{}x{}", w, h);
+    /// }
+    /// ```
+    pub unsafe fn GetPicSize(&self, pic_name: &str) -> Result<Option<(i32, i32)>> {
+        static NAME: &str = "GetPicSize";
+        let mut args = [Dmsoft::bstrVal(pic_name)];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        let size = result
+            .split_once(',')
+            .and_then(|(w, h)| Some((w.trim().parse().ok()?, h.trim().parse().ok()?)));
+        Ok(size)
+    }
 }
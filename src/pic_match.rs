@@ -0,0 +1,390 @@
+//! 找图算法的纯 Rust 实现, 在内存中的位图上模拟 `FindPic` / `FindPicEx` 的查找规则
+//!
+//! * 模板四个角的颜色相同时, 此颜色的像素为透明色, 不参与比较
+//! * 每个像素每个通道的差值都不超过 `delta_color` 时算作匹配
+//! * 匹配的像素数量占非透明像素总数的比例不小于 `sim` 时算作找到
+//! * 查找顺序和 [`Dir`] 一致, 多张图片时按图片顺序依次查找
+//!
+//! 区域(x1, y1, x2, y2)包含左上角, 不包含右下角, 和 `Capture` 截取的范围一致, 超出位图的部分会被忽略.
+//! 返回的坐标为图片左上角在位图中的坐标.
+
+use crate::{
+    bmp::{fits, Bmp},
+    color::{DeltaColor, Rgb},
+    pic::Dir,
+    Point,
+};
+
+/// 找图模板, 保存了图片中所有非透明的像素
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    width: u32,
+    height: u32,
+    points: Vec<(u32, u32, Rgb)>,
+}
+
+impl Template {
+    /// 从位图构建模板, 按大漠的规则去除透明色
+    pub fn new(bmp: &Bmp) -> Self {
+        let transparent = bmp.transparent_color();
+        let points = (0..bmp.height())
+            .flat_map(|y| (0..bmp.width()).map(move |x| (x, y)))
+            .map(|(x, y)| (x, y, bmp.pixel(x, y)))
+            .filter(|&(_, _, color)| Some(color) != transparent)
+            .collect();
+        Self {
+            width: bmp.width(),
+            height: bmp.height(),
+            points,
+        }
+    }
+
+    /// 模板宽度
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// 模板高度
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// 参与比较的(非透明)像素数量
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// 模板中所有像素都是透明色时返回 `true`, 这样的模板在任何位置都能匹配
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// 判断模板左上角放在`frame`的(x,y)时是否匹配, 模板超出位图范围时返回 `false`
+    pub fn matches_at(
+        &self,
+        frame: &Bmp,
+        x: u32,
+        y: u32,
+        delta_color: DeltaColor,
+        sim: f64,
+    ) -> bool {
+        if !fits(x, self.width, frame.width()) || !fits(y, self.height, frame.height()) {
+            return false;
+        }
+        let max_miss = max_miss(self.points.len(), sim);
+        let mut miss = 0;
+        for &(dx, dy, color) in &self.points {
+            if !delta_color.matches(frame.pixel(x + dx, y + dy), color) {
+                miss += 1;
+                if miss > max_miss {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+impl From<&Bmp> for Template {
+    fn from(bmp: &Bmp) -> Self {
+        Self::new(bmp)
+    }
+}
+
+/// 允许不匹配的像素数量
+pub(crate) fn max_miss(total: usize, sim: f64) -> usize {
+    // 加上一个很小的值, 避免 (1.0 - 0.9) * 10 这样的计算因为浮点误差少算一个像素
+    ((1.0 - sim.clamp(0.0, 1.0)) * total as f64 + 1e-9).floor() as usize
+}
+
+/// 按查找方向生成模板左上角可以放置的所有位置
+fn positions(
+    frame: &Bmp,
+    (x1, y1, x2, y2): (i32, i32, i32, i32),
+    template: &Template,
+    dir: Dir,
+) -> impl Iterator<Item = (u32, u32)> {
+    let clamp = |v: i32, max: u32| v.clamp(0, max as i32) as u32;
+    let (x1, x2) = (clamp(x1, frame.width()), clamp(x2, frame.width()));
+    let (y1, y2) = (clamp(y1, frame.height()), clamp(y2, frame.height()));
+    // 模板左上角的取值范围 [x1, x_end) [y1, y_end)
+    let x_end = (x2 + 1).saturating_sub(template.width).max(x1);
+    let y_end = (y2 + 1).saturating_sub(template.height).max(y1);
+
+    let (rev_x, rev_y) = match dir {
+        Dir::LeftTop => (false, false),
+        Dir::LeftBottom => (false, true),
+        Dir::RightTop => (true, false),
+        Dir::RightBottom => (true, true),
+    };
+    ordered(y1, y_end, rev_y).flat_map(move |y| ordered(x1, x_end, rev_x).map(move |x| (x, y)))
+}
+
+/// [start, end) 的正序或者倒序迭代器
+fn ordered(start: u32, end: u32, rev: bool) -> impl Iterator<Item = u32> {
+    (start..end).map(move |i| if rev { end - 1 - (i - start) } else { i })
+}
+
+/// 在`frame`的区域(x1, y1, x2, y2)内查找图片, 对应 `FindPic`
+///
+/// # Args
+/// * `templates:&[Template]`: 图片列表, 按顺序依次查找
+/// * `delta_color:DeltaColor`: 颜色色偏
+/// * `sim:f64`: 相似度,取值范围0.1-1.0
+/// * `dir:Dir`: 查找方向
+/// # Return
+/// `Option<(usize, Point)>`: 找到的图片序号和图片左上角的坐标, 没找到返回 `None`
+/// # Examples
+/// ```
+/// let frame = Bmp::open("screen.bmp").unwrap();
+/// let templates = [Template::new(&Bmp::open("test.bmp").unwrap())];
+/// let found = pic_match::find_pic(&frame, 0, 0, 2000, 2000, &templates, DeltaColor::ZERO, 0.9, Dir::LeftTop);
+/// ```
+pub fn find_pic(
+    frame: &Bmp,
+    x1: i32,
+    y1: i32,
+    x2: i32,
+    y2: i32,
+    templates: &[Template],
+    delta_color: DeltaColor,
+    sim: f64,
+    dir: Dir,
+) -> Option<(usize, Point)> {
+    templates.iter().enumerate().find_map(|(index, template)| {
+        positions(frame, (x1, y1, x2, y2), template, dir)
+            .find(|&(x, y)| template.matches_at(frame, x, y, delta_color, sim))
+            .map(|(x, y)| (index, Point::new(x as i32, y as i32)))
+    })
+}
+
+/// 在`frame`的区域(x1, y1, x2, y2)内查找图片, 返回所有找到的坐标, 对应 `FindPicEx`
+///
+/// 参数同 [`find_pic`]
+/// # Return
+/// `Vec<(usize, Point)>`: 所有找到的图片序号和图片左上角的坐标, 按图片顺序, 同一张图片按查找方向排列
+pub fn find_pic_ex(
+    frame: &Bmp,
+    x1: i32,
+    y1: i32,
+    x2: i32,
+    y2: i32,
+    templates: &[Template],
+    delta_color: DeltaColor,
+    sim: f64,
+    dir: Dir,
+) -> Vec<(usize, Point)> {
+    templates
+        .iter()
+        .enumerate()
+        .flat_map(|(index, template)| {
+            positions(frame, (x1, y1, x2, y2), template, dir)
+                .filter(|&(x, y)| template.matches_at(frame, x, y, delta_color, sim))
+                .map(move |(x, y)| (index, Point::new(x as i32, y as i32)))
+        })
+        .collect()
+}
+
+/// 测试图片在 `tests/fixtures/pic` 下:
+/// * `frame.bmp`: 64x48 的随机噪声背景
+/// * `a.bmp`: 8x6, 在 `frame.bmp` 中出现4次, 左上角为 (4,4) (30,4) (10,36) (50,36)
+/// * `b.bmp`: 9x7, 四周一圈为透明色 ff00ff, 中间部分在 `frame.bmp` 的 (21,21), 即模板左上角为 (20,20)
+/// * `c.bmp`: 8x6, `frame.bmp` 中 (40,20) 处每个像素的偏色为 R+10 G-08 B+04
+/// * `d.bmp`: 8x5 共40个像素, `frame.bmp` 中 (4,22) 处有3个像素不同
+///
+/// 期望的坐标是生成图片时按插件文档的规则确定的. 在注册了插件的 Windows 上运行
+/// `cargo test -- --ignored plugin_matches_fixtures`, 通过 `SetDisplayInput("pic:frame.bmp")`
+/// 让插件对同一张图找图, 复核这些坐标
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    /// 一次找图: 图片, 偏色, 相似度, 查找方向, 区域和期望的图片序号和坐标
+    #[derive(Debug, Clone, Copy)]
+    struct Case {
+        pics: &'static [&'static str],
+        delta: &'static str,
+        sim: f64,
+        dir: Dir,
+        region: (i32, i32, i32, i32),
+        expected: Option<(usize, (i32, i32))>,
+    }
+
+    /// 在整张图中查找
+    const fn case(
+        pics: &'static [&'static str],
+        delta: &'static str,
+        sim: f64,
+        dir: Dir,
+        expected: Option<(usize, (i32, i32))>,
+    ) -> Case {
+        Case {
+            pics,
+            delta,
+            sim,
+            dir,
+            region: (0, 0, 2000, 2000),
+            expected,
+        }
+    }
+
+    impl Case {
+        /// 区域的左上角改为(x1, y1)
+        const fn from(self, x1: i32, y1: i32) -> Self {
+            Case {
+                region: (x1, y1, 2000, 2000),
+                ..self
+            }
+        }
+    }
+
+    const CASES: &[Case] = &[
+        case(&["a.bmp"], "000000", 1.0, Dir::LeftTop, Some((0, (4, 4)))),
+        case(
+            &["a.bmp"],
+            "000000",
+            1.0,
+            Dir::LeftBottom,
+            Some((0, (10, 36))),
+        ),
+        case(&["a.bmp"], "000000", 1.0, Dir::RightTop, Some((0, (30, 4)))),
+        case(
+            &["a.bmp"],
+            "000000",
+            1.0,
+            Dir::RightBottom,
+            Some((0, (50, 36))),
+        ),
+        case(&["a.bmp"], "000000", 1.0, Dir::LeftTop, Some((0, (30, 4)))).from(5, 0),
+        case(&["a.bmp"], "000000", 1.0, Dir::LeftTop, Some((0, (10, 36)))).from(0, 5),
+        case(&["b.bmp"], "000000", 1.0, Dir::LeftTop, Some((0, (20, 20)))),
+        case(&["c.bmp"], "000000", 1.0, Dir::LeftTop, None),
+        case(&["c.bmp"], "080808", 1.0, Dir::LeftTop, None),
+        case(&["c.bmp"], "100804", 1.0, Dir::LeftTop, Some((0, (40, 20)))),
+        case(&["c.bmp"], "101010", 1.0, Dir::LeftTop, Some((0, (40, 20)))),
+        case(&["d.bmp"], "000000", 1.0, Dir::LeftTop, None),
+        case(&["d.bmp"], "000000", 0.95, Dir::LeftTop, None),
+        case(&["d.bmp"], "000000", 0.9, Dir::LeftTop, Some((0, (4, 22)))),
+        case(
+            &["d.bmp", "a.bmp"],
+            "000000",
+            1.0,
+            Dir::LeftTop,
+            Some((1, (4, 4))),
+        ),
+        case(
+            &["c.bmp", "d.bmp"],
+            "101010",
+            0.9,
+            Dir::LeftTop,
+            Some((0, (40, 20))),
+        ),
+    ];
+
+    fn open(name: &str) -> Bmp {
+        fixtures::bmp("pic", name)
+    }
+
+    fn templates(names: &[&str]) -> Vec<Template> {
+        names
+            .iter()
+            .map(|name| Template::new(&open(name)))
+            .collect()
+    }
+
+    #[test]
+    fn find_pic_fixtures() {
+        let frame = open("frame.bmp");
+        for case in CASES {
+            let (x1, y1, x2, y2) = case.region;
+            let found = find_pic(
+                &frame,
+                x1,
+                y1,
+                x2,
+                y2,
+                &templates(case.pics),
+                case.delta.parse().unwrap(),
+                case.sim,
+                case.dir,
+            );
+            let expected = case
+                .expected
+                .map(|(index, (x, y))| (index, Point::new(x, y)));
+            assert_eq!(found, expected, "{case:?}");
+        }
+    }
+
+    #[test]
+    fn find_pic_ex_order() {
+        let frame = open("frame.bmp");
+        let a = templates(&["a.bmp"]);
+        let find = |dir| {
+            find_pic_ex(&frame, 0, 0, 2000, 2000, &a, DeltaColor::ZERO, 1.0, dir)
+                .into_iter()
+                .map(|(_, pos)| (pos.x, pos.y))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(find(Dir::LeftTop), [(4, 4), (30, 4), (10, 36), (50, 36)]);
+        assert_eq!(find(Dir::LeftBottom), [(10, 36), (50, 36), (4, 4), (30, 4)]);
+        assert_eq!(find(Dir::RightTop), [(30, 4), (4, 4), (50, 36), (10, 36)]);
+        assert_eq!(
+            find(Dir::RightBottom),
+            [(50, 36), (10, 36), (30, 4), (4, 4)]
+        );
+    }
+
+    #[test]
+    fn transparent_corners() {
+        let b = open("b.bmp");
+        assert_eq!(b.transparent_color(), Some(Rgb::new(0xff, 0x00, 0xff)));
+        assert_eq!(Template::new(&b).len(), 7 * 5);
+        let a = open("a.bmp");
+        assert_eq!(a.transparent_color(), None);
+        assert_eq!(Template::new(&a).len(), 8 * 6);
+    }
+
+    #[test]
+    fn matches_at_bounds() {
+        let frame = open("frame.bmp");
+        let a = Template::new(&open("a.bmp"));
+        assert!(a.matches_at(&frame, 4, 4, DeltaColor::ZERO, 1.0));
+        assert!(!a.matches_at(&frame, 57, 4, DeltaColor::ZERO, 1.0));
+        assert!(!a.matches_at(&frame, u32::MAX, 4, DeltaColor::ZERO, 1.0));
+        assert!(!a.matches_at(&frame, 4, u32::MAX - 2, DeltaColor::ZERO, 1.0));
+    }
+
+    #[test]
+    fn max_miss_rounding() {
+        assert_eq!(max_miss(40, 1.0), 0);
+        assert_eq!(max_miss(40, 0.95), 2);
+        assert_eq!(max_miss(40, 0.9), 4);
+        assert_eq!(max_miss(10, 0.9), 1);
+        assert_eq!(max_miss(10, 0.0), 10);
+    }
+
+    /// 用插件对同一张图找图, 和 [`CASES`] 中的期望坐标比较
+    #[cfg(windows)]
+    #[test]
+    #[ignore = "需要注册大漠插件"]
+    fn plugin_matches_fixtures() {
+        use crate::pic::PicSet;
+
+        unsafe {
+            let dm = fixtures::plugin("pic", "frame.bmp");
+            for case in CASES {
+                let (x1, y1, x2, y2) = case.region;
+                let pics: PicSet = case.pics.iter().copied().collect();
+                let delta = case.delta.parse().unwrap();
+                let found = dm
+                    .FindPic(x1, y1, x2, y2, &pics, delta, case.sim, case.dir)
+                    .unwrap();
+                let expected = case
+                    .expected
+                    .map(|(index, (x, y))| (case.pics[index].to_owned(), Point::new(x, y)));
+                assert_eq!(found, expected, "{case:?}");
+            }
+        }
+    }
+}
@@ -2,7 +2,13 @@ use std::mem::ManuallyDrop;
 
 use windows::Win32::System::Com::VARIANT;
 
-use crate::{Dmsoft, Result};
+use crate::{
+    dict::{DictSlot, FontFlags},
+    dict_file::DictEntry,
+    find_str::StrSet,
+    ocr::{parse_chars, RecognizedChar},
+    pic, Dmsoft, Error, Point, Result,
+};
 
 #[allow(non_snake_case)]
 impl Dmsoft {
@@ -65,9 +71,119 @@ impl Dmsoft {
         Ok(result.try_into().unwrap())
     }
 
+    /// 识别屏幕范围(x1,y1,x2,y2)内符合color_format的字符串,并且相似度为sim,sim取值范围(0.1-1.0),
+    ///
+    /// 这个值越大越精确,越大速度越快,越小速度越慢,请斟酌使用! 这个函数可以返回识别到的字符串,以及每个字符的坐标.
+    ///
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::OcrEx(long x1,long y1,long x2,long y2,const TCHAR * color,double sim)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `color_format:&str`: 颜色格式串, 同 `Ocr`
+    /// * `sim:f64`:相似度,取值范围0.1-1.0
+    ///
+    /// # Return
+    /// * `Vec<RecognizedChar>`: 识别到的每个字符和坐标, 插件返回的格式为 "char$x$y|char$x$y|..."
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let chars = dm.OcrEx(0,0,2000,2000,"ffffff-000000",1.0).unwrap();
+    /// for line in ocr::group_lines(&chars, 3) {
+    ///     for word in ocr::group_words(&line, 14) {
+    ///         if word.text == "确定" {
+    ///             dm.MoveTo(word.pos.x, word.pos.y).unwrap();
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub unsafe fn OcrEx(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        color: &str,
+        sim: f64,
+    ) -> Result<Vec<RecognizedChar>> {
+        static NAME: &str = "OcrEx";
+
+        let mut args = [
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(color),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+        Ok(parse_chars(&result))
+    }
+
+    /// 识别屏幕范围(x1,y1,x2,y2)内符合color_format的字符串,并且相似度为sim,sim取值范围(0.1-1.0),
+    /// 这个值越大越精确,越大速度越快,越小速度越慢,请斟酌使用! 这个函数可以返回识别到的字符串,以及每个字符的坐标.
+    /// 这个同 `OcrEx`,另一种算法.
+    ///
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::OcrExOne(long x1,long y1,long x2,long y2,const TCHAR * color,double sim)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `color_format:&str`: 颜色格式串, 同 `Ocr`
+    /// * `sim:f64`:相似度,取值范围0.1-1.0
+    ///
+    /// # Return
+    /// * `Vec<RecognizedChar>`: 识别到的每个字符和坐标, 格式同 `OcrEx`
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let chars = dm.OcrExOne(0,0,2000,2000,"ffffff-000000",1.0).unwrap();
+    /// let text: String = chars.iter().map(|c| c.ch.as_str()).collect();
+    /// ```
+    pub unsafe fn OcrExOne(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        color: &str,
+        sim: f64,
+    ) -> Result<Vec<RecognizedChar>> {
+        static NAME: &str = "OcrExOne";
+
+        let mut args = [
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(color),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+        Ok(parse_chars(&result))
+    }
+
     /// 在屏幕范围(x1,y1,x2,y2)内,查找string(可以是任意个字符串的组合),并返回符合color_format的坐标位置,相似度sim同Ocr接口描述.
     ///
     /// (多色,差色查找类似于Ocr接口,不再重述)
+    ///
+    /// 用 [`StrSet`] 传入字符串并直接返回找到的字符串和坐标, 见 [`Dmsoft::find_str`]
     /// # The function prototype
     /// ```C++
     /// long dmsoft::FindStr(long x1,long y1,long x2,long y2,const TCHAR * str,const TCHAR * color,double sim,long * x,long * y)
@@ -142,216 +258,1291 @@ impl Dmsoft {
         Ok(result.Anonymous.lVal)
     }
 
-    /// 对插件部分接口的返回值进行解析,并返回ret中的坐标个数
+    /// 在屏幕范围(x1,y1,x2,y2)内,查找string(可以是任意个字符串的组合),并返回符合color_format的坐标位置,相似度sim同Ocr接口描述. 此函数同FindStr,只是返回值不同,坐标通过返回值返回.
     /// # The function prototype
     /// ```C++
-    /// long dmsoft::GetResultCount(const TCHAR * str)
+    /// CString dmsoft::FindStrE(long x1,long y1,long x2,long y2,const TCHAR * str,const TCHAR * color,double sim)
     /// ```
-    ///
     /// # Args
-    /// * `str:&str` 部分接口的返回串
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `string:&StrSet`: 待查找的字符串,可以是字符串组合，比如"长安|洛阳|大雁塔",中间用"|"来分割字符串
+    /// * `color_format:&str`: 颜色格式串, 可以包含换行分隔符,语法是","后加分割字符串. 注意，RGB和HSV,以及灰度格式都支持.
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
     /// # Return
-    /// `i32` 返回ret中的坐标个数
-    ///
+    /// `Option<(String, Point)>`: 找到的字符串和坐标, 没找到返回 `None` (插件返回"id|x|y"格式, 在本地转换)
     /// # Examples
     /// ```
     /// let dm = Dmsoft::new();
-    ///
-    /// let s = dm.FindColorEx(0,0,2000,2000,"123456-000000|abcdef-202020",1.0,0).unwrap();
-    /// let count = dm.GetResultCount(s).unwrap();
+    /// let strs = StrSet::new().with("长安").with("洛阳");
+    /// if let Some((s, pos)) = dm.FindStrE(0,0,2000,2000,&strs,"9f2e3f-000000",1.0).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
     /// ```
-    pub unsafe fn GetResultCount(&self, str: &str) -> Result<i32> {
-        static NAME: &str = "GetResultCount";
-        let mut args = [Dmsoft::bstrVal(str)];
+    /// # Note:
+    /// * 此函数的原理是先Ocr识别，然后再查找。所以速度比FindStrFast要慢，尤其是在字库 很大，或者模糊度不为1.0时。\
+    /// * 一般字库字符数量小于100左右，模糊度为1.0时，用FindStr要快一些,否则用FindStrFast.
+    pub unsafe fn FindStrE(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        str: &StrSet,
+        color: &str,
+        sim: f64,
+    ) -> Result<Option<(String, Point)>> {
+        static NAME: &str = "FindStrE";
+        let mut args = [
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(color),
+            Dmsoft::bstrVal(&str.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
         let result = self.Invoke(NAME, &mut args)?;
         let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
-        let a = result.Anonymous.lVal;
-        Ok(a)
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(str.matched_one(&result))
     }
 
-    /// 对插件部分接口的返回值进行解析,并根据指定的第index个坐标,返回具体的值
+    /// 在屏幕范围(x1,y1,x2,y2)内,查找string(可以是任意个字符串的组合),并返回符合color_format的坐标位置,相似度sim同Ocr接口描述. 此函数返回所有找到的坐标.
     /// # The function prototype
     /// ```C++
-    /// long dmsoft::GetResultPos(const TCHAR * str,long index,long * x,long * y)
+    /// CString dmsoft::FindStrEx(long x1,long y1,long x2,long y2,const TCHAR * str,const TCHAR * color,double sim)
     /// ```
     /// # Args
-    /// `ret:&str`: 部分接口的返回串
-    /// `index:i32`: 第几个坐标
-    /// `x:&mut i32`: 返回X坐标
-    /// `y:&mut i32`: 返回Y坐标
-    ///
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `string:&StrSet`: 待查找的字符串,可以是字符串组合，比如"长安|洛阳|大雁塔",中间用"|"来分割字符串
+    /// * `color_format:&str`: 颜色格式串, 可以包含换行分隔符,语法是","后加分割字符串. 注意，RGB和HSV,以及灰度格式都支持.
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
     /// # Return
-    /// `i32`: 0: 失败 1: 成功
-    ///
+    /// `Vec<(String, Point)>`: 所有找到的字符串和坐标 (插件返回"id,x,y|id,x,y"格式, 在本地转换)
     /// # Examples
     /// ```
     /// let dm = Dmsoft::new();
-    /// let (mut x,mut y) = (0,0);
-    ///
-    /// let s = dm.FindColorEx(0,0,2000,2000,"123456-000000|abcdef-202020",1.0,0).unwrap();
-    /// let count = dm.GetResultCount(s)
-    /// for i in 0..count{
-    ///     let dm_ret = dm.GetResultPos(s,i,&mut x,&mut y).unwrap();
+    /// let strs = StrSet::new().with("长安").with("洛阳");
+    /// for (s, pos) in dm.FindStrEx(0,0,2000,2000,&strs,"9f2e3f-000000",1.0).unwrap() {
+    ///     println!("{}: {},{}", s, pos.x, pos.y);
     /// }
     /// ```
-    pub unsafe fn GetResultPos(
+    /// # Note:
+    /// * 此函数的原理是先Ocr识别，然后再查找。所以速度比FindStrFast要慢，尤其是在字库 很大，或者模糊度不为1.0时。\
+    /// * 一般字库字符数量小于100左右，模糊度为1.0时，用FindStr要快一些,否则用FindStrFast.
+    pub unsafe fn FindStrEx(
         &self,
-        str: &str,
-        index: i32,
-        x: &mut i32,
-        y: &mut i32,
-    ) -> Result<i32> {
-        static NAME: &str = "GetResultPos";
-        let mut px = VARIANT::default();
-        let mut py = VARIANT::default();
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        str: &StrSet,
+        color: &str,
+        sim: f64,
+    ) -> Result<Vec<(String, Point)>> {
+        static NAME: &str = "FindStrEx";
         let mut args = [
-            Dmsoft::pvarVal(&mut py),
-            Dmsoft::pvarVal(&mut px),
-            Dmsoft::longVar(index),
-            Dmsoft::bstrVal(str),
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(color),
+            Dmsoft::bstrVal(&str.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
         ];
         let result = self.Invoke(NAME, &mut args)?;
         let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
 
-        *x = px.Anonymous.Anonymous.Anonymous.lVal;
-        *y = py.Anonymous.Anonymous.Anonymous.lVal;
-
-        Ok(result.Anonymous.lVal)
+        Ok(str.matched_list(&result))
     }
 
-    /// 表示使用哪个字库文件进行识别(index范围:0-99)
-    ///
-    /// 设置之后，永久生效，除非再次设定
-    ///
+    /// 在屏幕范围(x1,y1,x2,y2)内,查找string(可以是任意个字符串的组合),并返回符合color_format的坐标位置,相似度sim同Ocr接口描述. 此函数同FindStr,只是返回的是找到的字符串,而不是序号.
     /// # The function prototype
     /// ```C++
-    /// long dmsoft::UseDict(long index)
+    /// CString dmsoft::FindStrS(long x1,long y1,long x2,long y2,const TCHAR * str,const TCHAR * color,double sim,long * x,long * y)
     /// ```
-    ///
     /// # Args
-    /// * `index:i32`: 字库编号(0-99)
-    ///
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `string:&StrSet`: 待查找的字符串,可以是字符串组合，比如"长安|洛阳|大雁塔",中间用"|"来分割字符串
+    /// * `color_format:&str`: 颜色格式串, 可以包含换行分隔符,语法是","后加分割字符串. 注意，RGB和HSV,以及灰度格式都支持.
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
     /// # Return
-    /// `i32`: 0: 失败 1: 成功
-    ///
+    /// `Option<(String, Point)>`: 找到的字符串和坐标, 没找到返回 `None` (插件返回找到的字符串, 坐标通过输出参数返回)
     /// # Examples
     /// ```
     /// let dm = Dmsoft::new();
-    /// let dm_ret = dm.UseDict(1).unwrap();
-    /// ss = dm.Ocr(0,0,2000,2000,"FFFFFF-000000",1.0).unwrap();
-    /// dm_ret = dm.UseDict(0).unwrap();
+    /// let strs = StrSet::new().with("长安").with("洛阳");
+    /// if let Some((s, pos)) = dm.FindStrS(0,0,2000,2000,&strs,"9f2e3f-000000",1.0).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
     /// ```
-    pub unsafe fn UseDict(&self, index: i32) -> Result<i32> {
-        static NAME: &str = "UseDict";
-        let mut args = [Dmsoft::longVar(index)];
+    /// # Note:
+    /// * 此函数的原理是先Ocr识别，然后再查找。所以速度比FindStrFast要慢，尤其是在字库 很大，或者模糊度不为1.0时。\
+    /// * 一般字库字符数量小于100左右，模糊度为1.0时，用FindStr要快一些,否则用FindStrFast.
+    pub unsafe fn FindStrS(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        str: &StrSet,
+        color: &str,
+        sim: f64,
+    ) -> Result<Option<(String, Point)>> {
+        static NAME: &str = "FindStrS";
+        let mut px = VARIANT::default();
+        let mut py = VARIANT::default();
+        let mut args = [
+            Dmsoft::pvarVal(&mut py),
+            Dmsoft::pvarVal(&mut px),
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(color),
+            Dmsoft::bstrVal(&str.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
         let result = self.Invoke(NAME, &mut args)?;
         let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
-        Ok(result.Anonymous.lVal)
-    }
+        let pos = Point::new(
+            px.Anonymous.Anonymous.Anonymous.lVal,
+            py.Anonymous.Anonymous.Anonymous.lVal,
+        );
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
 
-    /// 设置字库的密码,在SetDict前调用,目前的设计是,所有字库通用一个密码.
-    /// # The function prototype
-    /// ```C++
-    /// long dmsoft::SetDictPwd(const TCHAR * pwd)
-    /// ```
-    /// # Args
-    /// * `pwd: &str`: 字库密码
-    /// # Return
-    /// `i32`: 0: 失败 1: 成功
-    /// # Examples
-    /// ```
-    /// let dm = Dmsoft::new();
-    /// dm.SetDictPwd("1234").unwrap();
-    /// ```
-    /// # Note:
-    /// * 如果使用了多字库,所有字库的密码必须一样. 此函数必须在SetDict之前调用,否则会解密失败.
-    pub unsafe fn SetDictPwd(&self, pwd: &str) -> Result<i32> {
-        static NAME: &str = "SetDictPwd";
-        let mut args = [Dmsoft::bstrVal(pwd)];
-        let result = self.Invoke(NAME, &mut args).unwrap();
-        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
-        Ok(result.Anonymous.lVal)
+        Ok((!result.is_empty()).then_some((result, pos)))
     }
 
-    /// 识别位图中区域(x1,y1,x2,y2)的文字
+    /// 在屏幕范围(x1,y1,x2,y2)内,查找string(可以是任意个字符串的组合),并返回符合color_format的坐标位置,相似度sim同Ocr接口描述. 此函数同FindStrEx,只是返回的是找到的字符串,而不是序号.
     /// # The function prototype
     /// ```C++
-    /// CString dmsoft::OcrInFile(long x1,long y1,long x2,long y2,const TCHAR * pic_name,const TCHAR * color,double sim)
+    /// CString dmsoft::FindStrExS(long x1,long y1,long x2,long y2,const TCHAR * str,const TCHAR * color,double sim)
     /// ```
     /// # Args
     /// * `x1:i32`: 区域的左上X坐标
     /// * `y1:i32`: 区域的左上Y坐标
     /// * `x2:i32`: 区域的右下X坐标
     /// * `y2:i32`: 区域的右下Y坐标
-    /// * `pic_name:&str`: 图片文件名
-    /// * `color_format:&str`: 颜色格式串. 注意，RGB和HSV,以及灰度格式都支持.
+    /// * `string:&StrSet`: 待查找的字符串,可以是字符串组合，比如"长安|洛阳|大雁塔",中间用"|"来分割字符串
+    /// * `color_format:&str`: 颜色格式串, 可以包含换行分隔符,语法是","后加分割字符串. 注意，RGB和HSV,以及灰度格式都支持.
     /// * `sim:f64`: 相似度,取值范围0.1-1.0
     /// # Return
-    /// * `String`: 返回识别到的字符串
+    /// `Vec<(String, Point)>`: 所有找到的字符串和坐标 (插件返回"str,x,y|str,x,y"格式, 在本地转换)
     /// # Examples
     /// ```
     /// let dm = Dmsoft::new();
-    /// let result = dm.OcrInFile(0,0,2000,2000,"test.bmp","000000-000000",1.0).unwrap();
+    /// let strs = StrSet::new().with("长安").with("洛阳");
+    /// for (s, pos) in dm.FindStrExS(0,0,2000,2000,&strs,"9f2e3f-000000",1.0).unwrap() {
+    ///     println!("{}: {},{}", s, pos.x, pos.y);
+    /// }
     /// ```
-    pub unsafe fn OcrInFile(
+    /// # Note:
+    /// * 此函数的原理是先Ocr识别，然后再查找。所以速度比FindStrFast要慢，尤其是在字库 很大，或者模糊度不为1.0时。\
+    /// * 一般字库字符数量小于100左右，模糊度为1.0时，用FindStr要快一些,否则用FindStrFast.
+    pub unsafe fn FindStrExS(
         &self,
         x1: i32,
         y1: i32,
         x2: i32,
         y2: i32,
-        pic_name: &str,
+        str: &StrSet,
         color: &str,
         sim: f64,
-    ) -> Result<String> {
-        static NAME: &str = "OcrInFile";
-
+    ) -> Result<Vec<(String, Point)>> {
+        static NAME: &str = "FindStrExS";
         let mut args = [
             Dmsoft::doubleVar(sim),
             Dmsoft::bstrVal(color),
-            Dmsoft::bstrVal(pic_name),
+            Dmsoft::bstrVal(&str.to_string()),
             Dmsoft::longVar(y2),
             Dmsoft::longVar(x2),
             Dmsoft::longVar(y1),
             Dmsoft::longVar(x1),
         ];
-
         let result = self.Invoke(NAME, &mut args)?;
         let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
-        let result = ManuallyDrop::into_inner(result.Anonymous.bstrVal);
-        Ok(result.try_into().unwrap())
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(pic::parse_list(&result)
+            .map(|(s, pos)| (s.to_owned(), pos))
+            .collect())
     }
 
-    /// 高级用户使用,在识别前,如果待识别区域有多行文字,可以设定行间距,默认的行间距是1,
-    ///
-    /// 如果根据情况设定,可以提高识别精度。一般不用设定。
+    /// 在屏幕范围(x1,y1,x2,y2)内,查找string(可以是任意个字符串的组合),并返回符合color_format的坐标位置,相似度sim同Ocr接口描述. 此函数同FindStr,只是算法不同.
     /// # The function prototype
     /// ```C++
-    /// long dmsoft::SetMinRowGap(long row_gap)
+    /// long dmsoft::FindStrFast(long x1,long y1,long x2,long y2,const TCHAR * str,const TCHAR * color,double sim,long * x,long * y)
     /// ```
     /// # Args
-    /// * `row_gap:i32`: 最小行间距
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `string:&StrSet`: 待查找的字符串,可以是字符串组合，比如"长安|洛阳|大雁塔",中间用"|"来分割字符串
+    /// * `color_format:&str`: 颜色格式串, 可以包含换行分隔符,语法是","后加分割字符串. 注意，RGB和HSV,以及灰度格式都支持.
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// # Return
+    /// `Option<(String, Point)>`: 找到的字符串和坐标, 没找到返回 `None`
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let strs = StrSet::new().with("长安").with("洛阳");
+    /// if let Some((s, pos)) = dm.FindStrFast(0,0,2000,2000,&strs,"9f2e3f-000000",1.0).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    /// ```
+    /// # Note:
+    /// * 此函数比FindStr要快很多，尤其是在字库很大时，或者是远程控制时.
+    pub unsafe fn FindStrFast(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        str: &StrSet,
+        color: &str,
+        sim: f64,
+    ) -> Result<Option<(String, Point)>> {
+        static NAME: &str = "FindStrFast";
+        let mut px = VARIANT::default();
+        let mut py = VARIANT::default();
+        let mut args = [
+            Dmsoft::pvarVal(&mut py),
+            Dmsoft::pvarVal(&mut px),
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(color),
+            Dmsoft::bstrVal(&str.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let pos = Point::new(
+            px.Anonymous.Anonymous.Anonymous.lVal,
+            py.Anonymous.Anonymous.Anonymous.lVal,
+        );
+
+        Ok(str.matched(result.Anonymous.lVal, pos))
+    }
+
+    /// 在屏幕范围(x1,y1,x2,y2)内,查找string(可以是任意个字符串的组合),并返回符合color_format的坐标位置,相似度sim同Ocr接口描述. 此函数同FindStrFast,只是返回值不同,坐标通过返回值返回.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::FindStrFastE(long x1,long y1,long x2,long y2,const TCHAR * str,const TCHAR * color,double sim)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `string:&StrSet`: 待查找的字符串,可以是字符串组合，比如"长安|洛阳|大雁塔",中间用"|"来分割字符串
+    /// * `color_format:&str`: 颜色格式串, 可以包含换行分隔符,语法是","后加分割字符串. 注意，RGB和HSV,以及灰度格式都支持.
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// # Return
+    /// `Option<(String, Point)>`: 找到的字符串和坐标, 没找到返回 `None` (插件返回"id|x|y"格式, 在本地转换)
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let strs = StrSet::new().with("长安").with("洛阳");
+    /// if let Some((s, pos)) = dm.FindStrFastE(0,0,2000,2000,&strs,"9f2e3f-000000",1.0).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    /// ```
+    /// # Note:
+    /// * 此函数比FindStr要快很多，尤其是在字库很大时，或者是远程控制时.
+    pub unsafe fn FindStrFastE(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        str: &StrSet,
+        color: &str,
+        sim: f64,
+    ) -> Result<Option<(String, Point)>> {
+        static NAME: &str = "FindStrFastE";
+        let mut args = [
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(color),
+            Dmsoft::bstrVal(&str.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(str.matched_one(&result))
+    }
+
+    /// 在屏幕范围(x1,y1,x2,y2)内,查找string(可以是任意个字符串的组合),并返回符合color_format的坐标位置,相似度sim同Ocr接口描述. 此函数同FindStrFast,返回所有找到的坐标.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::FindStrFastEx(long x1,long y1,long x2,long y2,const TCHAR * str,const TCHAR * color,double sim)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `string:&StrSet`: 待查找的字符串,可以是字符串组合，比如"长安|洛阳|大雁塔",中间用"|"来分割字符串
+    /// * `color_format:&str`: 颜色格式串, 可以包含换行分隔符,语法是","后加分割字符串. 注意，RGB和HSV,以及灰度格式都支持.
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// # Return
+    /// `Vec<(String, Point)>`: 所有找到的字符串和坐标 (插件返回"id,x,y|id,x,y"格式, 在本地转换)
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let strs = StrSet::new().with("长安").with("洛阳");
+    /// for (s, pos) in dm.FindStrFastEx(0,0,2000,2000,&strs,"9f2e3f-000000",1.0).unwrap() {
+    ///     println!("{}: {},{}", s, pos.x, pos.y);
+    /// }
+    /// ```
+    /// # Note:
+    /// * 此函数比FindStr要快很多，尤其是在字库很大时，或者是远程控制时.
+    pub unsafe fn FindStrFastEx(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        str: &StrSet,
+        color: &str,
+        sim: f64,
+    ) -> Result<Vec<(String, Point)>> {
+        static NAME: &str = "FindStrFastEx";
+        let mut args = [
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(color),
+            Dmsoft::bstrVal(&str.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(str.matched_list(&result))
+    }
+
+    /// 在屏幕范围(x1,y1,x2,y2)内,查找string(可以是任意个字符串的组合),并返回符合color_format的坐标位置,相似度sim同Ocr接口描述. 此函数同FindStrFast,只是返回的是找到的字符串,而不是序号.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::FindStrFastS(long x1,long y1,long x2,long y2,const TCHAR * str,const TCHAR * color,double sim,long * x,long * y)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `string:&StrSet`: 待查找的字符串,可以是字符串组合，比如"长安|洛阳|大雁塔",中间用"|"来分割字符串
+    /// * `color_format:&str`: 颜色格式串, 可以包含换行分隔符,语法是","后加分割字符串. 注意，RGB和HSV,以及灰度格式都支持.
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// # Return
+    /// `Option<(String, Point)>`: 找到的字符串和坐标, 没找到返回 `None` (插件返回找到的字符串, 坐标通过输出参数返回)
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let strs = StrSet::new().with("长安").with("洛阳");
+    /// if let Some((s, pos)) = dm.FindStrFastS(0,0,2000,2000,&strs,"9f2e3f-000000",1.0).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    /// ```
+    /// # Note:
+    /// * 此函数比FindStr要快很多，尤其是在字库很大时，或者是远程控制时.
+    pub unsafe fn FindStrFastS(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        str: &StrSet,
+        color: &str,
+        sim: f64,
+    ) -> Result<Option<(String, Point)>> {
+        static NAME: &str = "FindStrFastS";
+        let mut px = VARIANT::default();
+        let mut py = VARIANT::default();
+        let mut args = [
+            Dmsoft::pvarVal(&mut py),
+            Dmsoft::pvarVal(&mut px),
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(color),
+            Dmsoft::bstrVal(&str.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let pos = Point::new(
+            px.Anonymous.Anonymous.Anonymous.lVal,
+            py.Anonymous.Anonymous.Anonymous.lVal,
+        );
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok((!result.is_empty()).then_some((result, pos)))
+    }
+
+    /// 在屏幕范围(x1,y1,x2,y2)内,查找string(可以是任意个字符串的组合),并返回符合color_format的坐标位置,相似度sim同Ocr接口描述. 此函数同FindStrFastEx,只是返回的是找到的字符串,而不是序号.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::FindStrFastExS(long x1,long y1,long x2,long y2,const TCHAR * str,const TCHAR * color,double sim)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `string:&StrSet`: 待查找的字符串,可以是字符串组合，比如"长安|洛阳|大雁塔",中间用"|"来分割字符串
+    /// * `color_format:&str`: 颜色格式串, 可以包含换行分隔符,语法是","后加分割字符串. 注意，RGB和HSV,以及灰度格式都支持.
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// # Return
+    /// `Vec<(String, Point)>`: 所有找到的字符串和坐标 (插件返回"str,x,y|str,x,y"格式, 在本地转换)
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let strs = StrSet::new().with("长安").with("洛阳");
+    /// for (s, pos) in dm.FindStrFastExS(0,0,2000,2000,&strs,"9f2e3f-000000",1.0).unwrap() {
+    ///     println!("{}: {},{}", s, pos.x, pos.y);
+    /// }
+    /// ```
+    /// # Note:
+    /// * 此函数比FindStr要快很多，尤其是在字库很大时，或者是远程控制时.
+    pub unsafe fn FindStrFastExS(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        str: &StrSet,
+        color: &str,
+        sim: f64,
+    ) -> Result<Vec<(String, Point)>> {
+        static NAME: &str = "FindStrFastExS";
+        let mut args = [
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(color),
+            Dmsoft::bstrVal(&str.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(pic::parse_list(&result)
+            .map(|(s, pos)| (s.to_owned(), pos))
+            .collect())
+    }
+
+    /// 同FindStr，但是不使用SetDict设置的字库，而利用系统自带的字库，速度比FindStr稍慢.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::FindStrWithFont(long x1,long y1,long x2,long y2,const TCHAR * str,const TCHAR * color,double sim,const TCHAR * font_name,long font_size,long flag,long * x,long * y)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `string:&StrSet`: 待查找的字符串,可以是字符串组合，比如"长安|洛阳|大雁塔",中间用"|"来分割字符串
+    /// * `color_format:&str`: 颜色格式串, 可以包含换行分隔符,语法是","后加分割字符串. 注意，RGB和HSV,以及灰度格式都支持.
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// * `font_name:&str`: 系统字体名,比如"宋体"
+    /// * `font_size:i32`: 系统字体尺寸，这个尺寸一定要以大漠综合工具获取的为准.如果获取尺寸看视频教程.
+    /// * `flag:FontFlags`: 字体类别 比如粗体 `FontFlags::BOLD`, 可以用 `|` 组合
+    /// # Return
+    /// `Option<(String, Point)>`: 找到的字符串和坐标, 没找到返回 `None`
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let strs = StrSet::new().with("长安").with("洛阳");
+    /// if let Some((s, pos)) = dm.FindStrWithFont(0,0,2000,2000,&strs,"9f2e3f-000000",1.0,"宋体",9,FontFlags::NORMAL).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    /// ```
+    /// # Note:
+    /// * 此函数的原理是先用指定的系统字体生成点阵, 再查找. 不需要字库.
+    pub unsafe fn FindStrWithFont(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        str: &StrSet,
+        color: &str,
+        sim: f64,
+        font_name: &str,
+        font_size: i32,
+        flag: FontFlags,
+    ) -> Result<Option<(String, Point)>> {
+        static NAME: &str = "FindStrWithFont";
+        let mut px = VARIANT::default();
+        let mut py = VARIANT::default();
+        let mut args = [
+            Dmsoft::pvarVal(&mut py),
+            Dmsoft::pvarVal(&mut px),
+            Dmsoft::longVar(flag.bits()),
+            Dmsoft::longVar(font_size),
+            Dmsoft::bstrVal(font_name),
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(color),
+            Dmsoft::bstrVal(&str.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let pos = Point::new(
+            px.Anonymous.Anonymous.Anonymous.lVal,
+            py.Anonymous.Anonymous.Anonymous.lVal,
+        );
+
+        Ok(str.matched(result.Anonymous.lVal, pos))
+    }
+
+    /// 同FindStrE，但是不使用SetDict设置的字库，而利用系统自带的字库，速度比FindStrE稍慢.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::FindStrWithFontE(long x1,long y1,long x2,long y2,const TCHAR * str,const TCHAR * color,double sim,const TCHAR * font_name,long font_size,long flag)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `string:&StrSet`: 待查找的字符串,可以是字符串组合，比如"长安|洛阳|大雁塔",中间用"|"来分割字符串
+    /// * `color_format:&str`: 颜色格式串, 可以包含换行分隔符,语法是","后加分割字符串. 注意，RGB和HSV,以及灰度格式都支持.
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// * `font_name:&str`: 系统字体名,比如"宋体"
+    /// * `font_size:i32`: 系统字体尺寸，这个尺寸一定要以大漠综合工具获取的为准.如果获取尺寸看视频教程.
+    /// * `flag:FontFlags`: 字体类别 比如粗体 `FontFlags::BOLD`, 可以用 `|` 组合
+    /// # Return
+    /// `Option<(String, Point)>`: 找到的字符串和坐标, 没找到返回 `None` (插件返回"id|x|y"格式, 在本地转换)
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let strs = StrSet::new().with("长安").with("洛阳");
+    /// if let Some((s, pos)) = dm.FindStrWithFontE(0,0,2000,2000,&strs,"9f2e3f-000000",1.0,"宋体",9,FontFlags::NORMAL).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    /// ```
+    /// # Note:
+    /// * 此函数的原理是先用指定的系统字体生成点阵, 再查找. 不需要字库.
+    pub unsafe fn FindStrWithFontE(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        str: &StrSet,
+        color: &str,
+        sim: f64,
+        font_name: &str,
+        font_size: i32,
+        flag: FontFlags,
+    ) -> Result<Option<(String, Point)>> {
+        static NAME: &str = "FindStrWithFontE";
+        let mut args = [
+            Dmsoft::longVar(flag.bits()),
+            Dmsoft::longVar(font_size),
+            Dmsoft::bstrVal(font_name),
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(color),
+            Dmsoft::bstrVal(&str.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(str.matched_one(&result))
+    }
+
+    /// 同FindStrEx，但是不使用SetDict设置的字库，而利用系统自带的字库，速度比FindStrEx稍慢.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::FindStrWithFontEx(long x1,long y1,long x2,long y2,const TCHAR * str,const TCHAR * color,double sim,const TCHAR * font_name,long font_size,long flag)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `string:&StrSet`: 待查找的字符串,可以是字符串组合，比如"长安|洛阳|大雁塔",中间用"|"来分割字符串
+    /// * `color_format:&str`: 颜色格式串, 可以包含换行分隔符,语法是","后加分割字符串. 注意，RGB和HSV,以及灰度格式都支持.
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// * `font_name:&str`: 系统字体名,比如"宋体"
+    /// * `font_size:i32`: 系统字体尺寸，这个尺寸一定要以大漠综合工具获取的为准.如果获取尺寸看视频教程.
+    /// * `flag:FontFlags`: 字体类别 比如粗体 `FontFlags::BOLD`, 可以用 `|` 组合
+    /// # Return
+    /// `Vec<(String, Point)>`: 所有找到的字符串和坐标 (插件返回"id,x,y|id,x,y"格式, 在本地转换)
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let strs = StrSet::new().with("长安").with("洛阳");
+    /// for (s, pos) in dm.FindStrWithFontEx(0,0,2000,2000,&strs,"9f2e3f-000000",1.0,"宋体",9,FontFlags::NORMAL).unwrap() {
+    ///     println!("{}: {},{}", s, pos.x, pos.y);
+    /// }
+    /// ```
+    /// # Note:
+    /// * 此函数的原理是先用指定的系统字体生成点阵, 再查找. 不需要字库.
+    pub unsafe fn FindStrWithFontEx(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        str: &StrSet,
+        color: &str,
+        sim: f64,
+        font_name: &str,
+        font_size: i32,
+        flag: FontFlags,
+    ) -> Result<Vec<(String, Point)>> {
+        static NAME: &str = "FindStrWithFontEx";
+        let mut args = [
+            Dmsoft::longVar(flag.bits()),
+            Dmsoft::longVar(font_size),
+            Dmsoft::bstrVal(font_name),
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(color),
+            Dmsoft::bstrVal(&str.to_string()),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(str.matched_list(&result))
+    }
+
+    /// 对插件部分接口的返回值进行解析,并返回ret中的坐标个数
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::GetResultCount(const TCHAR * str)
+    /// ```
+    ///
+    /// # Args
+    /// * `str:&str` 部分接口的返回串
+    /// # Return
+    /// `i32` 返回ret中的坐标个数
+    ///
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    ///
+    /// let s = dm.FindColorEx(0,0,2000,2000,"123456-000000|abcdef-202020",1.0,0).unwrap();
+    /// let count = dm.GetResultCount(s).unwrap();
+    /// ```
+    pub unsafe fn GetResultCount(&self, str: &str) -> Result<i32> {
+        static NAME: &str = "GetResultCount";
+        let mut args = [Dmsoft::bstrVal(str)];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let a = result.Anonymous.lVal;
+        Ok(a)
+    }
+
+    /// 对插件部分接口的返回值进行解析,并根据指定的第index个坐标,返回具体的值
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::GetResultPos(const TCHAR * str,long index,long * x,long * y)
+    /// ```
+    /// # Args
+    /// `ret:&str`: 部分接口的返回串
+    /// `index:i32`: 第几个坐标
+    /// `x:&mut i32`: 返回X坐标
+    /// `y:&mut i32`: 返回Y坐标
+    ///
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    ///
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let (mut x,mut y) = (0,0);
+    ///
+    /// let s = dm.FindColorEx(0,0,2000,2000,"123456-000000|abcdef-202020",1.0,0).unwrap();
+    /// let count = dm.GetResultCount(s)
+    /// for i in 0..count{
+    ///     let dm_ret = dm.GetResultPos(s,i,&mut x,&mut y).unwrap();
+    /// }
+    /// ```
+    pub unsafe fn GetResultPos(
+        &self,
+        str: &str,
+        index: i32,
+        x: &mut i32,
+        y: &mut i32,
+    ) -> Result<i32> {
+        static NAME: &str = "GetResultPos";
+        let mut px = VARIANT::default();
+        let mut py = VARIANT::default();
+        let mut args = [
+            Dmsoft::pvarVal(&mut py),
+            Dmsoft::pvarVal(&mut px),
+            Dmsoft::longVar(index),
+            Dmsoft::bstrVal(str),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+
+        *x = px.Anonymous.Anonymous.Anonymous.lVal;
+        *y = py.Anonymous.Anonymous.Anonymous.lVal;
+
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 根据指定的范围,以及设定好的词组识别参数(一般不用更改,除非你真的理解了)
+    ///
+    /// 识别这个范围内所有满足条件的词组. 比较适合用在未知文字的情况下,进行不定识别.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::GetWords(long x1,long y1,long x2,long y2,const TCHAR * color,double sim)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `color:&str`: 颜色格式串, 同 `Ocr`
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// # Return
+    /// `String`: 识别到的格式串, 用 `GetWordResultCount` 等函数或者 [`Dmsoft::word_results`] 解析
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let s = dm.GetWords(0,0,2000,2000,"ffffff-000000",1.0).unwrap();
+    /// for word in dm.word_results(&s).unwrap() {
+    ///     println!("{}: {},{}", word.text, word.pos.x, word.pos.y);
+    /// }
+    /// ```
+    pub unsafe fn GetWords(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        color: &str,
+        sim: f64,
+    ) -> Result<String> {
+        static NAME: &str = "GetWords";
+        let mut args = [
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(color),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = ManuallyDrop::into_inner(result.Anonymous.bstrVal);
+        Ok(result.try_into().unwrap())
+    }
+
+    /// 根据指定的范围,以及设定好的词组识别参数(一般不用更改,除非你真的理解了)
+    ///
+    /// 识别这个范围内所有满足条件的词组. 这个识别函数不会用到字库. 只是识别大概形状的位置.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::GetWordsNoDict(long x1,long y1,long x2,long y2,const TCHAR * color)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `color:&str`: 颜色格式串, 同 `Ocr`
+    /// # Return
+    /// `String`: 识别到的格式串, 用 `GetWordResultCount` 等函数或者 [`Dmsoft::word_results`] 解析
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let s = dm.GetWordsNoDict(0,0,2000,2000,"ffffff-000000").unwrap();
+    /// let count = dm.GetWordResultCount(&s).unwrap();
+    /// ```
+    pub unsafe fn GetWordsNoDict(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        color: &str,
+    ) -> Result<String> {
+        static NAME: &str = "GetWordsNoDict";
+        let mut args = [
+            Dmsoft::bstrVal(color),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = ManuallyDrop::into_inner(result.Anonymous.bstrVal);
+        Ok(result.try_into().unwrap())
+    }
+
+    /// 在使用GetWords进行词组识别以后,可以用此接口进行识别词组数量的计算.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::GetWordResultCount(const TCHAR * str)
+    /// ```
+    /// # Args
+    /// * `str:&str`: `GetWords` 或者 `GetWordsNoDict` 的返回值
+    /// # Return
+    /// `i32`: 返回词组数量
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let s = dm.GetWords(0,0,2000,2000,"ffffff-000000",1.0).unwrap();
+    /// let count = dm.GetWordResultCount(&s).unwrap();
+    /// ```
+    pub unsafe fn GetWordResultCount(&self, str: &str) -> Result<i32> {
+        static NAME: &str = "GetWordResultCount";
+        let mut args = [Dmsoft::bstrVal(str)];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 在使用GetWords进行词组识别以后,可以用此接口进行识别各个词组的坐标
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::GetWordResultPos(const TCHAR * str,long index,long * x,long * y)
+    /// ```
+    /// # Args
+    /// * `str:&str`: `GetWords` 或者 `GetWordsNoDict` 的返回值
+    /// * `index:i32`: 表示第几个词组
+    /// # Return
+    /// `Option<Point>`: 词组左上角的坐标, 失败返回 `None`
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let s = dm.GetWords(0,0,2000,2000,"ffffff-000000",1.0).unwrap();
+    /// if let Some(pos) = dm.GetWordResultPos(&s, 0).unwrap() {
+    ///     dm.MoveTo(pos.x, pos.y).unwrap();
+    /// }
+    /// ```
+    pub unsafe fn GetWordResultPos(&self, str: &str, index: i32) -> Result<Option<Point>> {
+        static NAME: &str = "GetWordResultPos";
+        let mut px = VARIANT::default();
+        let mut py = VARIANT::default();
+        let mut args = [
+            Dmsoft::pvarVal(&mut py),
+            Dmsoft::pvarVal(&mut px),
+            Dmsoft::longVar(index),
+            Dmsoft::bstrVal(str),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let pos = Point::new(
+            px.Anonymous.Anonymous.Anonymous.lVal,
+            py.Anonymous.Anonymous.Anonymous.lVal,
+        );
+
+        Ok((result.Anonymous.lVal != 0).then_some(pos))
+    }
+
+    /// 在使用GetWords进行词组识别以后,可以用此接口进行识别各个词组的内容
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::GetWordResultStr(const TCHAR * str,long index)
+    /// ```
+    /// # Args
+    /// * `str:&str`: `GetWords` 或者 `GetWordsNoDict` 的返回值
+    /// * `index:i32`: 表示第几个词组
+    /// # Return
+    /// `String`: 返回的第index个词组内容
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let s = dm.GetWords(0,0,2000,2000,"ffffff-000000",1.0).unwrap();
+    /// let word = dm.GetWordResultStr(&s, 0).unwrap();
+    /// ```
+    pub unsafe fn GetWordResultStr(&self, str: &str, index: i32) -> Result<String> {
+        static NAME: &str = "GetWordResultStr";
+        let mut args = [Dmsoft::longVar(index), Dmsoft::bstrVal(str)];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = ManuallyDrop::into_inner(result.Anonymous.bstrVal);
+        Ok(result.try_into().unwrap())
+    }
+
+    /// 根据指定的范围,以及指定的颜色描述，提取点阵信息，类似于大漠工具里的单独提取.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::FetchWord(long x1,long y1,long x2,long y2,const TCHAR * color,const TCHAR * word)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `color:&str`: 颜色格式串, 同 `Ocr`
+    /// * `word:&str`: 待定义的文字,不能为空，且不能为关键符号"$"
+    /// # Return
+    /// `Option<DictEntry>`: 识别到的点阵信息, 可以直接添加到字库, 提取失败返回 `None`
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let mut dict = Dict::open("main.txt").unwrap();
+    /// if let Some(entry) = dm.FetchWord(200,170,1000,1000,"ffffff-000000","张三").unwrap() {
+    ///     dict.add(entry);
+    /// }
+    /// dict.save("main.txt").unwrap();
+    /// ```
+    pub unsafe fn FetchWord(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        color: &str,
+        word: &str,
+    ) -> Result<Option<DictEntry>> {
+        static NAME: &str = "FetchWord";
+        let mut args = [
+            Dmsoft::bstrVal(word),
+            Dmsoft::bstrVal(color),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result: String = ManuallyDrop::into_inner(result.Anonymous.bstrVal)
+            .try_into()
+            .unwrap();
+
+        Ok(result.parse().ok())
+    }
+
+    /// 表示使用哪个字库文件进行识别(index范围:0-99)
+    ///
+    /// 设置之后，永久生效，除非再次设定
+    ///
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::UseDict(long index)
+    /// ```
+    ///
+    /// # Args
+    /// * `index:DictSlot`: 字库编号(0-99)
+    ///
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    ///
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let dm_ret = dm.UseDict(DictSlot::new(1).unwrap()).unwrap();
+    /// ss = dm.Ocr(0,0,2000,2000,"FFFFFF-000000",1.0).unwrap();
+    /// dm_ret = dm.UseDict(DictSlot::default()).unwrap();
+    /// ```
+    pub unsafe fn UseDict(&self, index: DictSlot) -> Result<i32> {
+        static NAME: &str = "UseDict";
+        let mut args = [Dmsoft::longVar(index.index())];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 设置字库的密码,在SetDict前调用,目前的设计是,所有字库通用一个密码.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SetDictPwd(const TCHAR * pwd)
+    /// ```
+    /// # Args
+    /// * `pwd: &str`: 字库密码
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// dm.SetDictPwd("1234").unwrap();
+    /// ```
+    /// # Note:
+    /// * 如果使用了多字库,所有字库的密码必须一样. 此函数必须在SetDict之前调用,否则会解密失败.
+    pub unsafe fn SetDictPwd(&self, pwd: &str) -> Result<i32> {
+        static NAME: &str = "SetDictPwd";
+        let mut args = [Dmsoft::bstrVal(pwd)];
+        let result = self.Invoke(NAME, &mut args).unwrap();
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 识别位图中区域(x1,y1,x2,y2)的文字
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::OcrInFile(long x1,long y1,long x2,long y2,const TCHAR * pic_name,const TCHAR * color,double sim)
+    /// ```
+    /// # Args
+    /// * `x1:i32`: 区域的左上X坐标
+    /// * `y1:i32`: 区域的左上Y坐标
+    /// * `x2:i32`: 区域的右下X坐标
+    /// * `y2:i32`: 区域的右下Y坐标
+    /// * `pic_name:&str`: 图片文件名
+    /// * `color_format:&str`: 颜色格式串. 注意，RGB和HSV,以及灰度格式都支持.
+    /// * `sim:f64`: 相似度,取值范围0.1-1.0
+    /// # Return
+    /// * `String`: 返回识别到的字符串
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let result = dm.OcrInFile(0,0,2000,2000,"test.bmp","000000-000000",1.0).unwrap();
+    /// ```
+    pub unsafe fn OcrInFile(
+        &self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        pic_name: &str,
+        color: &str,
+        sim: f64,
+    ) -> Result<String> {
+        static NAME: &str = "OcrInFile";
+
+        let mut args = [
+            Dmsoft::doubleVar(sim),
+            Dmsoft::bstrVal(color),
+            Dmsoft::bstrVal(pic_name),
+            Dmsoft::longVar(y2),
+            Dmsoft::longVar(x2),
+            Dmsoft::longVar(y1),
+            Dmsoft::longVar(x1),
+        ];
+
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = ManuallyDrop::into_inner(result.Anonymous.bstrVal);
+        Ok(result.try_into().unwrap())
+    }
+
+    /// 高级用户使用,在识别前,如果待识别区域有多行文字,可以设定行间距,默认的行间距是1,
+    ///
+    /// 如果根据情况设定,可以提高识别精度。一般不用设定。
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SetMinRowGap(long row_gap)
+    /// ```
+    /// # Args
+    /// * `row_gap:i32`: 最小行间距
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.SetMinRowGap(1) .unwrap();
+    /// ```
+    pub unsafe fn SetMinRowGap(&self, row_gap: i32) -> Result<i32> {
+        static NAME: &str = "SetMinRowGap";
+        let mut args = [Dmsoft::longVar(row_gap)];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_ocr_config(result, |config| config.min_row_gap = row_gap);
+
+        Ok(result)
+    }
+
+    /// 高级用户使用,在识别前,如果待识别区域有多行文字,可以设定列间距,默认的列间距是0,
+    ///
+    /// 如果根据情况设定,可以提高识别精度。一般不用设定。
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SetMinColGap(long col_gap)
+    /// ```
+    /// # Args
+    /// * `col_gap:i32`: 最小列间距
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.SetMinColGap(1) .unwrap();
+    /// ```
+    /// # Note
+    /// * 此设置如果不为0,那么将不能识别连体字 慎用.
+    pub unsafe fn SetMinColGap(&self, col_gap: i32) -> Result<i32> {
+        static NAME: &str = "SetMinColGap";
+        let mut args = [Dmsoft::longVar(col_gap)];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_ocr_config(result, |config| config.min_col_gap = col_gap);
+
+        Ok(result)
+    }
+
+    /// 设置是否开启精准识别
+    ///
+    /// 开启精准识别后, 识别速度会降低, 但是准确率更高. 默认是关闭的.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SetExactOcr(long exact_ocr)
+    /// ```
+    /// # Args
+    /// * `exact_ocr:bool`: `true` 开启精准识别, `false` 关闭精准识别
     /// # Return
     /// `i32`: 0: 失败 1: 成功
     /// # Examples
     /// ```
     /// let dm = Dmsoft::new();
-    /// let status = dm.SetMinRowGap(1) .unwrap();
+    /// let status = dm.SetExactOcr(true).unwrap();
     /// ```
-    pub unsafe fn SetMinRowGap(&self, row_gap: i32) -> Result<i32> {
-        static NAME: &str = "SetMinRowGap";
+    pub unsafe fn SetExactOcr(&self, exact_ocr: bool) -> Result<i32> {
+        static NAME: &str = "SetExactOcr";
+        let mut args = [Dmsoft::longVar(exact_ocr as i32)];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_ocr_config(result, |config| config.exact_ocr = exact_ocr);
+
+        Ok(result)
+    }
+
+    /// 设置词组识别的单词最小间距(默认是5)
+    ///
+    /// 此参数用于 `GetWords`, 两个单词间距不小于此值时分为两个单词.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SetWordGap(long word_gap)
+    /// ```
+    /// # Args
+    /// * `word_gap:i32`: 单词的最小间距
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.SetWordGap(5).unwrap();
+    /// ```
+    pub unsafe fn SetWordGap(&self, word_gap: i32) -> Result<i32> {
+        static NAME: &str = "SetWordGap";
+        let mut args = [Dmsoft::longVar(word_gap)];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_ocr_config(result, |config| config.word_gap = word_gap);
+
+        Ok(result)
+    }
+
+    /// 设置词组识别的平均行高(默认是10)
+    ///
+    /// 此参数用于 `GetWords`, 应该设置为要识别的文字的平均高度.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SetWordLineHeight(long line_height)
+    /// ```
+    /// # Args
+    /// * `line_height:i32`: 行高
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.SetWordLineHeight(12).unwrap();
+    /// ```
+    pub unsafe fn SetWordLineHeight(&self, line_height: i32) -> Result<i32> {
+        static NAME: &str = "SetWordLineHeight";
+        let mut args = [Dmsoft::longVar(line_height)];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_ocr_config(result, |config| config.word_line_height = line_height);
+
+        Ok(result)
+    }
+
+    /// 设置不使用字库的词组识别的最小行间距(默认是1)
+    ///
+    /// 此参数用于 `GetWordsNoDict`.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SetRowGapNoDict(long row_gap)
+    /// ```
+    /// # Args
+    /// * `row_gap:i32`: 最小行间距
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.SetRowGapNoDict(1).unwrap();
+    /// ```
+    pub unsafe fn SetRowGapNoDict(&self, row_gap: i32) -> Result<i32> {
+        static NAME: &str = "SetRowGapNoDict";
         let mut args = [Dmsoft::longVar(row_gap)];
         let result = self.Invoke(NAME, &mut args)?;
         let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_ocr_config(result, |config| config.row_gap_no_dict = row_gap);
 
-        Ok(result.Anonymous.lVal)
+        Ok(result)
     }
 
-    /// 高级用户使用,在识别前,如果待识别区域有多行文字,可以设定列间距,默认的列间距是0,
+    /// 设置不使用字库的词组识别的最小列间距(默认是1)
     ///
-    /// 如果根据情况设定,可以提高识别精度。一般不用设定。
+    /// 此参数用于 `GetWordsNoDict`.
     /// # The function prototype
     /// ```C++
-    /// long dmsoft::SetMinColGap(long col_gap)
+    /// long dmsoft::SetColGapNoDict(long col_gap)
     /// ```
     /// # Args
     /// * `col_gap:i32`: 最小列间距
@@ -360,16 +1551,331 @@ impl Dmsoft {
     /// # Examples
     /// ```
     /// let dm = Dmsoft::new();
-    /// let status = dm.SetMinColGap(1) .unwrap();
+    /// let status = dm.SetColGapNoDict(0).unwrap();
     /// ```
-    /// # Note
-    /// * 此设置如果不为0,那么将不能识别连体字 慎用.
-    pub unsafe fn SetMinColGap(&self, col_gap: i32) -> Result<i32> {
-        static NAME: &str = "SetMinColGap";
+    pub unsafe fn SetColGapNoDict(&self, col_gap: i32) -> Result<i32> {
+        static NAME: &str = "SetColGapNoDict";
         let mut args = [Dmsoft::longVar(col_gap)];
         let result = self.Invoke(NAME, &mut args)?;
         let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_ocr_config(result, |config| config.col_gap_no_dict = col_gap);
+
+        Ok(result)
+    }
+
+    /// 设置不使用字库的词组识别的单词最小间距(默认是5)
+    ///
+    /// 此参数用于 `GetWordsNoDict`.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SetWordGapNoDict(long word_gap)
+    /// ```
+    /// # Args
+    /// * `word_gap:i32`: 单词的最小间距
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.SetWordGapNoDict(5).unwrap();
+    /// ```
+    pub unsafe fn SetWordGapNoDict(&self, word_gap: i32) -> Result<i32> {
+        static NAME: &str = "SetWordGapNoDict";
+        let mut args = [Dmsoft::longVar(word_gap)];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_ocr_config(result, |config| config.word_gap_no_dict = word_gap);
+
+        Ok(result)
+    }
+
+    /// 设置不使用字库的词组识别的平均行高(默认是10)
+    ///
+    /// 此参数用于 `GetWordsNoDict`.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SetWordLineHeightNoDict(long line_height)
+    /// ```
+    /// # Args
+    /// * `line_height:i32`: 行高
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.SetWordLineHeightNoDict(12).unwrap();
+    /// ```
+    pub unsafe fn SetWordLineHeightNoDict(&self, line_height: i32) -> Result<i32> {
+        static NAME: &str = "SetWordLineHeightNoDict";
+        let mut args = [Dmsoft::longVar(line_height)];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = result.Anonymous.lVal;
+        self.track_ocr_config(result, |config| {
+            config.word_line_height_no_dict = line_height
+        });
+
+        Ok(result)
+    }
+
+    /// 设置字库文件
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SetDict(long index,const TCHAR * dict_name)
+    /// ```
+    /// # Args
+    /// * `index:DictSlot`: 字库的序号,取值为0-99,目前最多支持100个字库
+    /// * `file:&str`: 字库文件名
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.SetDict(DictSlot::default(),"test.txt").unwrap();
+    /// ```
+    /// # Note
+    /// * 此函数速度很慢，全局初始化时调用一次即可，切换字库用UseDict
+    pub unsafe fn SetDict(&self, index: DictSlot, file: &str) -> Result<i32> {
+        static NAME: &str = "SetDict";
+        let mut args = [Dmsoft::bstrVal(file), Dmsoft::longVar(index.index())];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 从内存中设置字库.
+    ///
+    /// 插件会一直使用这块内存, 直到字库被清除. 推荐使用 `DictManager::load_mem`, 由借用检查保证数据有效
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SetDictMem(long index,long addr,long size)
+    /// ```
+    /// # Args
+    /// * `index:DictSlot`: 字库的序号,取值为0-99,目前最多支持100个字库
+    /// * `dict:&[u8]`: 字库数据, 地址和长度由此计算
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// static DICT: &[u8] = include_bytes!("test.txt");
+    /// let dm = Dmsoft::new();
+    /// let status = dm.SetDictMem(DictSlot::default(), DICT).unwrap();
+    /// ```
+    /// # Note
+    /// * 此函数不支持加密的内存字库.
+    pub unsafe fn SetDictMem(&self, index: DictSlot, dict: &[u8]) -> Result<i32> {
+        static NAME: &str = "SetDictMem";
+        let mut args = [
+            Dmsoft::longVar(dict.len() as i32),
+            Dmsoft::longVar(dict.as_ptr() as i32),
+            Dmsoft::longVar(index.index()),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 给指定的字库中添加一条字库信息.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::AddDict(long index,const TCHAR * dict_info)
+    /// ```
+    /// # Args
+    /// * `index:DictSlot`: 字库的序号,取值为0-99,目前最多支持100个字库
+    /// * `dict_info:&str`: 字库描述串，具体参考大漠综合工具中的字符定义
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.AddDict(DictSlot::default(),"081101BF8020089FD10A21443F85038$记$0.0$11").unwrap();
+    /// ```
+    /// # Note
+    /// * 此函数尽量在小字库中使用，大字库中使用AddDict速度比较慢
+    pub unsafe fn AddDict(&self, index: DictSlot, dict_info: &str) -> Result<i32> {
+        static NAME: &str = "AddDict";
+        let mut args = [Dmsoft::bstrVal(dict_info), Dmsoft::longVar(index.index())];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 清空指定的字库.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::ClearDict(long index)
+    /// ```
+    /// # Args
+    /// * `index:DictSlot`: 字库的序号,取值为0-99,目前最多支持100个字库
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.ClearDict(DictSlot::default()).unwrap();
+    /// ```
+    pub unsafe fn ClearDict(&self, index: DictSlot) -> Result<i32> {
+        static NAME: &str = "ClearDict";
+        let mut args = [Dmsoft::longVar(index.index())];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 保存指定的字库到指定的文件中.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::SaveDict(long index,const TCHAR * file_name)
+    /// ```
+    /// # Args
+    /// * `index:DictSlot`: 字库的序号,取值为0-99,目前最多支持100个字库
+    /// * `file:&str`: 文件名
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.SaveDict(DictSlot::default(),"test.txt").unwrap();
+    /// ```
+    pub unsafe fn SaveDict(&self, index: DictSlot, file: &str) -> Result<i32> {
+        static NAME: &str = "SaveDict";
+        let mut args = [Dmsoft::bstrVal(file), Dmsoft::longVar(index.index())];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 获取指定字库中指定条目的字库信息.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::GetDict(long index,long font_index)
+    /// ```
+    /// # Args
+    /// * `index:DictSlot`: 字库的序号,取值为0-99,目前最多支持100个字库
+    /// * `font_index:i32`: 字库条目序号(从0开始)
+    /// # Return
+    /// `String`: 返回字库条目信息. 失败返回空串.
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let info = dm.GetDict(DictSlot::default(),0).unwrap();
+    /// ```
+    pub unsafe fn GetDict(&self, index: DictSlot, font_index: i32) -> Result<String> {
+        static NAME: &str = "GetDict";
+        let mut args = [Dmsoft::longVar(font_index), Dmsoft::longVar(index.index())];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = ManuallyDrop::into_inner(result.Anonymous.bstrVal);
+        Ok(result.try_into().unwrap())
+    }
+
+    /// 获取指定的字库中的字符数量.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::GetDictCount(long index)
+    /// ```
+    /// # Args
+    /// * `index:DictSlot`: 字库的序号,取值为0-99,目前最多支持100个字库
+    /// # Return
+    /// `i32`: 字库数量
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let count = dm.GetDictCount(DictSlot::default()).unwrap();
+    /// ```
+    pub unsafe fn GetDictCount(&self, index: DictSlot) -> Result<i32> {
+        static NAME: &str = "GetDictCount";
+        let mut args = [Dmsoft::longVar(index.index())];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        Ok(result.Anonymous.lVal)
+    }
+
+    /// 根据指定的文字，以及指定的系统字库信息，获取字库描述信息.
+    /// # The function prototype
+    /// ```C++
+    /// CString dmsoft::GetDictInfo(const TCHAR * str,const TCHAR * font_name,long font_size,long flag)
+    /// ```
+    /// # Args
+    /// * `str:&str`: 需要获取的字符串
+    /// * `font_name:&str`: 系统字体名,比如"宋体"
+    /// * `font_size:i32`: 系统字体尺寸，这个尺寸一定要以大漠综合工具获取的为准.如何获取尺寸看视频教程.
+    /// * `flag:FontFlags`: 字体类别 取值可以是以下值的组合,比如`FontFlags::BOLD | FontFlags::ITALIC`
+    ///     * 0: 正常字体
+    ///     * 1: 粗体
+    ///     * 2: 斜体
+    ///     * 4: 下划线
+    ///     * 8: 删除线
+    /// # Return
+    /// `String`: 返回字库信息,每个字符的字库信息用"|"来分割
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let info = dm.GetDictInfo("回收站","宋体",9,FontFlags::NORMAL).unwrap();
+    /// ```
+    pub unsafe fn GetDictInfo(
+        &self,
+        str: &str,
+        font_name: &str,
+        font_size: i32,
+        flag: FontFlags,
+    ) -> Result<String> {
+        static NAME: &str = "GetDictInfo";
+        let mut args = [
+            Dmsoft::longVar(flag.bits()),
+            Dmsoft::longVar(font_size),
+            Dmsoft::bstrVal(font_name),
+            Dmsoft::bstrVal(str),
+        ];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        let result = ManuallyDrop::into_inner(result.Anonymous.bstrVal);
+        Ok(result.try_into().unwrap())
+    }
+
+    /// 获取当前使用的字库序号(0-99)
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::GetNowDict()
+    /// ```
+    /// # Args
+    /// # Return
+    /// `DictSlot`: 字库序号(0-99), 插件返回的序号不在范围内时返回 `Error::CallFailed`
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let slot = dm.GetNowDict().unwrap();
+    /// ```
+    pub unsafe fn GetNowDict(&self) -> Result<DictSlot> {
+        static NAME: &str = "GetNowDict";
+        let result = self.Invoke(NAME, &mut [])?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
+        DictSlot::new(result.Anonymous.lVal).ok_or(Error::CallFailed(NAME))
+    }
 
+    /// 允许当前调用的对象使用全局字库。 如果你的程序中对象太多,并且每个对象都用到了同样的字库,可以考虑用全局字库,这样可以节省大量内存.
+    /// # The function prototype
+    /// ```C++
+    /// long dmsoft::EnableShareDict(long en)
+    /// ```
+    /// # Args
+    /// * `enable:bool`: `false`: 关闭 `true`: 打开
+    /// # Return
+    /// `i32`: 0: 失败 1: 成功
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let status = dm.EnableShareDict(true).unwrap();
+    /// ```
+    /// # Note
+    /// * 一旦当前对象开启了全局字库,那么所有的SetDict和UseDict都会作用于全局字库. 全局字库是所有对象共享的.
+    pub unsafe fn EnableShareDict(&self, enable: bool) -> Result<i32> {
+        static NAME: &str = "EnableShareDict";
+        let mut args = [Dmsoft::longVar(enable as i32)];
+        let result = self.Invoke(NAME, &mut args)?;
+        let result = ManuallyDrop::into_inner(result.Anonymous.Anonymous);
         Ok(result.Anonymous.lVal)
     }
 }
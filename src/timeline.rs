@@ -0,0 +1,678 @@
+//! 键鼠宏: 按顺序执行的键鼠动作序列, 回放和录制
+//!
+//! * 开启 `serde` feature 后 [`Timeline`] 实现了 `Serialize` / `Deserialize`
+//! * 开启 `json` / `toml` feature 后可以用 [`Timeline::open`] / [`Timeline::save`] 读写对应格式的文件
+//!
+//! 动作之间的间隔用 [`Action::Wait`] 表示. JSON 格式如下:
+//! ```json
+//! { "actions": [
+//!     { "type": "move_to", "x": 100, "y": 200 },
+//!     { "type": "wait", "ms": 120 },
+//!     { "type": "click", "button": "left" },
+//!     { "type": "text", "text": "你好" }
+//! ] }
+//! ```
+//! TOML 格式如下:
+//! ```toml
+//! [[actions]]
+//! type = "key_down"
+//! vk = 17
+//!
+//! [[actions]]
+//! type = "wait"
+//! ms = 30
+//! ```
+
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+#[cfg(any(feature = "json", feature = "toml"))]
+use std::{io, path::Path};
+
+#[cfg(feature = "keymap")]
+use crate::typing::TypeOptions;
+use crate::{check, click::MouseButton, Dmsoft, Error, KeyMap, Point, Result};
+
+/// 一个键鼠动作
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum Action {
+    /// `KeyDown` 按住虚拟键码
+    KeyDown {
+        /// 虚拟键码
+        vk: i32,
+    },
+    /// `KeyUp` 弹起虚拟键码
+    KeyUp {
+        /// 虚拟键码
+        vk: i32,
+    },
+    /// `KeyPress` 按下虚拟键码
+    KeyPress {
+        /// 虚拟键码
+        vk: i32,
+    },
+    /// `MoveTo` 移动鼠标
+    MoveTo {
+        /// X坐标
+        x: i32,
+        /// Y坐标
+        y: i32,
+    },
+    /// 按住鼠标键
+    ButtonDown {
+        /// 鼠标按键
+        button: MouseButton,
+    },
+    /// 弹起鼠标键
+    ButtonUp {
+        /// 鼠标按键
+        button: MouseButton,
+    },
+    /// 点击鼠标键
+    Click {
+        /// 鼠标按键
+        button: MouseButton,
+    },
+    /// `WheelUp` 滚轮向上滚
+    WheelUp,
+    /// `WheelDown` 滚轮向下滚
+    WheelDown,
+    /// 输入文本, 开启 `keymap` feature 时按 [`PlayOptions::typing`] 用 [`Dmsoft::type_text`] 输入,
+    /// 否则用 `SendString` 发送到 [`PlayOptions::hwnd`]
+    Text {
+        /// 文本
+        text: String,
+    },
+    /// 等待, 回放时按 [`PlayOptions::speed`] 缩放
+    Wait {
+        /// 毫秒
+        ms: u64,
+    },
+}
+
+/// 键鼠动作序列
+/// # Examples
+/// ```
+/// let timeline = Timeline::new()
+///     .with(Action::MoveTo { x: 100, y: 200 })
+///     .with(Action::Wait { ms: 100 })
+///     .with(Action::Click { button: MouseButton::Left });
+/// assert_eq!(timeline.duration(), Duration::from_millis(100));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timeline {
+    /// 按顺序执行的动作
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub actions: Vec<Action>,
+}
+
+impl Timeline {
+    /// 新建一个空的动作序列
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加一个动作, 返回自身
+    pub fn with(mut self, action: Action) -> Self {
+        self.push(action);
+        self
+    }
+
+    /// 添加一个动作, 连续的等待会合并为一个
+    pub fn push(&mut self, action: Action) {
+        match (self.actions.last_mut(), &action) {
+            (Some(Action::Wait { ms }), Action::Wait { ms: more }) => *ms += more,
+            _ => self.actions.push(action),
+        }
+    }
+
+    /// 所有等待的总时长
+    pub fn duration(&self) -> Duration {
+        self.actions
+            .iter()
+            .map(|action| match action {
+                Action::Wait { ms } => Duration::from_millis(*ms),
+                _ => Duration::ZERO,
+            })
+            .sum()
+    }
+}
+
+impl FromIterator<Action> for Timeline {
+    fn from_iter<T: IntoIterator<Item = Action>>(iter: T) -> Self {
+        let mut timeline = Self::new();
+        for action in iter {
+            timeline.push(action);
+        }
+        timeline
+    }
+}
+
+#[cfg(feature = "json")]
+impl Timeline {
+    /// 转换为格式化的 JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// 从 JSON 解析
+    pub fn from_json(s: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(s)
+    }
+}
+
+#[cfg(feature = "toml")]
+impl Timeline {
+    /// 转换为 TOML
+    pub fn to_toml(&self) -> std::result::Result<String, toml::ser::Error> {
+        toml::to_string(self)
+    }
+
+    /// 从 TOML 解析
+    pub fn from_toml(s: &str) -> std::result::Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+}
+
+#[cfg(any(feature = "json", feature = "toml"))]
+impl Timeline {
+    /// 读取宏文件, 按扩展名 ".json" 或者 ".toml" 选择格式
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let s = std::fs::read_to_string(path)?;
+        match extension(path) {
+            #[cfg(feature = "json")]
+            Some("json") => Self::from_json(&s).map_err(invalid_data),
+            #[cfg(feature = "toml")]
+            Some("toml") => Self::from_toml(&s).map_err(invalid_data),
+            _ => Err(unsupported(path)),
+        }
+    }
+
+    /// 保存为宏文件, 按扩展名 ".json" 或者 ".toml" 选择格式
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let s = match extension(path) {
+            #[cfg(feature = "json")]
+            Some("json") => self.to_json(),
+            #[cfg(feature = "toml")]
+            Some("toml") => self.to_toml().map_err(invalid_data)?,
+            _ => return Err(unsupported(path)),
+        };
+        std::fs::write(path, s)
+    }
+}
+
+/// 小写的扩展名
+#[cfg(any(feature = "json", feature = "toml"))]
+fn extension(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    ["json", "toml"].into_iter().find(|e| *e == ext)
+}
+
+#[cfg(any(feature = "json", feature = "toml"))]
+fn invalid_data(e: impl std::error::Error + Send + Sync + 'static) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+#[cfg(any(feature = "json", feature = "toml"))]
+fn unsupported(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("不支持的宏文件格式: {}", path.display()),
+    )
+}
+
+/// 回放选项
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayOptions {
+    /// 回放速度, 2.0表示所有等待缩短一半, 默认为1.0
+    pub speed: f64,
+    /// 回放次数, 0表示一直循环直到出错或者被取消, 默认为1
+    pub loops: u32,
+    /// 两次回放之间的间隔, 同样按`speed`缩放, 默认为0
+    pub loop_delay: Duration,
+    /// [`Action::Text`] 的输入方式, 默认为 [`TypeOptions::default`]. 字符之间的间隔不按`speed`缩放
+    #[cfg(feature = "keymap")]
+    pub typing: TypeOptions,
+    /// [`Action::Text`] 用 `SendString` 发送的窗口句柄, 0表示当前激活的窗口, 默认为0
+    #[cfg(not(feature = "keymap"))]
+    pub hwnd: i32,
+}
+
+impl Default for PlayOptions {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            loops: 1,
+            loop_delay: Duration::ZERO,
+            #[cfg(feature = "keymap")]
+            typing: TypeOptions::default(),
+            #[cfg(not(feature = "keymap"))]
+            hwnd: 0,
+        }
+    }
+}
+
+impl PlayOptions {
+    /// 按`speed`缩放的时长
+    fn scale(&self, duration: Duration) -> Duration {
+        if self.speed > 0.0 {
+            duration.div_f64(self.speed)
+        } else {
+            duration
+        }
+    }
+}
+
+/// 录制选项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordOptions {
+    /// 轮询间隔, 默认为10毫秒
+    pub interval: Duration,
+    /// 轮询的虚拟键码, 默认为 [`RECORD_KEYS`]. 每次轮询对每个键调用一次 `GetKeyState`
+    pub keys: Vec<i32>,
+    /// 鼠标移动超过这个距离(像素)才记录, 默认为1
+    pub min_move: i32,
+    /// 按下时结束录制的键, 这个键本身不会被记录, 默认为 F12(123)
+    pub stop_key: Option<i32>,
+    /// 最长录制时间, 默认不限制
+    pub max_duration: Option<Duration>,
+}
+
+impl Default for RecordOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(10),
+            keys: RECORD_KEYS.to_vec(),
+            min_move: 1,
+            stop_key: Some(123),
+            max_duration: None,
+        }
+    }
+}
+
+/// 默认录制的虚拟键码, 即大漠键码对应表中的按键: 数字, 字母, 功能键, 方向键, 编辑键, 修饰键和主键盘上的符号
+#[rustfmt::skip]
+pub const RECORD_KEYS: &[i32] = &[
+    // 0-9
+    48, 49, 50, 51, 52, 53, 54, 55, 56, 57,
+    // a-z
+    65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77,
+    78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90,
+    // f1-f12
+    112, 113, 114, 115, 116, 117, 118, 119, 120, 121, 122, 123,
+    // back, tab, enter, shift, ctrl, alt, cap, esc, space
+    8, 9, 13, 16, 17, 18, 20, 27, 32,
+    // pgup, pgdn, end, home, left, up, right, down, print, delete
+    33, 34, 35, 36, 37, 38, 39, 40, 44, 46,
+    // win, option
+    91, 93,
+    // ; = , - . / ~ [ \ ] '
+    186, 187, 188, 189, 190, 191, 192, 219, 220, 221, 222,
+];
+
+/// 录制时轮询的鼠标键
+static BUTTONS: [(i32, MouseButton); 3] = [
+    (1, MouseButton::Left),
+    (2, MouseButton::Right),
+    (4, MouseButton::Middle),
+];
+
+/// 键鼠宏辅助函数
+impl Dmsoft {
+    /// 回放动作序列, 出错或者被取消时弹起所有通过绑定按住的键, 并返回错误
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let timeline = Timeline::open("macro.json").unwrap();
+    /// dm.play(&timeline, &PlayOptions { speed: 1.5, loops: 3, ..PlayOptions::default() }).unwrap();
+    /// ```
+    pub unsafe fn play(&self, timeline: &Timeline, options: &PlayOptions) -> Result<()> {
+        let result = self.play_loops(timeline, options);
+        if result.is_err() {
+            let _ = self.release_all();
+        }
+        result
+    }
+
+    /// 按`options.loops`循环回放
+    unsafe fn play_loops(&self, timeline: &Timeline, options: &PlayOptions) -> Result<()> {
+        let mut i = 0;
+        while options.loops == 0 || i < options.loops {
+            if i > 0 {
+                self.wait(options.scale(options.loop_delay))?;
+            }
+            for action in &timeline.actions {
+                self.play_action(action, options)?;
+            }
+            i += 1;
+        }
+        Ok(())
+    }
+
+    /// 执行一个动作
+    pub unsafe fn play_action(&self, action: &Action, options: &PlayOptions) -> Result<()> {
+        match action {
+            Action::KeyDown { vk } => check(self.KeyDown(KeyMap::new("", *vk)), "KeyDown"),
+            Action::KeyUp { vk } => check(self.KeyUp(KeyMap::new("", *vk)), "KeyUp"),
+            Action::KeyPress { vk } => check(self.KeyPress(&KeyMap::new("", *vk)), "KeyPress"),
+            Action::MoveTo { x, y } => check(self.MoveTo(*x, *y), "MoveTo"),
+            Action::ButtonDown { button } => match button {
+                MouseButton::Left => check(self.LeftDown(), "LeftDown"),
+                MouseButton::Right => check(self.RightDown(), "RightDown"),
+                MouseButton::Middle => check(self.MiddleDown(), "MiddleDown"),
+            },
+            Action::ButtonUp { button } => match button {
+                MouseButton::Left => check(self.LeftUp(), "LeftUp"),
+                MouseButton::Right => check(self.RightUp(), "RightUp"),
+                MouseButton::Middle => check(self.MiddleUp(), "MiddleUp"),
+            },
+            Action::Click { button } => match button {
+                MouseButton::Left => check(self.LeftClick(), "LeftClick"),
+                MouseButton::Right => check(self.RightClick(), "RightClick"),
+                MouseButton::Middle => check(self.MiddleClick(), "MiddleClick"),
+            },
+            Action::WheelUp => check(self.WheelUp(), "WheelUp"),
+            Action::WheelDown => check(self.WheelDown(), "WheelDown"),
+            #[cfg(feature = "keymap")]
+            Action::Text { text } => self.type_text(text, &options.typing),
+            #[cfg(not(feature = "keymap"))]
+            Action::Text { text } => check(self.SendString(options.hwnd, text), "SendString"),
+            Action::Wait { ms } => self.wait(options.scale(Duration::from_millis(*ms))),
+        }
+    }
+
+    /// 轮询 `GetKeyState` 和 `GetCursorPos` 录制用户的操作, 直到按下结束键, 达到最长录制时间或者被取消
+    ///
+    /// 第一个动作为移动到开始时的鼠标位置. 结束时仍然按住的键会补上弹起动作.
+    /// 滚轮无法轮询, 不会被录制. 每次轮询会调用 `GetKeyState` 检查每个键, 键越多轮询越慢
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// println!("开始录制, 按 F12 结束");
+    /// let timeline = dm.record(&RecordOptions::default()).unwrap();
+    /// timeline.save("macro.toml").unwrap();
+    /// ```
+    pub unsafe fn record(&self, options: &RecordOptions) -> Result<Timeline> {
+        let start = Instant::now();
+        let mut last = start;
+        let mut timeline = Timeline::new();
+        let mut push = |timeline: &mut Timeline, action: Action| {
+            let ms = last.elapsed().as_millis() as u64;
+            if ms > 0 {
+                timeline.push(Action::Wait { ms });
+                last = Instant::now();
+            }
+            timeline.push(action);
+        };
+
+        let mut pos = self.cursor_pos()?;
+        push(&mut timeline, Action::MoveTo { x: pos.x, y: pos.y });
+        let keys: Vec<i32> = options
+            .keys
+            .iter()
+            .copied()
+            .filter(|vk| Some(*vk) != options.stop_key && !BUTTONS.iter().any(|(b, _)| b == vk))
+            .collect();
+        let mut pressed: HashSet<i32> = HashSet::new();
+        loop {
+            if options
+                .max_duration
+                .is_some_and(|max| start.elapsed() >= max)
+            {
+                break;
+            }
+            if let Some(stop) = options.stop_key {
+                if self.GetKeyState(KeyMap::new("", stop))? != 0 {
+                    break;
+                }
+            }
+            let now = self.cursor_pos()?;
+            if (now.x - pos.x).abs().max((now.y - pos.y).abs()) >= options.min_move.max(1) {
+                pos = now;
+                push(&mut timeline, Action::MoveTo { x: pos.x, y: pos.y });
+            }
+            for &(vk, button) in &BUTTONS {
+                let down = self.GetKeyState(KeyMap::new("", vk))? != 0;
+                if down != pressed.contains(&vk) {
+                    let action = if down {
+                        Action::ButtonDown { button }
+                    } else {
+                        Action::ButtonUp { button }
+                    };
+                    push(&mut timeline, action);
+                    toggle(&mut pressed, vk, down);
+                }
+            }
+            for &vk in &keys {
+                let down = self.GetKeyState(KeyMap::new("", vk))? != 0;
+                if down != pressed.contains(&vk) {
+                    let action = if down {
+                        Action::KeyDown { vk }
+                    } else {
+                        Action::KeyUp { vk }
+                    };
+                    push(&mut timeline, action);
+                    toggle(&mut pressed, vk, down);
+                }
+            }
+            match self.wait(options.interval) {
+                Err(Error::Cancelled) => break,
+                result => result?,
+            }
+        }
+
+        let mut pressed: Vec<i32> = pressed.into_iter().collect();
+        pressed.sort();
+        for vk in pressed {
+            match BUTTONS.iter().find(|(b, _)| *b == vk) {
+                Some(&(_, button)) => push(&mut timeline, Action::ButtonUp { button }),
+                None => push(&mut timeline, Action::KeyUp { vk }),
+            }
+        }
+        Ok(timeline)
+    }
+
+    /// `GetCursorPos`, 失败时转换为 [`Error::CallFailed`]
+    unsafe fn cursor_pos(&self) -> Result<Point> {
+        self.GetCursorPos()?
+            .ok_or(Error::CallFailed("GetCursorPos"))
+    }
+}
+
+/// 更新按住的键
+fn toggle(pressed: &mut HashSet<i32>, vk: i32, down: bool) {
+    if down {
+        pressed.insert(vk);
+    } else {
+        pressed.remove(&vk);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 每种动作各一个
+    fn every_action() -> Timeline {
+        Timeline {
+            actions: vec![
+                Action::MoveTo { x: 100, y: -20 },
+                Action::KeyDown { vk: 17 },
+                Action::KeyPress { vk: 65 },
+                Action::KeyUp { vk: 17 },
+                Action::ButtonDown {
+                    button: MouseButton::Left,
+                },
+                Action::ButtonUp {
+                    button: MouseButton::Left,
+                },
+                Action::Click {
+                    button: MouseButton::Right,
+                },
+                Action::Click {
+                    button: MouseButton::Middle,
+                },
+                Action::WheelUp,
+                Action::WheelDown,
+                Action::Text {
+                    text: "你好, \"world\"\n".to_owned(),
+                },
+                Action::Wait { ms: 120 },
+            ],
+        }
+    }
+
+    #[test]
+    fn push_merges_waits() {
+        let mut timeline = Timeline::new();
+        timeline.push(Action::Wait { ms: 10 });
+        timeline.push(Action::Wait { ms: 20 });
+        timeline.push(Action::WheelUp);
+        timeline.push(Action::Wait { ms: 5 });
+        timeline.push(Action::Wait { ms: 0 });
+        assert_eq!(
+            timeline.actions,
+            [
+                Action::Wait { ms: 30 },
+                Action::WheelUp,
+                Action::Wait { ms: 5 }
+            ]
+        );
+        assert_eq!(timeline.duration(), Duration::from_millis(35));
+
+        let collected: Timeline = [
+            Action::Wait { ms: 1 },
+            Action::Wait { ms: 2 },
+            Action::KeyPress { vk: 13 },
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            collected.actions,
+            [Action::Wait { ms: 3 }, Action::KeyPress { vk: 13 }]
+        );
+        // `with` 同样合并
+        let timeline = Timeline::new()
+            .with(Action::Wait { ms: 1 })
+            .with(Action::Wait { ms: 1 });
+        assert_eq!(timeline.actions, [Action::Wait { ms: 2 }]);
+    }
+
+    #[test]
+    fn default_record_keys() {
+        let keys = RecordOptions::default().keys;
+        let unique: HashSet<i32> = keys.iter().copied().collect();
+        assert_eq!(unique.len(), keys.len());
+        assert!(keys.len() < 100);
+        for vk in [16, 17, 18, 65, 90, 48, 57, 112, 123, 37, 40, 32, 13, 27] {
+            assert!(keys.contains(&vk), "{vk}");
+        }
+        // 鼠标键单独轮询
+        assert!(BUTTONS.iter().all(|(vk, _)| !keys.contains(vk)));
+    }
+
+    #[cfg(feature = "keymap")]
+    #[test]
+    fn record_keys_are_dm_keys() {
+        let mut keys = RECORD_KEYS.to_vec();
+        keys.sort();
+        let mut named: Vec<i32> = KeyMap::ALL
+            .iter()
+            .filter(|key| key.has_key_str())
+            .map(|key| key.get_id())
+            .collect();
+        named.sort();
+        assert_eq!(keys, named);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_round_trip() {
+        let timeline = every_action();
+        let json = timeline.to_json();
+        assert_eq!(Timeline::from_json(&json).unwrap(), timeline);
+
+        let parsed = Timeline::from_json(
+            r#"{ "actions": [
+                { "type": "move_to", "x": 100, "y": 200 },
+                { "type": "wait", "ms": 120 },
+                { "type": "click", "button": "left" },
+                { "type": "wheel_down" },
+                { "type": "text", "text": "你好" }
+            ] }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.actions,
+            [
+                Action::MoveTo { x: 100, y: 200 },
+                Action::Wait { ms: 120 },
+                Action::Click {
+                    button: MouseButton::Left
+                },
+                Action::WheelDown,
+                Action::Text {
+                    text: "你好".to_owned()
+                },
+            ]
+        );
+        assert_eq!(Timeline::from_json("{}").unwrap(), Timeline::new());
+        assert!(Timeline::from_json(r#"{ "actions": [{ "type": "jump" }] }"#).is_err());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_round_trip() {
+        let timeline = every_action();
+        let toml = timeline.to_toml().unwrap();
+        assert_eq!(Timeline::from_toml(&toml).unwrap(), timeline);
+
+        let parsed = Timeline::from_toml(
+            r#"
+            [[actions]]
+            type = "key_down"
+            vk = 17
+
+            [[actions]]
+            type = "wait"
+            ms = 30
+
+            [[actions]]
+            type = "wheel_up"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.actions,
+            [
+                Action::KeyDown { vk: 17 },
+                Action::Wait { ms: 30 },
+                Action::WheelUp
+            ]
+        );
+        assert_eq!(Timeline::from_toml("").unwrap(), Timeline::new());
+    }
+
+    #[cfg(all(feature = "json", feature = "toml"))]
+    #[test]
+    fn files_by_extension() {
+        let dir = std::env::temp_dir().join(format!("dm_timeline_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let timeline = every_action();
+        for name in ["macro.json", "macro.TOML"] {
+            let path = dir.join(name);
+            timeline.save(&path).unwrap();
+            assert_eq!(Timeline::open(&path).unwrap(), timeline, "{name}");
+        }
+        let err = timeline.save(dir.join("macro.txt")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,402 @@
+//! 字库训练: 找出区域内字库无法识别的字, 展示给用户标注, 把标注的结果添加到字库文件
+//!
+//! 未识别的字可以显示为字符画([`Unknown`] 实现了 `Display`), 开启 `png` feature 后也可以保存为 PNG 图片.
+//! 标注通过回调函数完成, [`stdin_label`] 是一个在终端中逐个输入的实现.
+
+use std::{
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{
+    bmp::Bmp,
+    check,
+    color::ColorSpec,
+    dict::DictSlot,
+    dict_file::{Dict, DictEntry, Glyph},
+    ocr::Recognizer,
+    Dmsoft, Error, Point, Result,
+};
+
+/// 字库无法识别的一个字
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Unknown {
+    /// 左上角的坐标
+    pub pos: Point,
+    /// 点阵
+    pub glyph: Glyph,
+}
+
+/// 显示为字符画, `'#'` 表示有点
+impl std::fmt::Display for Unknown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "({}, {}) {}x{}",
+            self.pos.x,
+            self.pos.y,
+            self.glyph.width(),
+            self.glyph.height()
+        )?;
+        self.glyph.fmt(f)
+    }
+}
+
+#[cfg(feature = "png")]
+impl Unknown {
+    /// 保存为黑底白字的 PNG 图片, 每个点放大为`scale`x`scale`个像素
+    pub fn save_png(&self, path: impl AsRef<Path>, scale: u32) -> io::Result<()> {
+        let scale = scale.max(1);
+        let (width, height) = (self.glyph.width() * scale, self.glyph.height() * scale);
+        let data: Vec<u8> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                if self.glyph.get(x / scale, y / scale) {
+                    255
+                } else {
+                    0
+                }
+            })
+            .collect();
+        let file = io::BufWriter::new(std::fs::File::create(path)?);
+        let mut encoder = png::Encoder::new(file, width, height);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&data))
+            .map_err(io::Error::other)
+    }
+}
+
+/// 字库训练器, 持有正在训练的字库
+///
+/// 绑定了字库序号([`Trainer::with_slot`])时, 可以用 [`Dmsoft::save_trained`] 保存并让插件重新加载这个字库
+/// # Examples
+/// ```
+/// let dm = Dmsoft::new();
+/// let color: ColorSpec = "ffffff-000000".parse().unwrap();
+/// let mut trainer = Trainer::open("main.txt").unwrap();
+/// let added = dm.train_dict(&mut trainer, 0, 0, 800, 600, &color, 1.0, trainer::stdin_label).unwrap();
+/// trainer.save().unwrap();
+///
+/// // 训练插件正在使用的字库, 保存后立即生效
+/// let mut trainer = dm.now_dict_trainer("main.txt").unwrap();
+/// dm.train_dict(&mut trainer, 0, 0, 800, 600, &color, 1.0, trainer::stdin_label).unwrap();
+/// dm.save_trained(&trainer).unwrap();
+/// let s = dm.Ocr(0,0,800,600,"ffffff-000000",1.0).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Trainer {
+    dict: Dict,
+    path: Option<PathBuf>,
+    slot: Option<DictSlot>,
+}
+
+impl Trainer {
+    /// 训练内存中的字库, 需要用 [`Trainer::save_as`] 保存
+    pub fn new(dict: Dict) -> Self {
+        Self {
+            dict,
+            path: None,
+            slot: None,
+        }
+    }
+
+    /// 打开字库文件进行训练, 文件不存在时从空字库开始
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let dict = if path.exists() {
+            Dict::open(&path)?
+        } else {
+            Dict::new()
+        };
+        Ok(Self {
+            dict,
+            path: Some(path),
+            slot: None,
+        })
+    }
+
+    /// 绑定插件中加载这个字库的序号, 返回自身
+    pub fn with_slot(mut self, slot: DictSlot) -> Self {
+        self.slot = Some(slot);
+        self
+    }
+
+    /// 绑定的字库序号
+    pub fn slot(&self) -> Option<DictSlot> {
+        self.slot
+    }
+
+    /// 打开的字库文件
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// 正在训练的字库
+    pub fn dict(&self) -> &Dict {
+        &self.dict
+    }
+
+    /// 结束训练, 返回字库
+    pub fn into_dict(self) -> Dict {
+        self.dict
+    }
+
+    /// 找出区域内字库无法识别的字, 参数同 [`Recognizer::recognize`]
+    pub fn unknowns(
+        &self,
+        frame: &Bmp,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        color: &ColorSpec,
+        sim: f64,
+    ) -> Vec<Unknown> {
+        Recognizer::new(&self.dict)
+            .unknown_glyphs(frame, (x1, y1, x2, y2), color, sim)
+            .into_iter()
+            .map(|(pos, glyph)| Unknown { pos, glyph })
+            .collect()
+    }
+
+    /// 逐个标注区域内无法识别的字, 并添加到字库
+    ///
+    /// `label` 返回 `None` 时跳过这个字. 同一个点阵只会询问一次. 返回添加的条目数量
+    pub fn train(
+        &mut self,
+        frame: &Bmp,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        color: &ColorSpec,
+        sim: f64,
+        mut label: impl FnMut(&Unknown) -> Option<String>,
+    ) -> usize {
+        let mut asked: Vec<Glyph> = Vec::new();
+        let mut added = 0;
+        for unknown in self.unknowns(frame, x1, y1, x2, y2, color, sim) {
+            if asked.contains(&unknown.glyph) {
+                continue;
+            }
+            asked.push(unknown.glyph.clone());
+            if let Some(text) = label(&unknown).filter(|text| !text.is_empty()) {
+                self.dict.add(DictEntry::new(text, &unknown.glyph));
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// 保存到打开的字库文件, 通过 [`Trainer::new`] 创建时返回 `InvalidInput` 错误
+    pub fn save(&self) -> io::Result<()> {
+        match &self.path {
+            Some(path) => self.dict.save(path),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "没有打开的字库文件",
+            )),
+        }
+    }
+
+    /// 保存到`path`
+    pub fn save_as(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.dict.save(path)
+    }
+}
+
+/// 在终端中显示字符画并读取一行作为标注, 输入为空时跳过
+pub fn stdin_label(unknown: &Unknown) -> Option<String> {
+    print!("{}输入这个字(直接回车跳过): ", unknown);
+    io::stdout().flush().ok()?;
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line).ok()?;
+    let line = line.trim();
+    (!line.is_empty()).then(|| line.to_owned())
+}
+
+/// 字库训练辅助函数
+impl Dmsoft {
+    /// 用 `Capture` 截取屏幕区域(x1,y1,x2,y2), 标注其中字库无法识别的字, 并添加到训练器的字库
+    ///
+    /// 标注时 [`Unknown::pos`] 为屏幕坐标. 返回添加的条目数量, 读取截图失败时返回 [`Error::Io`]
+    pub unsafe fn train_dict(
+        &self,
+        trainer: &mut Trainer,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        color: &ColorSpec,
+        sim: f64,
+        mut label: impl FnMut(&Unknown) -> Option<String>,
+    ) -> Result<usize> {
+        /// 每次截图使用不同的临时文件, 多个线程同时训练时不会互相覆盖
+        static CAPTURES: AtomicUsize = AtomicUsize::new(0);
+        let file = std::env::temp_dir().join(format!(
+            "dm_trainer_{}_{}.bmp",
+            std::process::id(),
+            CAPTURES.fetch_add(1, Ordering::Relaxed)
+        ));
+        check(
+            self.Capture(x1, y1, x2, y2, &file.to_string_lossy()),
+            "Capture",
+        )?;
+        let frame = Bmp::open(&file);
+        let _ = std::fs::remove_file(&file);
+        let frame = frame.map_err(Error::Io)?;
+        let (width, height) = (frame.width() as i32, frame.height() as i32);
+        Ok(
+            trainer.train(&frame, 0, 0, width, height, color, sim, |unknown| {
+                label(&Unknown {
+                    pos: Point::new(unknown.pos.x + x1, unknown.pos.y + y1),
+                    glyph: unknown.glyph.clone(),
+                })
+            }),
+        )
+    }
+
+    /// 打开`path`训练插件当前使用的字库, 训练器绑定到 `GetNowDict` 返回的序号
+    ///
+    /// `path` 应该是这个序号用 `SetDict` 加载的字库文件, 和 `SetDict` 一样, 相对路径相对于 `GetPath` 的全局路径.
+    /// 文件不存在时返回 [`Error::FileNotFound`], 避免保存时用只有新字的字库覆盖插件中的字库
+    pub unsafe fn now_dict_trainer(&self, path: impl Into<PathBuf>) -> Result<Trainer> {
+        let path = path.into();
+        let path = if path.is_relative() {
+            Path::new(&self.GetPath()?).join(path)
+        } else {
+            path
+        };
+        if !path.is_file() {
+            return Err(Error::FileNotFound(path));
+        }
+        let slot = self.GetNowDict()?;
+        Ok(Trainer::open(path).map_err(Error::Io)?.with_slot(slot))
+    }
+
+    /// 保存训练器的字库文件, 训练器绑定了字库序号时再用 `SetDict` 重新加载, 之后的识字立即使用新的字库
+    ///
+    /// 保存失败时返回 [`Error::Io`], 重新加载失败时返回 [`Error::CallFailed`]
+    pub unsafe fn save_trained(&self, trainer: &Trainer) -> Result<()> {
+        trainer.save().map_err(Error::Io)?;
+        let (Some(slot), Some(path)) = (trainer.slot(), trainer.path()) else {
+            return Ok(());
+        };
+        // 训练器的相对路径相对于当前目录, 插件却按 `SetPath` 的全局路径解析, 这里传入绝对路径
+        let path = std::path::absolute(path).map_err(Error::Io)?;
+        check(self.SetDict(slot, &path.to_string_lossy()), "SetDict")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    /// 去掉 "6" "7" "9" 的测试字库, 返回字库和去掉的条目
+    fn partial_dict() -> (Dict, Vec<DictEntry>) {
+        let full = Dict::open(fixtures::path("ocr", "dict.txt")).unwrap();
+        let mut dict = full.clone();
+        let removed = ["6", "7", "9"]
+            .map(|text| full.find(text).unwrap().clone())
+            .to_vec();
+        for entry in &removed {
+            assert_eq!(dict.remove(&entry.text), 1);
+        }
+        (dict, removed)
+    }
+
+    #[test]
+    fn unknowns() {
+        let frame = fixtures::bmp("ocr", "text.bmp");
+        let color = "f8f8f8-080808".parse().unwrap();
+        let (dict, removed) = partial_dict();
+        let trainer = Trainer::new(dict);
+        // "7" 和 "8" 连在一起, "8" 被识别后剩下的部分是 "7"
+        let unknowns = trainer.unknowns(&frame, 40, 0, 120, 12, &color, 1.0);
+        let found: Vec<(Point, Glyph)> = unknowns.into_iter().map(|u| (u.pos, u.glyph)).collect();
+        let expected: Vec<(Point, Glyph)> = [(48, 2), (56, 2), (72, 2)]
+            .into_iter()
+            .zip(&removed)
+            .map(|((x, y), entry)| (Point::new(x, y), entry.glyph().unwrap()))
+            .collect();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn train_adds_entries() {
+        let frame = fixtures::bmp("ocr", "text.bmp");
+        let color = "f8f8f8-080808".parse().unwrap();
+        let (dict, removed) = partial_dict();
+        let mut trainer = Trainer::new(dict);
+        let mut asked = Vec::new();
+        let added = trainer.train(&frame, 40, 0, 120, 12, &color, 1.0, |unknown| {
+            asked.push(unknown.pos);
+            let entry = removed
+                .iter()
+                .find(|e| e.glyph().unwrap() == unknown.glyph)?;
+            // 跳过 "9"
+            (entry.text != "9").then(|| entry.text.clone())
+        });
+        assert_eq!(asked.len(), 3);
+        assert_eq!(added, 2);
+        let positions: Vec<Point> = trainer
+            .unknowns(&frame, 40, 0, 120, 12, &color, 1.0)
+            .into_iter()
+            .map(|u| u.pos)
+            .collect();
+        assert_eq!(positions, [Point::new(72, 2)]);
+        let s = Recognizer::new(trainer.dict()).ocr(&frame, 40, 0, 120, 12, &color, 1.0);
+        assert_eq!(s, "678");
+        assert_eq!(trainer.dict().find("6").unwrap().bits, removed[0].bits);
+    }
+
+    #[test]
+    fn same_glyph_asked_once() {
+        let mut frame = Bmp::new(20, 6);
+        let white = crate::color::Rgb::new(0xff, 0xff, 0xff);
+        for left in [2, 12] {
+            for (x, y) in [(0, 0), (1, 0), (0, 1), (0, 2), (1, 2)] {
+                frame.set_pixel(left + x, 1 + y, white);
+            }
+        }
+        let color = "ffffff-000000".parse().unwrap();
+        let mut trainer = Trainer::new(Dict::new());
+        let mut asked = 0;
+        let added = trainer.train(&frame, 0, 0, 20, 6, &color, 1.0, |_| {
+            asked += 1;
+            Some("c".to_owned())
+        });
+        assert_eq!((asked, added), (1, 1));
+        assert!(trainer
+            .unknowns(&frame, 0, 0, 20, 6, &color, 1.0)
+            .is_empty());
+        assert_eq!(
+            Recognizer::new(trainer.dict()).ocr(&frame, 0, 0, 20, 6, &color, 1.0),
+            "cc"
+        );
+
+        // 空标注同样跳过
+        let mut trainer = Trainer::new(Dict::new());
+        assert_eq!(
+            trainer.train(&frame, 0, 0, 20, 6, &color, 1.0, |_| Some(String::new())),
+            0
+        );
+        assert!(trainer.dict().is_empty());
+    }
+
+    #[test]
+    fn save_without_path() {
+        let trainer = Trainer::new(Dict::new());
+        assert_eq!(
+            trainer.save().unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+        assert_eq!(trainer.path(), None);
+        assert_eq!(trainer.slot(), None);
+    }
+}
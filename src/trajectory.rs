@@ -0,0 +1,448 @@
+//! 模拟人手移动鼠标: 生成带时间的轨迹点, 再通过 `MoveTo` / `MoveR` 依次移动
+//!
+//! * 路径为三次贝塞尔曲线, 控制点在起点和终点连线两侧随机偏移
+//! * 速度按缓动函数变化, 默认为先加速后减速
+//! * 距离较远时先越过目标一小段, 再修正回来
+//! * 每个点叠加微小的抖动, 终点不抖动
+//! * 总时长按菲茨定律(Fitts' law)由距离和目标大小计算
+//!
+//! 随机数由种子决定, 相同的种子和参数总是生成相同的轨迹.
+
+use std::time::{Duration, Instant};
+
+use crate::{check, Dmsoft, Error, Point, Result};
+
+/// 可以指定种子的伪随机数生成器(splitmix64)
+#[derive(Debug, Clone)]
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// [0, 1) 之间的随机数
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// [min, max) 之间的随机数
+    pub(crate) fn range(&mut self, min: f64, max: f64) -> f64 {
+        min + (max - min) * self.next_f64()
+    }
+}
+
+/// 缓动函数, 决定移动过程中的速度变化
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Easing {
+    /// 匀速
+    Linear,
+    /// 逐渐加速
+    EaseIn,
+    /// 逐渐减速
+    EaseOut,
+    /// 先加速后减速
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    /// 把时间进度`t`(0.0-1.0)转换为路径进度(0.0-1.0)
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// 轨迹上的一个点
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimedPoint {
+    /// 坐标
+    pub pos: Point,
+    /// 从开始移动算起, 到达这个点的时间
+    pub at: Duration,
+}
+
+/// 按轨迹移动鼠标使用的函数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MoveMethod {
+    /// `MoveTo` 移动到每个点的绝对坐标
+    #[default]
+    MoveTo,
+    /// `MoveR` 按相邻两个点的差值相对移动, 适合只接受相对移动的窗口
+    MoveR,
+}
+
+/// 轨迹生成器
+/// # Examples
+/// ```
+/// let mut traj = Trajectory::new(42).with_jitter(1.0);
+/// let path = traj.generate(Point::new(0, 0), Point::new(500, 300));
+/// assert_eq!(path.last().unwrap().pos, Point::new(500, 300));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Trajectory {
+    rng: Rng,
+    easing: Easing,
+    curvature: f64,
+    overshoot: f64,
+    overshoot_distance: f64,
+    jitter: f64,
+    fitts_a: Duration,
+    fitts_b: Duration,
+    target_width: f64,
+    step: Duration,
+}
+
+impl Trajectory {
+    /// 用`seed`新建一个轨迹生成器
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: Rng::new(seed),
+            easing: Easing::default(),
+            curvature: 0.25,
+            overshoot: 0.06,
+            overshoot_distance: 300.0,
+            jitter: 0.6,
+            fitts_a: Duration::from_millis(80),
+            fitts_b: Duration::from_millis(120),
+            target_width: 10.0,
+            step: Duration::from_millis(10),
+        }
+    }
+
+    /// 设置缓动函数, 默认为 [`Easing::EaseInOut`]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// 设置弯曲程度, 控制点最多偏离连线 距离*`curvature`, 默认为0.25. 为0时走直线
+    pub fn with_curvature(mut self, curvature: f64) -> Self {
+        self.curvature = curvature.max(0.0);
+        self
+    }
+
+    /// 设置越过目标的比例和触发越过的最小距离, 默认为0.06和300. 比例为0时不越过
+    pub fn with_overshoot(mut self, ratio: f64, min_distance: f64) -> Self {
+        self.overshoot = ratio.max(0.0);
+        self.overshoot_distance = min_distance;
+        self
+    }
+
+    /// 设置抖动的最大像素, 默认为0.6. 为0时不抖动, 距离小于100像素时按比例减小
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.max(0.0);
+        self
+    }
+
+    /// 设置菲茨定律的参数: 时长 = a + b * log2(距离 / 目标宽度 + 1), 默认为80毫秒, 120毫秒和10像素
+    pub fn with_fitts(mut self, a: Duration, b: Duration, target_width: f64) -> Self {
+        self.fitts_a = a;
+        self.fitts_b = b;
+        self.target_width = target_width.max(1.0);
+        self
+    }
+
+    /// 设置采样间隔, 默认为10毫秒
+    pub fn with_step(mut self, step: Duration) -> Self {
+        self.step = step.max(Duration::from_millis(1));
+        self
+    }
+
+    /// 按菲茨定律计算移动`distance`像素需要的时长
+    pub fn duration(&self, distance: f64) -> Duration {
+        let index = (distance.max(0.0) / self.target_width + 1.0).log2();
+        self.fitts_a + self.fitts_b.mul_f64(index)
+    }
+
+    /// 生成从`from`到`to`的轨迹, 第一个点为`from`(时间为0), 最后一个点为`to`
+    ///
+    /// 每次生成都会消耗随机数, 同一个生成器连续生成的轨迹各不相同, 但整个序列由种子决定
+    pub fn generate(&mut self, from: Point, to: Point) -> Vec<TimedPoint> {
+        let start = (from.x as f64, from.y as f64);
+        let end = (to.x as f64, to.y as f64);
+        let distance = (end.0 - start.0).hypot(end.1 - start.1);
+        let mut path = vec![TimedPoint {
+            pos: from,
+            at: Duration::ZERO,
+        }];
+        if distance < 1.0 {
+            if from != to {
+                path.push(TimedPoint {
+                    pos: to,
+                    at: self.step,
+                });
+            }
+            return path;
+        }
+        let total = self.duration(distance);
+        if self.overshoot > 0.0 && distance >= self.overshoot_distance {
+            let (dx, dy) = ((end.0 - start.0) / distance, (end.1 - start.1) / distance);
+            let over = distance * self.overshoot * self.rng.range(0.5, 1.0);
+            let side = self.rng.range(-0.3, 0.3) * over;
+            let overshot = (end.0 + dx * over - dy * side, end.1 + dy * over + dx * side);
+            let main = total.mul_f64(0.85);
+            self.segment(&mut path, start, overshot, Duration::ZERO, main);
+            self.segment(&mut path, overshot, end, main, total - main);
+        } else {
+            self.segment(&mut path, start, end, Duration::ZERO, total);
+        }
+        if let Some(last) = path.last_mut() {
+            last.pos = to;
+        }
+        path
+    }
+
+    /// 生成一段贝塞尔曲线, 追加到`path`, 不包含起点
+    fn segment(
+        &mut self,
+        path: &mut Vec<TimedPoint>,
+        start: (f64, f64),
+        end: (f64, f64),
+        offset: Duration,
+        duration: Duration,
+    ) {
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let distance = dx.hypot(dy).max(1.0);
+        let (nx, ny) = (-dy / distance, dx / distance);
+        let mut control = |t: f64| {
+            let bend = self.rng.range(-1.0, 1.0) * distance * self.curvature;
+            (start.0 + dx * t + nx * bend, start.1 + dy * t + ny * bend)
+        };
+        let (c1, c2) = (control(1.0 / 3.0), control(2.0 / 3.0));
+        // 距离较短时减小抖动, 避免在原地来回跳动
+        let jitter = self.jitter * (distance / 100.0).min(1.0);
+        let steps = (duration.as_secs_f64() / self.step.as_secs_f64())
+            .ceil()
+            .max(1.0) as u32;
+        for i in 1..=steps {
+            let t = i as f64 / steps as f64;
+            let u = self.easing.apply(t);
+            let (x, y) = bezier(start, c1, c2, end, u);
+            let (jx, jy) = if i < steps {
+                (
+                    self.rng.range(-jitter, jitter),
+                    self.rng.range(-jitter, jitter),
+                )
+            } else {
+                (0.0, 0.0)
+            };
+            let pos = Point::new((x + jx).round() as i32, (y + jy).round() as i32);
+            let at = offset + duration.mul_f64(t);
+            match path.last_mut() {
+                Some(last) if last.pos == pos && i < steps => {}
+                Some(last) if last.pos == pos => last.at = at,
+                _ => path.push(TimedPoint { pos, at }),
+            }
+        }
+    }
+}
+
+/// 三次贝塞尔曲线上参数为`t`的点
+fn bezier(p0: (f64, f64), p1: (f64, f64), p2: (f64, f64), p3: (f64, f64), t: f64) -> (f64, f64) {
+    let s = 1.0 - t;
+    let (a, b, c, d) = (s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t);
+    (
+        a * p0.0 + b * p1.0 + c * p2.0 + d * p3.0,
+        a * p0.1 + b * p1.1 + c * p2.1 + d * p3.1,
+    )
+}
+
+/// 按轨迹移动时每次调用的时间和参数: `MoveTo` 为绝对坐标, `MoveR` 为和上一个点的差值
+fn moves(path: &[TimedPoint], method: MoveMethod) -> impl Iterator<Item = (Duration, Point)> + '_ {
+    path.windows(2).map(move |pair| {
+        let (prev, point) = (pair[0], pair[1]);
+        let pos = match method {
+            MoveMethod::MoveTo => point.pos,
+            MoveMethod::MoveR => Point::new(point.pos.x - prev.pos.x, point.pos.y - prev.pos.y),
+        };
+        (point.at, pos)
+    })
+}
+
+/// 模拟人手移动鼠标辅助函数
+impl Dmsoft {
+    /// 按轨迹移动鼠标, 在每个点的时间到达时移动到这个点
+    ///
+    /// 轨迹的第一个点视为当前位置, 不会移动. 使用 [`MoveMethod::MoveR`] 时按相邻两个点的差值相对移动
+    pub unsafe fn move_along(&self, path: &[TimedPoint], method: MoveMethod) -> Result<()> {
+        let start = Instant::now();
+        for (at, pos) in moves(path, method) {
+            if let Some(wait) = at.checked_sub(start.elapsed()) {
+                self.wait(wait)?;
+            }
+            match method {
+                MoveMethod::MoveTo => check(self.MoveTo(pos.x, pos.y), "MoveTo")?,
+                MoveMethod::MoveR => check(self.MoveR(pos.x, pos.y), "MoveR")?,
+            }
+        }
+        Ok(())
+    }
+
+    /// 用 `GetCursorPos` 获取当前位置, 按`trajectory`生成的轨迹用 `MoveTo` 移动到`to`
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// let mut traj = Trajectory::new(7);
+    /// dm.human_move_to(Point::new(300, 200), &mut traj).unwrap();
+    /// ```
+    pub unsafe fn human_move_to(&self, to: Point, trajectory: &mut Trajectory) -> Result<()> {
+        let from = self
+            .GetCursorPos()?
+            .ok_or(Error::CallFailed("GetCursorPos"))?;
+        self.move_along(&trajectory.generate(from, to), MoveMethod::MoveTo)
+    }
+
+    /// 按`trajectory`生成的轨迹用 `MoveR` 相对移动(rx, ry)
+    pub unsafe fn human_move_r(&self, rx: i32, ry: i32, trajectory: &mut Trajectory) -> Result<()> {
+        let path = trajectory.generate(Point::new(0, 0), Point::new(rx, ry));
+        self.move_along(&path, MoveMethod::MoveR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FROM: Point = Point::new(10, 20);
+
+    /// 检查轨迹的起点, 终点和时间
+    fn check(path: &[TimedPoint], from: Point, to: Point) {
+        assert_eq!(
+            path[0],
+            TimedPoint {
+                pos: from,
+                at: Duration::ZERO
+            }
+        );
+        assert_eq!(path.last().unwrap().pos, to);
+        assert!(
+            path.windows(2).all(|pair| pair[0].at <= pair[1].at),
+            "{path:?}"
+        );
+    }
+
+    #[test]
+    fn same_seed_same_path() {
+        let to = Point::new(500, 300);
+        let a = Trajectory::new(42).generate(FROM, to);
+        let b = Trajectory::new(42).generate(FROM, to);
+        assert_eq!(a, b);
+        assert_ne!(a, Trajectory::new(43).generate(FROM, to));
+
+        // 同一个生成器连续生成的轨迹不同, 但序列由种子决定
+        let mut traj = Trajectory::new(42);
+        let (first, second) = (traj.generate(FROM, to), traj.generate(FROM, to));
+        assert_eq!(first, a);
+        assert_ne!(first, second);
+        let mut traj = Trajectory::new(42);
+        traj.generate(FROM, to);
+        assert_eq!(traj.generate(FROM, to), second);
+    }
+
+    #[test]
+    fn endpoints_and_time() {
+        for seed in 0..50 {
+            let mut traj = Trajectory::new(seed).with_jitter(2.0);
+            for to in [
+                Point::new(11, 20),
+                Point::new(60, 80),
+                Point::new(-400, 900),
+                Point::new(1920, 20),
+            ] {
+                let path = traj.generate(FROM, to);
+                check(&path, FROM, to);
+                let distance = ((to.x - FROM.x) as f64).hypot((to.y - FROM.y) as f64);
+                let end = path.last().unwrap().at;
+                assert!(
+                    end <= traj.duration(distance) + Duration::from_nanos(1),
+                    "{end:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn short_distances() {
+        let mut traj = Trajectory::new(1);
+        assert_eq!(
+            traj.generate(FROM, FROM),
+            [TimedPoint {
+                pos: FROM,
+                at: Duration::ZERO
+            }]
+        );
+        // 对角相邻的点距离大于1, 仍然生成轨迹
+        for to in [Point::new(11, 20), Point::new(10, 21), Point::new(11, 21)] {
+            let path = traj.generate(FROM, to);
+            check(&path, FROM, to);
+            assert!(path.len() >= 2);
+        }
+    }
+
+    #[test]
+    fn overshoot() {
+        let to = Point::new(1010, 20);
+        let past = |path: &[TimedPoint]| path.iter().map(|p| p.pos.x).max().unwrap() > to.x;
+        for seed in 0..20 {
+            let path = Trajectory::new(seed)
+                .with_jitter(0.0)
+                .with_curvature(0.0)
+                .generate(FROM, to);
+            check(&path, FROM, to);
+            // 越过 1000 * 0.06 * (0.5-1.0) 像素后再修正回来
+            assert!(past(&path), "seed {seed}");
+            let max = path.iter().map(|p| p.pos.x).max().unwrap();
+            assert!(max <= to.x + 61, "seed {seed}: {max}");
+
+            let path = Trajectory::new(seed)
+                .with_jitter(0.0)
+                .with_curvature(0.0)
+                .with_overshoot(0.0, 0.0)
+                .generate(FROM, to);
+            check(&path, FROM, to);
+            assert!(!past(&path), "seed {seed}");
+        }
+        // 距离小于触发距离时不越过
+        let path = Trajectory::new(3)
+            .with_jitter(0.0)
+            .with_curvature(0.0)
+            .generate(FROM, Point::new(210, 20));
+        assert!(path.iter().all(|p| p.pos.x <= 210));
+    }
+
+    #[test]
+    fn move_r_deltas_sum_to_target() {
+        for seed in 0..20 {
+            let to = Point::new(-300 + seed as i32 * 37, 450);
+            let path = Trajectory::new(seed).generate(FROM, to);
+            let (dx, dy) =
+                moves(&path, MoveMethod::MoveR).fold((0, 0), |(x, y), (_, d)| (x + d.x, y + d.y));
+            assert_eq!(Point::new(FROM.x + dx, FROM.y + dy), to);
+
+            let abs: Vec<(Duration, Point)> = moves(&path, MoveMethod::MoveTo).collect();
+            assert_eq!(abs.len(), path.len() - 1);
+            assert_eq!(abs.last().unwrap().1, to);
+        }
+    }
+}
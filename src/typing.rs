@@ -0,0 +1,298 @@
+//! 输入文本: 按字符选择输入方式, 可以设置每个字符之间的延时
+//!
+//! 美式键盘上可以直接按出的字符(字母, 数字, 符号, 空格, 换行, 制表符)用按键输入, 需要时按住 shift;
+//! 其他字符(比如中文)用 `SendString`, 输入法或者剪贴板粘贴输入.
+
+use std::time::Duration;
+
+use crate::{check, Dmsoft, Error, KeyMap, Result};
+
+/// 需要按住 shift 输入的字符和对应的按键
+static SHIFTED: [(char, char); 21] = [
+    ('!', '1'),
+    ('@', '2'),
+    ('#', '3'),
+    ('$', '4'),
+    ('%', '5'),
+    ('^', '6'),
+    ('&', '7'),
+    ('*', '8'),
+    ('(', '9'),
+    (')', '0'),
+    ('_', '-'),
+    ('+', '='),
+    ('{', '['),
+    ('}', ']'),
+    ('|', '\\'),
+    (':', ';'),
+    ('"', '\''),
+    ('<', ','),
+    ('>', '.'),
+    ('?', '/'),
+    ('~', '`'),
+];
+
+/// 美式键盘上输入`c`需要按的键, 以及是否需要按住 shift. 无法直接按出时返回 `None`
+/// # Examples
+/// ```
+/// assert_eq!(typing::char_key('A'), Some((KeyMap::KEY_A, true)));
+/// assert_eq!(typing::char_key('['), Some((KeyMap::KEY_OPEN_BRACKET, false)));
+/// assert_eq!(typing::char_key('中'), None);
+/// ```
+pub fn char_key(c: char) -> Option<(KeyMap<'static>, bool)> {
+    let (base, shift) = match SHIFTED.iter().find(|&&(shifted, _)| shifted == c) {
+        Some(&(_, base)) => (base, true),
+        None => (c, c.is_ascii_uppercase()),
+    };
+    let key = match base {
+        ' ' => KeyMap::KEY_SPACE,
+        '\n' => KeyMap::KEY_ENTER,
+        '\t' => KeyMap::KEY_TAB,
+        '`' => KeyMap::KEY_WAVY_LINES,
+        c if c.is_ascii_graphic() => c.to_string().parse().ok()?,
+        _ => return None,
+    };
+    Some((key, shift))
+}
+
+/// 单个字符的输入方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeMethod {
+    /// `KeyPress` 按虚拟键码, 需要时用 `KeyDown` 按住 shift. 只能输入 [`char_key`] 支持的字符
+    Vk,
+    /// `KeyPressChar` 按键名, 需要时用 `KeyDown` 按住 shift. 只能输入 [`char_key`] 支持的字符
+    KeyPressChar,
+    /// `SendString` 向窗口句柄发送文本, 0表示当前激活的窗口
+    SendString(i32),
+    /// `SendString2` 向窗口句柄发送文本, 0表示当前激活的窗口
+    SendString2(i32),
+    /// `SendStringIme` 通过输入法向绑定的窗口发送文本
+    Ime,
+    /// `SetClipboard` 设置剪贴板后 `SendPaste` 粘贴到窗口句柄, 会覆盖剪贴板原来的内容
+    Paste(i32),
+}
+
+impl TypeMethod {
+    /// 是否通过按键输入
+    pub fn is_key(&self) -> bool {
+        matches!(self, TypeMethod::Vk | TypeMethod::KeyPressChar)
+    }
+}
+
+/// 输入文本的选项
+/// # Examples
+/// ```
+/// let options = TypeOptions {
+///     other: TypeMethod::Paste(hwnd),
+///     char_delay: Duration::from_millis(80),
+///     ..TypeOptions::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TypeOptions {
+    /// 输入 ASCII 字符的方式, 默认为 [`TypeMethod::Vk`]. 按键无法输入的控制字符使用`other`
+    pub ascii: TypeMethod,
+    /// 输入其他字符的方式, 默认为 [`TypeMethod::SendString`] 发送到当前激活的窗口
+    ///
+    /// 不能是按键方式([`TypeMethod::is_key`]), 否则文本中有非 ASCII 字符时 [`Dmsoft::type_text`] 返回
+    /// [`Error::CannotType`]
+    pub other: TypeMethod,
+    /// 每个字符之间的间隔, 默认为50毫秒. 为0时连续的非按键输入的字符合并为一次调用
+    pub char_delay: Duration,
+    /// 按住 shift 和按下按键之间的间隔, 默认为30毫秒
+    pub key_delay: Duration,
+}
+
+impl Default for TypeOptions {
+    fn default() -> Self {
+        Self {
+            ascii: TypeMethod::Vk,
+            other: TypeMethod::SendString(0),
+            char_delay: Duration::from_millis(50),
+            key_delay: Duration::from_millis(30),
+        }
+    }
+}
+
+impl TypeOptions {
+    /// 输入`c`使用的方式
+    pub fn method(&self, c: char) -> TypeMethod {
+        if c.is_ascii() && (!self.ascii.is_key() || char_key(c).is_some()) {
+            self.ascii
+        } else {
+            self.other
+        }
+    }
+
+    /// 把`text`按输入方式分段, 每段为一次调用输入的文本
+    ///
+    /// 按键输入的字符每个一段; `char_delay` 不为0时每个字符一段, 否则合并相同方式的连续字符
+    pub fn plan(&self, text: &str) -> Vec<(TypeMethod, String)> {
+        let mut plan: Vec<(TypeMethod, String)> = Vec::new();
+        for c in text.chars() {
+            let method = self.method(c);
+            match plan.last_mut() {
+                Some((last, s))
+                    if *last == method && !method.is_key() && self.char_delay.is_zero() =>
+                {
+                    s.push(c)
+                }
+                _ => plan.push((method, c.to_string())),
+            }
+        }
+        plan
+    }
+}
+
+/// 输入文本辅助函数
+impl Dmsoft {
+    /// 按 [`TypeOptions`] 逐个字符输入`text`, 遇到失败时停止并返回 [`Error::CallFailed`]
+    ///
+    /// 输入之前先检查所有字符, 有按键输入方式无法输入的字符([`char_key`] 返回 `None`)时不输入任何字符,
+    /// 返回 [`Error::CannotType`]
+    /// # Examples
+    /// ```
+    /// let dm = Dmsoft::new();
+    /// dm.type_text("Hello, 世界!\n", &TypeOptions::default()).unwrap();
+    /// ```
+    pub unsafe fn type_text(&self, text: &str, options: &TypeOptions) -> Result<()> {
+        let plan = options.plan(text);
+        let untypable = plan
+            .iter()
+            .filter(|(method, _)| method.is_key())
+            .flat_map(|(_, s)| s.chars())
+            .find(|&c| char_key(c).is_none());
+        if let Some(c) = untypable {
+            return Err(Error::CannotType(c));
+        }
+        for (i, (method, s)) in plan.iter().enumerate() {
+            if i > 0 {
+                self.wait(options.char_delay)?;
+            }
+            self.type_with(*method, s, options.key_delay)?;
+        }
+        Ok(())
+    }
+
+    /// 用`method`输入一段文本
+    unsafe fn type_with(&self, method: TypeMethod, s: &str, key_delay: Duration) -> Result<()> {
+        match method {
+            TypeMethod::SendString(hwnd) => check(self.SendString(hwnd, s), "SendString"),
+            TypeMethod::SendString2(hwnd) => check(self.SendString2(hwnd, s), "SendString2"),
+            TypeMethod::Ime => check(self.SendStringIme(s), "SendStringIme"),
+            TypeMethod::Paste(hwnd) => {
+                check(self.SetClipboard(s), "SetClipboard")?;
+                check(self.SendPaste(hwnd), "SendPaste")
+            }
+            TypeMethod::Vk | TypeMethod::KeyPressChar => {
+                for c in s.chars() {
+                    let (key, shift) = char_key(c).ok_or(Error::CannotType(c))?;
+                    let shift = if shift {
+                        let guard = self.key_down_guard(KeyMap::KEY_SHIFT)?;
+                        self.wait(key_delay)?;
+                        Some(guard)
+                    } else {
+                        None
+                    };
+                    match method {
+                        TypeMethod::Vk => check(self.KeyPress(&key), "KeyPress")?,
+                        _ => check(self.KeyPressChar(key.get_key_str()), "KeyPressChar")?,
+                    }
+                    if let Some(guard) = shift {
+                        self.wait(key_delay)?;
+                        guard.release()?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn char_keys() {
+        assert_eq!(char_key('a'), Some((KeyMap::KEY_A, false)));
+        assert_eq!(char_key('A'), Some((KeyMap::KEY_A, true)));
+        assert_eq!(char_key('Z'), Some((KeyMap::KEY_Z, true)));
+        assert_eq!(char_key('7'), Some((KeyMap::KEY_7, false)));
+        assert_eq!(char_key('&'), Some((KeyMap::KEY_7, true)));
+        assert_eq!(char_key('_'), Some((KeyMap::KEY_MINUS, true)));
+        assert_eq!(char_key('"'), Some((KeyMap::KEY_SINGLE_QUOTES, true)));
+        assert_eq!(char_key('|'), Some((KeyMap::KEY_BACKSLASH, true)));
+        assert_eq!(char_key('~'), Some((KeyMap::KEY_WAVY_LINES, true)));
+        assert_eq!(char_key('`'), Some((KeyMap::KEY_WAVY_LINES, false)));
+        assert_eq!(char_key(' '), Some((KeyMap::KEY_SPACE, false)));
+        assert_eq!(char_key('\n'), Some((KeyMap::KEY_ENTER, false)));
+        assert_eq!(char_key('\t'), Some((KeyMap::KEY_TAB, false)));
+        assert_eq!(char_key('\r'), None);
+        assert_eq!(char_key('中'), None);
+        assert_eq!(char_key('Ａ'), None);
+    }
+
+    #[test]
+    fn every_shifted_symbol() {
+        for &(shifted, base) in &SHIFTED {
+            let (key, shift) = char_key(shifted).unwrap();
+            assert!(shift, "{shifted}");
+            assert_eq!(char_key(base), Some((key, false)), "{shifted}");
+        }
+        // 可以按出的 ASCII 字符都有按键名, 可以用 `KeyPressChar` 输入
+        for c in (' '..='~').chain(['\n', '\t']) {
+            let (key, _) = char_key(c).unwrap();
+            assert!(key.has_key_str(), "{c:?}");
+        }
+    }
+
+    #[test]
+    fn plan_keys_and_other() {
+        let options = TypeOptions::default();
+        assert_eq!(
+            options.plan("aB\n中文"),
+            [
+                (TypeMethod::Vk, "a".to_owned()),
+                (TypeMethod::Vk, "B".to_owned()),
+                (TypeMethod::Vk, "\n".to_owned()),
+                (TypeMethod::SendString(0), "中".to_owned()),
+                (TypeMethod::SendString(0), "文".to_owned()),
+            ]
+        );
+        // 按键无法输入的控制字符使用 `other`
+        assert_eq!(options.method('\r'), TypeMethod::SendString(0));
+        assert_eq!(options.method('\t'), TypeMethod::Vk);
+    }
+
+    #[test]
+    fn plan_merges_without_delay() {
+        let options = TypeOptions {
+            char_delay: Duration::ZERO,
+            ..TypeOptions::default()
+        };
+        assert_eq!(
+            options.plan("ab中文\r!"),
+            [
+                (TypeMethod::Vk, "a".to_owned()),
+                (TypeMethod::Vk, "b".to_owned()),
+                (TypeMethod::SendString(0), "中文\r".to_owned()),
+                (TypeMethod::Vk, "!".to_owned()),
+            ]
+        );
+        let options = TypeOptions {
+            ascii: TypeMethod::Ime,
+            other: TypeMethod::Paste(1),
+            char_delay: Duration::ZERO,
+            ..TypeOptions::default()
+        };
+        assert_eq!(
+            options.plan("ab\n中文c"),
+            [
+                (TypeMethod::Ime, "ab\n".to_owned()),
+                (TypeMethod::Paste(1), "中文".to_owned()),
+                (TypeMethod::Ime, "c".to_owned()),
+            ]
+        );
+    }
+}